@@ -1,23 +1,69 @@
-use super::{load_config, print_placeholder, require_api_key, CliResult, Context};
-use serde_json;
-
-pub fn run(ctx: &Context, period: Option<String>) -> CliResult {
-	let config = load_config(&ctx.config_path)?;
-	require_api_key(&config)?;
-
-	if let Some(period) = period {
-		if ctx.json {
-			let payload = serde_json::json!({
-				"status": "not_implemented",
-				"title": "Analytics",
-				"message": "Analytics will be added once the API is available.",
-				"period": period,
-			});
-			return super::output_json(&payload);
-		}
-		print_placeholder(ctx, "Analytics", &format!("Period: {period}"))?;
-		return Ok(());
+use super::{output_json, CliResult, Context};
+use colored::Colorize;
+use tuish::AnalyticsSummary;
+
+pub async fn run(ctx: &Context, period: String) -> CliResult {
+	let client = ctx.client()?;
+	let summary = client.get_analytics(&period).await?;
+
+	if ctx.json {
+		return output_json(&summary);
 	}
-	print_placeholder(ctx, "Analytics", "Analytics will be added once the API is available.")?;
+
+	print_summary(&summary);
 	Ok(())
 }
+
+fn print_summary(summary: &AnalyticsSummary) {
+	println!("{}", format!("Analytics ({})", summary.period).bold());
+	println!();
+	println!("Active licenses: {}", summary.active_licenses);
+	println!("New customers:   {}", summary.new_customers);
+	println!(
+		"Revenue:         {}",
+		format_amount(summary.revenue, &summary.currency)
+	);
+
+	if summary.by_product.is_empty() {
+		return;
+	}
+
+	let name_width = summary
+		.by_product
+		.iter()
+		.map(|product| product.product_name.chars().count())
+		.max()
+		.unwrap_or(0)
+		.max("PRODUCT".len());
+
+	println!();
+	println!(
+		"{}",
+		format!(
+			"{:<name_width$}  {:>8}  {:>8}  {:>14}",
+			"PRODUCT", "ACTIVE", "NEW", "REVENUE"
+		)
+		.dimmed()
+	);
+	for product in &summary.by_product {
+		println!(
+			"{:<name_width$}  {:>8}  {:>8}  {:>14}",
+			product.product_name,
+			product.active_licenses,
+			product.new_customers,
+			format_amount(product.revenue, &summary.currency),
+		);
+	}
+}
+
+/// Render an amount in cents as e.g. `343.00 USD`.
+fn format_amount(cents: i64, currency: &str) -> String {
+	let sign = if cents < 0 { "-" } else { "" };
+	let cents = cents.unsigned_abs();
+	format!(
+		"{sign}{}.{:02} {}",
+		cents / 100,
+		cents % 100,
+		currency.to_uppercase()
+	)
+}
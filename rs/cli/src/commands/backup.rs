@@ -0,0 +1,68 @@
+use super::{output_json, CliError, CliResult, Context, ErrorKind};
+use colored::Colorize;
+use std::path::PathBuf;
+use tuish::Tuish;
+
+const SENSITIVE_NOTICE: &str =
+	"This license key is sensitive: anyone who has it can use your license. Store it like a password.";
+
+pub fn export(ctx: &Context, product_id: String, public_key: String) -> CliResult {
+	let tuish = build(ctx, product_id, public_key)?;
+	let license_key = tuish.export_license().ok_or_else(|| {
+		CliError::new(ErrorKind::NotFound, "no license is cached for this product")
+	})?;
+
+	if ctx.json {
+		let output = serde_json::json!({
+			"licenseKey": license_key,
+			"notice": SENSITIVE_NOTICE,
+		});
+		return output_json(&output);
+	}
+
+	// Warn on stderr so stdout can be redirected straight into a backup file
+	eprintln!("{}", SENSITIVE_NOTICE.yellow().bold());
+	println!("{license_key}");
+	Ok(())
+}
+
+pub fn import(ctx: &Context, file: PathBuf, product_id: String, public_key: String) -> CliResult {
+	let license_key = std::fs::read_to_string(&file).map_err(|e| {
+		CliError::new(
+			ErrorKind::General,
+			format!("failed to read {}: {e}", file.display()),
+		)
+	})?;
+	let mut tuish = build(ctx, product_id, public_key)?;
+	let result = tuish.import_license(&license_key)?;
+
+	if ctx.json {
+		return output_json(&result);
+	}
+
+	if !result.valid {
+		let reason = result
+			.reason
+			.map(|reason| reason.to_string())
+			.unwrap_or_else(|| "unknown".to_string());
+		return Err(CliError::new(
+			ErrorKind::InvalidLicense,
+			format!("license not restored: {reason}"),
+		)
+		.into());
+	}
+
+	println!("{}", "License restored.".green());
+	if let Some(license) = &result.license {
+		println!("{}", format!("License ID: {}", license.id).dimmed());
+	}
+	Ok(())
+}
+
+fn build(ctx: &Context, product_id: String, public_key: String) -> CliResult<Tuish> {
+	Ok(Tuish::builder()
+		.product_id(product_id)
+		.public_key(public_key)
+		.debug(ctx.debug)
+		.build()?)
+}
@@ -0,0 +1,9 @@
+use super::CliResult;
+use clap_complete::Shell;
+use std::io;
+
+pub fn run(mut command: clap::Command, shell: Shell) -> CliResult {
+	let name = command.get_name().to_string();
+	clap_complete::generate(shell, &mut command, name, &mut io::stdout());
+	Ok(())
+}
@@ -0,0 +1,90 @@
+use super::{output_json, resolve_api_key, CliResult, Config, Context};
+use colored::Colorize;
+use serde_json;
+use tuish::DEFAULT_API_URL;
+
+const MIN_API_KEY_LEN: usize = 8;
+
+pub fn show(ctx: &Context) -> CliResult {
+	let config = ctx.load_config()?;
+	let api_key = resolve_api_key(&config);
+	let payload = serde_json::json!({
+		"apiBaseUrl": resolved_base_url(ctx, &config),
+		"apiKeySet": api_key.is_some(),
+		"apiKey": api_key.as_deref().map(mask_api_key),
+	});
+	output_json(&payload)
+}
+
+pub fn path(ctx: &Context) -> CliResult {
+	if ctx.json {
+		let payload = serde_json::json!({ "path": ctx.config_path });
+		return output_json(&payload);
+	}
+	println!("{}", ctx.config_path.display());
+	Ok(())
+}
+
+pub fn validate(ctx: &Context) -> CliResult {
+	let config = ctx.load_config()?;
+	let base_url = resolved_base_url(ctx, &config);
+
+	let mut issues = Vec::new();
+	if !is_valid_base_url(&base_url) {
+		issues.push("api_base_url must be an http(s) URL".to_string());
+	}
+	match resolve_api_key(&config).as_deref() {
+		None => issues.push("no API key set; run tuish login".to_string()),
+		Some(key) if !is_plausible_api_key(key) => {
+			issues.push("api_key does not look like a Tuish API key".to_string());
+		}
+		Some(_) => {}
+	}
+
+	if !issues.is_empty() {
+		return Err(format!("invalid config: {}", issues.join("; ")).into());
+	}
+
+	if ctx.json {
+		let payload = serde_json::json!({
+			"valid": true,
+			"apiBaseUrl": base_url,
+		});
+		return output_json(&payload);
+	}
+	println!("{}", "Config is valid.".green());
+	println!("{}", format!("API Base URL: {base_url}").dimmed());
+	Ok(())
+}
+
+fn resolved_base_url(ctx: &Context, config: &Config) -> String {
+	ctx.api_url
+		.clone()
+		.or_else(|| config.api_base_url.clone())
+		.unwrap_or_else(|| DEFAULT_API_URL.to_string())
+}
+
+fn mask_api_key(key: &str) -> String {
+	let chars: Vec<char> = key.chars().collect();
+	if chars.len() <= 12 {
+		return "****".to_string();
+	}
+	let prefix: String = chars[..4].iter().collect();
+	let suffix: String = chars[chars.len() - 4..].iter().collect();
+	format!("{prefix}****{suffix}")
+}
+
+fn is_valid_base_url(url: &str) -> bool {
+	let Some(rest) = url
+		.strip_prefix("https://")
+		.or_else(|| url.strip_prefix("http://"))
+	else {
+		return false;
+	};
+	let host = rest.split(['/', '?', '#']).next().unwrap_or("");
+	!host.is_empty() && !url.chars().any(char::is_whitespace)
+}
+
+fn is_plausible_api_key(key: &str) -> bool {
+	key.len() >= MIN_API_KEY_LEN && key.chars().all(|c| c.is_ascii_graphic())
+}
@@ -0,0 +1,248 @@
+use super::{delete_config, load_config, save_config, CliResult, Config, Context};
+use std::fmt;
+
+/// Service name the API key is stored under in the OS keyring.
+pub const KEYRING_SERVICE: &str = "tuish-cli";
+const KEYRING_USER: &str = "api_key";
+
+/// Minimal secret store, so credential handling can be tested without an OS keyring.
+pub trait Keyring {
+	fn get(&self) -> Result<Option<String>, KeyringError>;
+	fn set(&self, secret: &str) -> Result<(), KeyringError>;
+	fn delete(&self) -> Result<(), KeyringError>;
+}
+
+/// The keyring could not be used (no backend, locked, access denied, ...).
+#[derive(Debug)]
+pub struct KeyringError(String);
+
+impl fmt::Display for KeyringError {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		write!(f, "keyring unavailable: {}", self.0)
+	}
+}
+
+impl std::error::Error for KeyringError {}
+
+/// The platform keyring (Keychain, Credential Manager, kernel keyutils).
+pub struct OsKeyring;
+
+impl OsKeyring {
+	fn entry(&self) -> Result<keyring::Entry, KeyringError> {
+		keyring::Entry::new(KEYRING_SERVICE, KEYRING_USER)
+			.map_err(|err| KeyringError(err.to_string()))
+	}
+}
+
+impl Keyring for OsKeyring {
+	fn get(&self) -> Result<Option<String>, KeyringError> {
+		match self.entry()?.get_password() {
+			Ok(secret) => Ok(Some(secret)),
+			Err(keyring::Error::NoEntry) => Ok(None),
+			Err(err) => Err(KeyringError(err.to_string())),
+		}
+	}
+
+	fn set(&self, secret: &str) -> Result<(), KeyringError> {
+		self.entry()?
+			.set_password(secret)
+			.map_err(|err| KeyringError(err.to_string()))
+	}
+
+	fn delete(&self) -> Result<(), KeyringError> {
+		match self.entry()?.delete_credential() {
+			Ok(()) | Err(keyring::Error::NoEntry) => Ok(()),
+			Err(err) => Err(KeyringError(err.to_string())),
+		}
+	}
+}
+
+/// Where `store` ended up putting the API key.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeyLocation {
+	ConfigFile,
+	Keyring,
+}
+
+impl KeyLocation {
+	pub fn as_str(self) -> &'static str {
+		match self {
+			Self::ConfigFile => "config",
+			Self::Keyring => "keyring",
+		}
+	}
+}
+
+fn keyring_enabled(ctx: &Context, config: &Config) -> bool {
+	ctx.use_keyring || config.use_keyring
+}
+
+/// Load the config, taking the API key from the keyring when it is enabled.
+///
+/// A key still present in the file (e.g. from an earlier fallback) is used
+/// when the keyring is empty or unavailable.
+pub fn load(ctx: &Context, keyring: &dyn Keyring) -> CliResult<Config> {
+	let mut config = load_config(&ctx.config_path)?;
+	if !keyring_enabled(ctx, &config) {
+		return Ok(config);
+	}
+	match keyring.get() {
+		Ok(Some(secret)) => config.api_key = Some(secret),
+		Ok(None) => {}
+		Err(err) => tracing::warn!("{err}; reading API key from {}", ctx.config_path.display()),
+	}
+	Ok(config)
+}
+
+/// Save `config` with `api_key`, in the keyring when enabled and in the file otherwise.
+///
+/// Only non-secret fields are written to disk when the keyring accepts the key;
+/// if it does not, the key is written to the file with a warning.
+pub fn store(
+	ctx: &Context,
+	keyring: &dyn Keyring,
+	mut config: Config,
+	api_key: String,
+) -> CliResult<KeyLocation> {
+	let mut location = KeyLocation::ConfigFile;
+	if keyring_enabled(ctx, &config) {
+		match keyring.set(&api_key) {
+			Ok(()) => {
+				config.use_keyring = true;
+				location = KeyLocation::Keyring;
+			}
+			Err(err) => tracing::warn!("{err}; saving API key to {}", ctx.config_path.display()),
+		}
+	}
+	config.api_key = match location {
+		KeyLocation::Keyring => None,
+		KeyLocation::ConfigFile => Some(api_key),
+	};
+	save_config(&ctx.config_path, &config)?;
+	Ok(location)
+}
+
+/// Remove the stored API key from the keyring (when enabled) and delete the config file.
+pub fn clear(ctx: &Context, keyring: &dyn Keyring) -> CliResult {
+	let config = load_config(&ctx.config_path).unwrap_or_default();
+	if keyring_enabled(ctx, &config) {
+		if let Err(err) = keyring.delete() {
+			tracing::warn!("{err}; the API key may still be in the keyring");
+		}
+	}
+	delete_config(&ctx.config_path)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use std::cell::RefCell;
+	use std::path::Path;
+
+	#[derive(Default)]
+	struct MemoryKeyring {
+		secret: RefCell<Option<String>>,
+		unavailable: bool,
+	}
+
+	impl MemoryKeyring {
+		fn unavailable() -> Self {
+			Self {
+				unavailable: true,
+				..Self::default()
+			}
+		}
+
+		fn check(&self) -> Result<(), KeyringError> {
+			if self.unavailable {
+				return Err(KeyringError("no backend".into()));
+			}
+			Ok(())
+		}
+	}
+
+	impl Keyring for MemoryKeyring {
+		fn get(&self) -> Result<Option<String>, KeyringError> {
+			self.check()?;
+			Ok(self.secret.borrow().clone())
+		}
+
+		fn set(&self, secret: &str) -> Result<(), KeyringError> {
+			self.check()?;
+			*self.secret.borrow_mut() = Some(secret.to_string());
+			Ok(())
+		}
+
+		fn delete(&self) -> Result<(), KeyringError> {
+			self.check()?;
+			self.secret.borrow_mut().take();
+			Ok(())
+		}
+	}
+
+	fn context(path: &Path, use_keyring: bool) -> Context {
+		Context::new(path.to_path_buf(), None, true).with_keyring(use_keyring)
+	}
+
+	#[test]
+	fn store_retrieve_delete_through_keyring() {
+		let tmp = tempfile::tempdir().unwrap();
+		let path = tmp.path().join("config.json");
+		let ctx = context(&path, true);
+		let keyring = MemoryKeyring::default();
+
+		let config = Config {
+			api_base_url: Some("https://api.example.com".into()),
+			..Config::default()
+		};
+		let location = store(&ctx, &keyring, config, "sk_test_secret".into()).unwrap();
+		assert_eq!(location, KeyLocation::Keyring);
+		assert_eq!(keyring.secret.borrow().as_deref(), Some("sk_test_secret"));
+
+		let on_disk = std::fs::read_to_string(&path).unwrap();
+		assert!(!on_disk.contains("sk_test_secret"));
+		assert!(on_disk.contains("https://api.example.com"));
+
+		// The saved setting keeps the keyring in use without the flag.
+		let ctx = context(&path, false);
+		let loaded = load(&ctx, &keyring).unwrap();
+		assert_eq!(loaded.api_key.as_deref(), Some("sk_test_secret"));
+		assert!(loaded.use_keyring);
+
+		clear(&ctx, &keyring).unwrap();
+		assert!(keyring.secret.borrow().is_none());
+		assert!(!path.exists());
+	}
+
+	#[test]
+	fn store_falls_back_to_file_when_keyring_unavailable() {
+		let tmp = tempfile::tempdir().unwrap();
+		let path = tmp.path().join("config.json");
+		let ctx = context(&path, true);
+		let keyring = MemoryKeyring::unavailable();
+
+		let location = store(&ctx, &keyring, Config::default(), "sk_test_secret".into()).unwrap();
+		assert_eq!(location, KeyLocation::ConfigFile);
+
+		let loaded = load(&ctx, &keyring).unwrap();
+		assert_eq!(loaded.api_key.as_deref(), Some("sk_test_secret"));
+		assert!(!loaded.use_keyring);
+		assert!(clear(&ctx, &keyring).is_ok());
+	}
+
+	#[test]
+	fn keyring_untouched_when_disabled() {
+		let tmp = tempfile::tempdir().unwrap();
+		let path = tmp.path().join("config.json");
+		let ctx = context(&path, false);
+		let keyring = MemoryKeyring::default();
+
+		let location = store(&ctx, &keyring, Config::default(), "sk_test_secret".into()).unwrap();
+		assert_eq!(location, KeyLocation::ConfigFile);
+		assert!(keyring.secret.borrow().is_none());
+		assert_eq!(
+			load(&ctx, &keyring).unwrap().api_key.as_deref(),
+			Some("sk_test_secret")
+		);
+	}
+}
@@ -1,17 +1,115 @@
-use super::{output_json, CliResult, Context};
+use super::{json_envelope, require_api_key, CliResult, Context};
 use colored::Colorize;
 use serde_json;
+use std::io::{self, Write};
+use std::time::Duration;
+use tuish::{LicenseCheckResult, Tuish, DEFAULT_API_URL};
+
+const SPINNER_FRAMES: [char; 4] = ['|', '/', '-', '\\'];
+
+pub async fn run(
+	ctx: &Context,
+	product_id: String,
+	public_key: String,
+	api_key: Option<String>,
+) -> CliResult {
+	let config = ctx.load_config()?;
+	let api_key = match api_key {
+		Some(key) => key,
+		None => require_api_key(&config)?,
+	};
+	let api_url = ctx
+		.api_url
+		.clone()
+		.or(config.api_base_url)
+		.unwrap_or_else(|| DEFAULT_API_URL.to_string());
+
+	let mut tuish = Tuish::builder()
+		.product_id(product_id)
+		.public_key(public_key)
+		.api_key(api_key)
+		.api_url(api_url)
+		.debug(ctx.debug)
+		.build()?;
+
+	if !ctx.json {
+		println!("{}", "Tuish demo".bold());
+	}
+
+	#[cfg(feature = "browser")]
+	let session = tuish.open_checkout(None).await?;
+	#[cfg(not(feature = "browser"))]
+	let session = tuish.purchase_in_browser(None).await?;
+
+	if ctx.json {
+		emit_event(serde_json::json!({
+			"event": "checkout_created",
+			"sessionId": session.session_id,
+			"checkoutUrl": session.checkout_url,
+			"browserOpened": cfg!(feature = "browser"),
+		}))?;
+		emit_event(serde_json::json!({ "event": "waiting" }))?;
+	} else {
+		println!("Checkout URL: {}", session.checkout_url.underline());
+		if !cfg!(feature = "browser") {
+			println!(
+				"{}",
+				"Open the URL above in your browser to continue.".dimmed()
+			);
+		}
+	}
+
+	let spinner = (!ctx.json).then(|| tokio::spawn(spin("Waiting for checkout to complete")));
+	let result = tuish.wait_for_checkout(&session.session_id).await;
+	if let Some(spinner) = spinner {
+		spinner.abort();
+		eprint!("\r\x1b[2K");
+	}
+	let result = result?;
 
-pub fn run(ctx: &Context) -> CliResult {
 	if ctx.json {
-		let payload = serde_json::json!({
-			"status": "not_implemented",
-			"title": "Tuish demo",
-			"message": "Interactive purchase flow demo will be added soon.",
-		});
-		return output_json(&payload);
+		return emit_event(result_event(&result)?);
 	}
-	println!("{}", "Tuish demo".bold());
-	println!("{}", "Interactive purchase flow demo will be added soon.".yellow());
+
+	if result.valid {
+		println!("{}", "License acquired.".green());
+		if let Some(license) = &result.license {
+			println!("{}", format!("License ID: {}", license.id).dimmed());
+			if !license.features.is_empty() {
+				println!(
+					"{}",
+					format!("Features: {}", license.features.join(", ")).dimmed()
+				);
+			}
+		}
+	} else {
+		let reason = result
+			.reason
+			.map(|reason| reason.to_string())
+			.unwrap_or_else(|| "unknown".to_string());
+		println!(
+			"{}",
+			format!("Checkout did not complete: {reason}").yellow()
+		);
+	}
+	Ok(())
+}
+
+fn result_event(result: &LicenseCheckResult) -> CliResult<serde_json::Value> {
+	let mut event = serde_json::to_value(result)?;
+	event["event"] = "result".into();
+	Ok(event)
+}
+
+fn emit_event(event: serde_json::Value) -> CliResult {
+	println!("{}", serde_json::to_string(&json_envelope(&event)?)?);
+	io::stdout().flush()?;
 	Ok(())
 }
+
+async fn spin(message: &'static str) {
+	for frame in SPINNER_FRAMES.iter().cycle() {
+		eprint!("\r{frame} {message}...");
+		tokio::time::sleep(Duration::from_millis(120)).await;
+	}
+}
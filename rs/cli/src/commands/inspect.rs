@@ -0,0 +1,52 @@
+use super::{output_json, CliError, CliResult, Context, ErrorKind};
+use colored::Colorize;
+use serde_json;
+use tuish::extract_license_payload;
+
+const UNVERIFIED_NOTICE: &str = "UNVERIFIED: signature not checked";
+
+pub fn run(ctx: &Context, license_key: String) -> CliResult {
+	let payload = extract_license_payload(license_key.trim())
+		.ok_or_else(|| CliError::new(ErrorKind::InvalidLicense, "not a well-formed license key"))?;
+
+	if ctx.json {
+		let output = serde_json::json!({
+			"verified": false,
+			"notice": UNVERIFIED_NOTICE,
+			"claims": payload,
+		});
+		return output_json(&output);
+	}
+
+	println!("{}", UNVERIFIED_NOTICE.yellow().bold());
+	println!(
+		"{}",
+		"These claims have not been verified and may be forged.".dimmed()
+	);
+	println!();
+	println!("License ID:  {}", payload.lid);
+	println!("Product ID:  {}", payload.pid);
+	println!("Customer ID: {}", payload.cid);
+	let features = if payload.features.is_empty() {
+		"(none)".to_string()
+	} else {
+		payload.features.join(", ")
+	};
+	println!("Features:    {features}");
+	println!("Issued at:   {}", payload.iat);
+	match payload.exp {
+		Some(exp) => println!("Expires at:  {exp}"),
+		None => println!("Expires at:  never"),
+	}
+	if let Some(support_exp) = payload.support_exp {
+		println!("Support to:  {support_exp}");
+	}
+	println!(
+		"Machine ID:  {}",
+		payload.mid.as_deref().unwrap_or("(not bound)")
+	);
+	if !payload.mids.is_empty() {
+		println!("Also bound:  {}", payload.mids.join(", "));
+	}
+	Ok(())
+}
@@ -1,9 +1,9 @@
-use super::{load_config, output_json, require_api_key, CliResult, Context};
+use super::{output_json, require_api_key, CliResult, Context};
 use colored::Colorize;
 use serde_json;
 
 pub fn run(ctx: &Context) -> CliResult {
-	let config = load_config(&ctx.config_path)?;
+	let config = ctx.load_config()?;
 	let api_key = require_api_key(&config)?;
 
 	if ctx.json {
@@ -1,13 +1,15 @@
-use super::{load_config, output_json, save_config, CliResult, Context};
+use super::credentials::{self, KeyLocation, OsKeyring};
+use super::{env_api_key, load_config, output_json, CliError, CliResult, Context, ErrorKind};
 use colored::Colorize;
 use serde_json;
 use std::io::{self, Write};
+use tuish::TuishError;
 
 const DEFAULT_API_BASE_URL: &str = "https://api.tuish.dev";
 
-pub fn run(ctx: &Context, api_key: Option<String>) -> CliResult {
+pub async fn run(ctx: &Context, api_key: Option<String>, skip_verify: bool) -> CliResult {
 	let mut config = load_config(&ctx.config_path)?;
-	let mut key = api_key.unwrap_or_default();
+	let mut key = api_key.or_else(env_api_key).unwrap_or_default();
 
 	if key.trim().is_empty() && ctx.json {
 		return Err("API key is required".into());
@@ -24,24 +26,57 @@ pub fn run(ctx: &Context, api_key: Option<String>) -> CliResult {
 		return Err("api key is required".into());
 	}
 
-	config.api_key = Some(key);
 	if let Some(url) = &ctx.api_url {
 		config.api_base_url = Some(url.clone());
 	} else if config.api_base_url.is_none() {
 		config.api_base_url = Some(DEFAULT_API_BASE_URL.to_string());
 	}
 
-	save_config(&ctx.config_path, &config)?;
+	if !skip_verify {
+		verify_key(
+			ctx,
+			&key,
+			config
+				.api_base_url
+				.as_deref()
+				.unwrap_or(DEFAULT_API_BASE_URL),
+		)
+		.await?;
+	}
+
+	let location = credentials::store(ctx, &OsKeyring, config, key)?;
 
 	if ctx.json {
 		let payload = serde_json::json!({
 			"success": true,
 			"message": "API key stored successfully",
+			"verified": !skip_verify,
+			"storage": location.as_str(),
 		});
 		return output_json(&payload);
 	}
 
-	println!("{}", "Saved credentials.".green());
+	if location == KeyLocation::Keyring {
+		println!("{}", "Saved credentials to the OS keyring.".green());
+	} else {
+		println!("{}", "Saved credentials.".green());
+	}
 	println!("{}", format!("Config: {}", ctx.config_path.display()).dimmed());
 	Ok(())
 }
+
+/// Ping the API with the new key so typos are caught before it is saved.
+async fn verify_key(ctx: &Context, api_key: &str, base_url: &str) -> CliResult {
+	let client = ctx.client_with_key(api_key, base_url)?;
+	match client.ping().await {
+		Ok(()) => Ok(()),
+		Err(TuishError::ApiError { status: 401, .. }) => {
+			Err(CliError::new(ErrorKind::Auth, "invalid API key; nothing was saved").into())
+		}
+		Err(err) => Err(CliError::new(
+			ErrorKind::of(&err),
+			format!("could not verify API key: {err} (use --skip-verify to save it anyway)"),
+		)
+		.into()),
+	}
+}
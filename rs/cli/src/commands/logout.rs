@@ -1,9 +1,10 @@
-use super::{delete_config, output_json, CliResult, Context};
+use super::credentials::{self, OsKeyring};
+use super::{output_json, CliResult, Context};
 use colored::Colorize;
 use serde_json;
 
 pub fn run(ctx: &Context) -> CliResult {
-	delete_config(&ctx.config_path)?;
+	credentials::clear(ctx, &OsKeyring)?;
 	if ctx.json {
 		let payload = serde_json::json!({
 			"success": true,
@@ -3,21 +3,133 @@ use serde::{Deserialize, Serialize};
 use std::error::Error;
 use std::fs;
 use std::path::{Path, PathBuf};
+use std::time::Duration;
+use tracing_subscriber::filter::LevelFilter;
+use tuish::{TuishClient, TuishError, DEFAULT_API_URL};
 
 pub mod analytics;
+pub mod backup;
+pub mod completions;
+pub mod config;
+pub mod credentials;
 pub mod customers;
 pub mod demo;
+pub mod inspect;
 pub mod keys;
 pub mod login;
 pub mod logout;
 pub mod products;
+pub mod whoami;
 
 pub type CliResult<T = ()> = Result<T, Box<dyn Error>>;
 
+/// Class of failure, which decides the process exit code.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ErrorKind {
+	General,
+	Auth,
+	Network,
+	InvalidLicense,
+	NotFound,
+}
+
+impl ErrorKind {
+	/// Classify any error returned by a command.
+	pub fn of(err: &(dyn Error + 'static)) -> Self {
+		if let Some(err) = err.downcast_ref::<CliError>() {
+			return err.kind;
+		}
+		match err.downcast_ref::<TuishError>() {
+			Some(TuishError::ApiError {
+				status: 401 | 403, ..
+			}) => Self::Auth,
+			Some(TuishError::ApiError { status: 404, .. }) => Self::NotFound,
+			Some(TuishError::NetworkError(_)) => Self::Network,
+			Some(
+				TuishError::InvalidLicense(_)
+				| TuishError::ExpiredLicense
+				| TuishError::NotYetValid
+				| TuishError::InvalidSignature
+				| TuishError::InvalidMachineId
+				| TuishError::ExpectationUnmet(_),
+			) => Self::InvalidLicense,
+			_ => Self::General,
+		}
+	}
+
+	/// Process exit code for this class; see `EXIT_CODES_HELP`.
+	pub fn exit_code(self) -> i32 {
+		match self {
+			Self::General => 1,
+			Self::Auth => 3,
+			Self::Network => 4,
+			Self::InvalidLicense => 5,
+			Self::NotFound => 6,
+		}
+	}
+
+	fn code(self) -> &'static str {
+		match self {
+			Self::General => "error",
+			Self::Auth => "unauthorized",
+			Self::Network => "network_error",
+			Self::InvalidLicense => "invalid_license",
+			Self::NotFound => "not_found",
+		}
+	}
+}
+
+/// Exit code table shown in `tuish --help`.
+pub const EXIT_CODES_HELP: &str = "Exit codes:
+  0  success
+  1  general error
+  2  invalid arguments
+  3  not authenticated (missing or rejected API key)
+  4  network error (API unreachable)
+  5  invalid license
+  6  not found";
+
+/// A command error with an explicit class, for failures not covered by `TuishError`.
+#[derive(Debug)]
+pub struct CliError {
+	pub kind: ErrorKind,
+	message: String,
+}
+
+impl CliError {
+	pub fn new(kind: ErrorKind, message: impl Into<String>) -> Self {
+		Self {
+			kind,
+			message: message.into(),
+		}
+	}
+}
+
+impl std::fmt::Display for CliError {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		f.write_str(&self.message)
+	}
+}
+
+impl Error for CliError {}
+
+/// Process exit code for an error returned by a command.
+pub fn exit_code(err: &(dyn Error + 'static)) -> i32 {
+	ErrorKind::of(err).exit_code()
+}
+
+const DEFAULT_TIMEOUT_SECS: u64 = 30;
+
+/// Environment variable consulted for the API key before the config file.
+pub const API_KEY_ENV: &str = "TUISH_API_KEY";
+
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct Config {
 	pub api_key: Option<String>,
 	pub api_base_url: Option<String>,
+	/// Keep the API key in the OS keyring instead of this file.
+	#[serde(default, skip_serializing_if = "std::ops::Not::not")]
+	pub use_keyring: bool,
 }
 
 #[derive(Debug, Clone)]
@@ -25,6 +137,10 @@ pub struct Context {
 	pub config_path: PathBuf,
 	pub api_url: Option<String>,
 	pub json: bool,
+	pub timeout: Duration,
+	pub retries: u32,
+	pub debug: bool,
+	pub use_keyring: bool,
 }
 
 impl Context {
@@ -33,8 +149,81 @@ impl Context {
 			config_path,
 			api_url,
 			json,
+			timeout: Duration::from_secs(DEFAULT_TIMEOUT_SECS),
+			retries: 0,
+			debug: false,
+			use_keyring: false,
 		}
 	}
+
+	/// Turn on the SDK's request/response debug logging.
+	pub fn with_debug(mut self, debug: bool) -> Self {
+		self.debug = debug;
+		self
+	}
+
+	/// Store and read the API key through the OS keyring.
+	pub fn with_keyring(mut self, use_keyring: bool) -> Self {
+		self.use_keyring = use_keyring;
+		self
+	}
+
+	pub fn with_network(mut self, timeout: Duration, retries: u32) -> Self {
+		self.timeout = timeout;
+		self.retries = retries;
+		self
+	}
+
+	/// Load the saved config, with the API key from the keyring when enabled.
+	pub fn load_config(&self) -> CliResult<Config> {
+		credentials::load(self, &credentials::OsKeyring)
+	}
+
+	/// Build an API client from the saved config and the network flags.
+	pub fn client(&self) -> CliResult<TuishClient> {
+		let config = self.load_config()?;
+		let api_key = require_api_key(&config)?;
+		let base_url = self
+			.api_url
+			.as_deref()
+			.or(config.api_base_url.as_deref())
+			.unwrap_or(DEFAULT_API_URL);
+		self.client_with_key(&api_key, base_url)
+	}
+
+	/// Build an API client for an explicit key and URL, using the network flags.
+	pub fn client_with_key(&self, api_key: &str, base_url: &str) -> CliResult<TuishClient> {
+		let client = TuishClient::with_config(base_url, api_key, self.timeout, self.debug)?
+			.with_retries(self.retries);
+		Ok(client)
+	}
+}
+
+/// Send SDK logs to stderr so `--json` output on stdout stays parseable.
+///
+/// Warnings are shown by default; `quiet` limits output to errors and each
+/// `verbose` step adds a level (info, debug, trace).
+pub fn init_logging(quiet: bool, verbose: u8) {
+	let level = match (quiet, verbose) {
+		(true, _) => LevelFilter::ERROR,
+		(false, 0) => LevelFilter::WARN,
+		(false, 1) => LevelFilter::INFO,
+		(false, 2) => LevelFilter::DEBUG,
+		(false, _) => LevelFilter::TRACE,
+	};
+	tracing_subscriber::fmt()
+		.with_max_level(level)
+		.with_writer(std::io::stderr)
+		.with_target(false)
+		.init();
+}
+
+pub fn resolve_timeout(secs: Option<u64>) -> CliResult<Duration> {
+	match secs {
+		Some(0) => Err("--timeout must be greater than zero".into()),
+		Some(secs) => Ok(Duration::from_secs(secs)),
+		None => Ok(Duration::from_secs(DEFAULT_TIMEOUT_SECS)),
+	}
 }
 
 pub fn resolve_config_path(cli_path: Option<PathBuf>) -> CliResult<PathBuf> {
@@ -75,25 +264,65 @@ pub fn delete_config(path: &Path) -> CliResult {
 	Ok(())
 }
 
-pub fn require_api_key(config: &Config) -> CliResult<&str> {
-	config
-		.api_key
-		.as_deref()
-		.ok_or_else(|| "No API key found; run tuish login".into())
+/// Resolve the API key from `TUISH_API_KEY`, falling back to the config file.
+///
+/// Precedence is CLI flag > environment > config; commands that accept an
+/// `--api-key` flag check it before calling this.
+pub fn resolve_api_key(config: &Config) -> Option<String> {
+	env_api_key().or_else(|| config.api_key.clone())
+}
+
+pub fn require_api_key(config: &Config) -> CliResult<String> {
+	resolve_api_key(config)
+		.ok_or_else(|| CliError::new(ErrorKind::Auth, "No API key found; run tuish login").into())
+}
+
+pub fn env_api_key() -> Option<String> {
+	std::env::var(API_KEY_ENV)
+		.ok()
+		.map(|key| key.trim().to_string())
+		.filter(|key| !key.is_empty())
+}
+
+/// Wrap a successful `--json` result as `{ "ok": true, "data": ... }`.
+pub fn json_envelope<T: Serialize>(value: &T) -> CliResult<serde_json::Value> {
+	Ok(serde_json::json!({ "ok": true, "data": serde_json::to_value(value)? }))
 }
 
 pub fn output_json<T: Serialize>(value: &T) -> CliResult {
-	let data = serde_json::to_string_pretty(value)?;
+	let data = serde_json::to_string_pretty(&json_envelope(value)?)?;
 	println!("{data}");
 	Ok(())
 }
 
-pub fn print_json_error(err: &dyn Error) {
-	let payload = serde_json::json!({ "error": err.to_string() });
-	if let Ok(data) = serde_json::to_string_pretty(&payload) {
-		eprintln!("{data}");
+/// Print an error as `{ "ok": false, "error": { "code", "message" } }`.
+pub fn print_json_error(err: &(dyn Error + 'static)) {
+	eprintln!("{}", json_error(err));
+}
+
+fn json_error(err: &(dyn Error + 'static)) -> String {
+	let payload = serde_json::json!({
+		"ok": false,
+		"error": {
+			"code": error_code(err),
+			"message": err.to_string(),
+		},
+	});
+	serde_json::to_string_pretty(&payload).unwrap_or_else(|_| payload.to_string())
+}
+
+/// Machine-readable code for an error, from the `TuishError` variant when there is one.
+pub fn error_code(err: &(dyn Error + 'static)) -> &'static str {
+	if let Some(err) = err.downcast_ref::<CliError>() {
+		err.kind.code()
+	} else if let Some(err) = err.downcast_ref::<TuishError>() {
+		err.code()
+	} else if err.is::<std::io::Error>() {
+		"io_error"
+	} else if err.is::<serde_json::Error>() {
+		"parse_error"
 	} else {
-		eprintln!("{{\"error\":\"{}\"}}", err);
+		"error"
 	}
 }
 
@@ -114,3 +343,21 @@ pub fn print_placeholder(ctx: &Context, title: &str, detail: &str) -> CliResult
 	}
 	Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_json_error_escapes_message() {
+		let err = CliError::new(ErrorKind::NotFound, r#"no license at "C:\Users\me\tuish""#);
+		let parsed: serde_json::Value = serde_json::from_str(&json_error(&err)).unwrap();
+
+		assert_eq!(parsed["ok"], false);
+		assert_eq!(parsed["error"]["code"], error_code(&err));
+		assert_eq!(
+			parsed["error"]["message"],
+			r#"no license at "C:\Users\me\tuish""#
+		);
+	}
+}
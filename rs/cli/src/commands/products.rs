@@ -1,4 +1,4 @@
-use super::{load_config, print_placeholder, require_api_key, CliResult, Context};
+use super::{print_placeholder, require_api_key, CliResult, Context};
 
 pub fn list(ctx: &Context) -> CliResult {
 	ensure_auth(ctx)?;
@@ -25,7 +25,7 @@ pub fn delete(ctx: &Context, _id: String) -> CliResult {
 }
 
 fn ensure_auth(ctx: &Context) -> CliResult {
-	let config = load_config(&ctx.config_path)?;
+	let config = ctx.load_config()?;
 	require_api_key(&config)?;
 	Ok(())
 }
@@ -0,0 +1,18 @@
+use super::{output_json, CliResult, Context};
+use colored::Colorize;
+
+pub async fn run(ctx: &Context) -> CliResult {
+	let client = ctx.client()?;
+	let developer = client.whoami().await?;
+
+	if ctx.json {
+		return output_json(&developer);
+	}
+
+	match &developer.name {
+		Some(name) => println!("{} <{}>", name.bold(), developer.email),
+		None => println!("{}", developer.email.bold()),
+	}
+	println!("{}", format!("Developer ID: {}", developer.id).dimmed());
+	Ok(())
+}
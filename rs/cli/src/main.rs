@@ -1,10 +1,10 @@
 mod commands;
 
-use clap::{Parser, Subcommand};
+use clap::{CommandFactory, Parser, Subcommand};
 use std::path::PathBuf;
 
 #[derive(Parser)]
-#[command(name = "tuish", version, about = "Tuish developer CLI")]
+#[command(name = "tuish", version, about = "Tuish developer CLI", after_help = commands::EXIT_CODES_HELP)]
 struct Cli {
 	/// Path to the config file
 	#[arg(long, global = true)]
@@ -14,10 +14,30 @@ struct Cli {
 	#[arg(long, global = true)]
 	api_url: Option<String>,
 
+	/// Network timeout for API requests, in seconds
+	#[arg(long, global = true, value_name = "SECS")]
+	timeout: Option<u64>,
+
+	/// Number of times to retry API requests after a network failure
+	#[arg(long, global = true, value_name = "N", default_value_t = 0)]
+	retries: u32,
+
 	/// Output JSON (headless mode for scripting)
 	#[arg(long, short = 'j', global = true)]
 	json: bool,
 
+	/// Store and read the API key in the OS keyring instead of the config file
+	#[arg(long, global = true)]
+	use_keyring: bool,
+
+	/// Only log errors to stderr
+	#[arg(long, short = 'q', global = true, conflicts_with = "verbose")]
+	quiet: bool,
+
+	/// Log more detail to stderr (-v info, -vv debug, -vvv trace)
+	#[arg(long, short = 'v', global = true, action = clap::ArgAction::Count)]
+	verbose: u8,
+
 	#[command(subcommand)]
 	command: Command,
 }
@@ -25,11 +45,16 @@ struct Cli {
 #[derive(Subcommand)]
 enum Command {
 	Login {
-		/// API key to store
+		/// API key to store (defaults to $TUISH_API_KEY)
 		#[arg(long)]
 		api_key: Option<String>,
+		/// Save the key without checking it against the API (for offline setup)
+		#[arg(long)]
+		skip_verify: bool,
 	},
 	Logout,
+	/// Show the developer account the stored API key belongs to
+	Whoami,
 	Products {
 		#[command(subcommand)]
 		command: Option<ProductCommand>,
@@ -40,11 +65,54 @@ enum Command {
 	},
 	Keys,
 	Analytics {
-		/// Time window (e.g. 7d, 30d)
+		/// Time window: a number followed by d, w or m (e.g. 7d, 4w, 3m)
+		#[arg(long, default_value = "7d")]
+		period: String,
+	},
+	Demo {
+		/// Product to purchase
+		#[arg(long)]
+		product_id: String,
+		/// Ed25519 public key for verifying the issued license
+		#[arg(long)]
+		public_key: String,
+		/// API key (defaults to $TUISH_API_KEY or the stored key)
 		#[arg(long)]
-		period: Option<String>,
+		api_key: Option<String>,
+	},
+	/// Show a license's claims WITHOUT verifying its signature
+	Inspect {
+		/// License key to decode
+		license_key: String,
+	},
+	/// Print the cached license key for backup (the key is sensitive)
+	Export {
+		/// Product whose license to export
+		#[arg(long)]
+		product_id: String,
+		/// Ed25519 public key the license is verified with
+		#[arg(long)]
+		public_key: String,
+	},
+	/// Restore a license key saved with `tuish export`
+	Import {
+		/// File containing the license key
+		file: PathBuf,
+		/// Product the license is for
+		#[arg(long)]
+		product_id: String,
+		/// Ed25519 public key for verifying the license
+		#[arg(long)]
+		public_key: String,
+	},
+	Config {
+		#[command(subcommand)]
+		command: ConfigCommand,
+	},
+	Completions {
+		/// Shell to generate completions for
+		shell: clap_complete::Shell,
 	},
-	Demo,
 }
 
 #[derive(Subcommand)]
@@ -62,27 +130,46 @@ enum CustomerCommand {
 	Revoke { id: String },
 }
 
+#[derive(Subcommand)]
+enum ConfigCommand {
+	/// Print the resolved config with the API key masked
+	Show,
+	/// Print the config file location
+	Path,
+	/// Check the API URL and API key look well-formed
+	Validate,
+}
+
 #[tokio::main]
 async fn main() {
 	let cli = Cli::parse();
 	let json = cli.json;
+	commands::init_logging(cli.quiet, cli.verbose);
 	if let Err(err) = run(cli).await {
 		if json {
 			commands::print_json_error(err.as_ref());
 		} else {
 			eprintln!("{err}");
 		}
-		std::process::exit(1);
+		std::process::exit(commands::exit_code(err.as_ref()));
 	}
 }
 
 async fn run(cli: Cli) -> commands::CliResult {
 	let config_path = commands::resolve_config_path(cli.config)?;
-	let context = commands::Context::new(config_path, cli.api_url, cli.json);
+	let timeout = commands::resolve_timeout(cli.timeout)?;
+	let context = commands::Context::new(config_path, cli.api_url, cli.json)
+		.with_network(timeout, cli.retries)
+		.with_debug(cli.verbose >= 2)
+		.with_keyring(cli.use_keyring);
 
 	match cli.command {
-		Command::Login { api_key } => commands::login::run(&context, api_key),
+		Command::Login {
+			api_key,
+			skip_verify,
+		} => commands::login::run(&context, api_key, skip_verify).await,
 		Command::Logout => commands::logout::run(&context),
+		Command::Whoami => commands::whoami::run(&context).await,
 		Command::Products { command } => match command.unwrap_or(ProductCommand::List) {
 			ProductCommand::List => commands::products::list(&context),
 			ProductCommand::Create => commands::products::create(&context),
@@ -95,7 +182,27 @@ async fn run(cli: Cli) -> commands::CliResult {
 			CustomerCommand::Revoke { id } => commands::customers::revoke(&context, id),
 		},
 		Command::Keys => commands::keys::run(&context),
-		Command::Analytics { period } => commands::analytics::run(&context, period),
-		Command::Demo => commands::demo::run(&context),
+		Command::Analytics { period } => commands::analytics::run(&context, period).await,
+		Command::Demo {
+			product_id,
+			public_key,
+			api_key,
+		} => commands::demo::run(&context, product_id, public_key, api_key).await,
+		Command::Inspect { license_key } => commands::inspect::run(&context, license_key),
+		Command::Export {
+			product_id,
+			public_key,
+		} => commands::backup::export(&context, product_id, public_key),
+		Command::Import {
+			file,
+			product_id,
+			public_key,
+		} => commands::backup::import(&context, file, product_id, public_key),
+		Command::Config { command } => match command {
+			ConfigCommand::Show => commands::config::show(&context),
+			ConfigCommand::Path => commands::config::path(&context),
+			ConfigCommand::Validate => commands::config::validate(&context),
+		},
+		Command::Completions { shell } => commands::completions::run(Cli::command(), shell),
 	}
 }
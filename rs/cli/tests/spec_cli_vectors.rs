@@ -1,8 +1,14 @@
 use serde::Deserialize;
 use serde_json::Value;
+use std::collections::HashMap;
+use std::io::{Read, Write};
+use std::net::TcpListener;
 use std::path::PathBuf;
 use std::process::Command;
 
+/// Placeholder in case args replaced with the mock API's base URL
+const MOCK_API_URL: &str = "$MOCK_API_URL";
+
 #[derive(Deserialize)]
 struct CliVectors {
 	cases: Vec<CliCase>,
@@ -12,9 +18,20 @@ struct CliVectors {
 struct CliCase {
 	name: String,
 	args: Vec<String>,
+	#[serde(default)]
+	env: HashMap<String, String>,
+	mock_api: Option<MockApi>,
 	expect: CliExpect,
 }
 
+/// Canned response served for every request while a case runs
+#[derive(Deserialize)]
+struct MockApi {
+	status: u16,
+	#[serde(default)]
+	body: Value,
+}
+
 #[derive(Deserialize)]
 struct CliExpect {
 	exit_code: i32,
@@ -35,11 +52,24 @@ fn spec_cli_vectors() -> Result<(), Box<dyn std::error::Error>> {
 		let temp_dir = tempfile::tempdir()?;
 		let config_path = temp_dir.path().join("config.json");
 
+		let args: Vec<String> = match &case.mock_api {
+			Some(mock) => {
+				let url = serve_mock_api(mock)?;
+				case.args
+					.iter()
+					.map(|arg| arg.replace(MOCK_API_URL, &url))
+					.collect()
+			}
+			None => case.args.clone(),
+		};
+
 		let output = Command::new(&bin)
 			.arg("--config")
 			.arg(&config_path)
 			.arg("--json")
-			.args(&case.args)
+			.args(&args)
+			.env_remove("TUISH_API_KEY")
+			.envs(&case.env)
 			.output()?;
 
 		let exit_code = output.status.code().unwrap_or(1);
@@ -62,6 +92,28 @@ fn spec_cli_vectors() -> Result<(), Box<dyn std::error::Error>> {
 	Ok(())
 }
 
+/// Start a background HTTP server that answers every request with `mock`.
+fn serve_mock_api(mock: &MockApi) -> Result<String, Box<dyn std::error::Error>> {
+	let listener = TcpListener::bind("127.0.0.1:0")?;
+	let url = format!("http://{}", listener.local_addr()?);
+	let body = mock.body.to_string();
+	let response = format!(
+		"HTTP/1.1 {} Mock\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+		mock.status,
+		body.len(),
+		body
+	);
+
+	std::thread::spawn(move || {
+		for mut stream in listener.incoming().flatten() {
+			let mut buf = [0u8; 4096];
+			let _ = stream.read(&mut buf);
+			let _ = stream.write_all(response.as_bytes());
+		}
+	});
+	Ok(url)
+}
+
 fn build_cli_bin() -> Result<PathBuf, Box<dyn std::error::Error>> {
 	let manifest_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
 	let status = Command::new("cargo")
@@ -78,3 +130,60 @@ fn build_cli_bin() -> Result<PathBuf, Box<dyn std::error::Error>> {
 	};
 	Ok(manifest_dir.join("target").join("debug").join(bin_name))
 }
+
+#[test]
+fn cli_completions_bash() -> Result<(), Box<dyn std::error::Error>> {
+	let bin = build_cli_bin()?;
+	let output = Command::new(&bin).args(["completions", "bash"]).output()?;
+
+	assert_eq!(output.status.code(), Some(0), "completions exit code");
+	let script = String::from_utf8(output.stdout)?;
+	for subcommand in [
+		"login",
+		"logout",
+		"products",
+		"customers",
+		"keys",
+		"analytics",
+		"demo",
+	] {
+		assert!(
+			script.contains(subcommand),
+			"missing subcommand {subcommand}"
+		);
+	}
+	Ok(())
+}
+
+#[test]
+fn cli_json_envelope() -> Result<(), Box<dyn std::error::Error>> {
+	let bin = build_cli_bin()?;
+	let temp_dir = tempfile::tempdir()?;
+	let config_path = temp_dir.path().join("config.json");
+
+	let success = Command::new(&bin)
+		.arg("--config")
+		.arg(&config_path)
+		.args(["--json", "config", "path"])
+		.output()?;
+	let envelope: Value = serde_json::from_slice(&success.stdout)?;
+	assert_eq!(envelope["ok"], true);
+	assert_eq!(
+		envelope["data"]["path"],
+		config_path.to_string_lossy().as_ref()
+	);
+	assert!(envelope.get("error").is_none());
+
+	let failure = Command::new(&bin)
+		.arg("--config")
+		.arg(&config_path)
+		.args(["--json", "inspect", "eyJub3Q.eyJub3Q.c2ln"])
+		.env_remove("TUISH_API_KEY")
+		.output()?;
+	let envelope: Value = serde_json::from_slice(&failure.stderr)?;
+	assert_eq!(envelope["ok"], false);
+	assert!(envelope.get("data").is_none());
+	assert!(envelope["error"]["code"].is_string());
+	assert!(envelope["error"]["message"].is_string());
+	Ok(())
+}
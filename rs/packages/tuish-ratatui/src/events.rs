@@ -1,7 +1,12 @@
+use std::time::Duration;
+
 use tuish::LicenseCheckResult;
 
 #[derive(Debug, Clone)]
 pub enum LicenseEvent {
 	Checked(LicenseCheckResult),
 	Error(String),
+	/// The license expires within the reminder window, with this much time
+	/// left (see `Tuish::renewal_due`). Emit at most once per session.
+	RenewalDue(Duration),
 }
@@ -0,0 +1,88 @@
+/// Symbol and number of minor-unit digits for currencies with a
+/// well-known sign. Others fall back to `1,234.56 XYZ`.
+const CURRENCIES: &[(&str, &str, u32)] = &[
+	("usd", "$", 2),
+	("eur", "€", 2),
+	("gbp", "£", 2),
+	("jpy", "¥", 0),
+	("krw", "₩", 0),
+	("inr", "₹", 2),
+	("cny", "CN¥", 2),
+	("cad", "CA$", 2),
+	("aud", "A$", 2),
+	("nzd", "NZ$", 2),
+	("brl", "R$", 2),
+	("mxn", "MX$", 2),
+];
+
+/// Render an amount in the currency's minor unit for display, e.g.
+/// `(1999, "usd")` → `$19.99` and `(1999, "jpy")` → `¥1,999`.
+///
+/// Uses English digit grouping for every currency; unknown currency codes
+/// are shown after the amount, e.g. `19.99 XYZ`.
+pub fn format_amount(cents: i64, currency: &str) -> String {
+	let code = currency.to_ascii_lowercase();
+	let known = CURRENCIES.iter().find(|(known, _, _)| *known == code);
+	let decimals = known.map_or(2, |(_, _, decimals)| *decimals);
+
+	let sign = if cents < 0 { "-" } else { "" };
+	let minor = cents.unsigned_abs();
+	let scale = 10u64.pow(decimals);
+	let mut number = group_thousands(minor / scale);
+	if decimals > 0 {
+		number.push_str(&format!(
+			".{:0width$}",
+			minor % scale,
+			width = decimals as usize
+		));
+	}
+
+	match known {
+		Some((_, symbol, _)) => format!("{sign}{symbol}{number}"),
+		None => format!("{sign}{number} {}", currency.to_uppercase()),
+	}
+}
+
+/// `1234567` → `1,234,567`
+fn group_thousands(value: u64) -> String {
+	let digits = value.to_string();
+	let mut grouped = String::with_capacity(digits.len() + digits.len() / 3);
+	for (i, digit) in digits.chars().enumerate() {
+		if i > 0 && (digits.len() - i).is_multiple_of(3) {
+			grouped.push(',');
+		}
+		grouped.push(digit);
+	}
+	grouped
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_usd() {
+		assert_eq!(format_amount(1999, "usd"), "$19.99");
+		assert_eq!(format_amount(5, "USD"), "$0.05");
+		assert_eq!(format_amount(123_456_700, "usd"), "$1,234,567.00");
+		assert_eq!(format_amount(-1999, "usd"), "-$19.99");
+	}
+
+	#[test]
+	fn test_eur() {
+		assert_eq!(format_amount(1999, "eur"), "€19.99");
+		assert_eq!(format_amount(100_000, "eur"), "€1,000.00");
+	}
+
+	#[test]
+	fn test_jpy_has_no_decimals() {
+		assert_eq!(format_amount(1999, "jpy"), "¥1,999");
+		assert_eq!(format_amount(500, "JPY"), "¥500");
+	}
+
+	#[test]
+	fn test_unknown_currency_fallback() {
+		assert_eq!(format_amount(1999, "xyz"), "19.99 XYZ");
+		assert_eq!(format_amount(123_456, "chf"), "1,234.56 CHF");
+	}
+}
@@ -1,9 +1,14 @@
 //! Ratatui widgets for integrating Tuish license state into terminal apps.
 
 mod events;
+mod format;
+mod macros;
+mod registry;
 mod state;
 
 pub mod widgets;
 
 pub use events::LicenseEvent;
+pub use format::format_amount;
+pub use registry::FeatureRegistry;
 pub use state::LicenseState;
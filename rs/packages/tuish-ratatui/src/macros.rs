@@ -0,0 +1,50 @@
+/// Run `else` unless the license grants a feature.
+///
+/// Checks `LicenseState::is_valid` and `LicenseState::has_feature` and,
+/// when the license is invalid (e.g. expired) or lacks the feature, runs the `else` block, which must diverge (`return`, `continue`, ...)
+/// like the `else` of a `let ... else`. Replaces the repeated
+/// `if !state.has_feature(..) { ...; return; }` in render functions.
+///
+/// ```
+/// use tuish::{LicenseCheckResult, LicenseDetails, LicenseStatus, ResultSource};
+/// use tuish_ratatui::{require_feature, LicenseState};
+///
+/// fn export_panel(state: &LicenseState) -> &'static str {
+///     require_feature!(state, "export", else {
+///         return "Export requires Pro";
+///     });
+///     "Export ready"
+/// }
+///
+/// # let license = |valid: bool, features: &[&str]| LicenseState::with_result(LicenseCheckResult {
+/// #     valid,
+/// #     license: Some(LicenseDetails {
+/// #         id: "lic_1".to_string(),
+/// #         product_id: "prod_1".to_string(),
+/// #         product_name: None,
+/// #         features: features.iter().map(|f| f.to_string()).collect(),
+/// #         status: if valid { LicenseStatus::Active } else { LicenseStatus::Expired },
+/// #         issued_at: 0,
+/// #         expires_at: None,
+/// #         support_expires_at: None,
+/// #     }),
+/// #     reason: (!valid).then_some(tuish::LicenseInvalidReason::Expired),
+/// #     source: ResultSource::Offline,
+/// #     stale: false,
+/// #     mismatch_detail: None,
+/// #     warning: None,
+/// # });
+/// assert_eq!(export_panel(&license(true, &["export"])), "Export ready");
+/// assert_eq!(export_panel(&license(true, &["sync"])), "Export requires Pro");
+/// assert_eq!(export_panel(&license(false, &["export"])), "Export requires Pro");
+/// assert_eq!(export_panel(&LicenseState::new()), "Export requires Pro");
+/// ```
+#[macro_export]
+macro_rules! require_feature {
+	($state:expr, $feature:expr, else $else:block) => {
+		let true = ({
+			let state: &$crate::LicenseState = &$state;
+			state.is_valid() && state.has_feature($feature)
+		}) else $else;
+	};
+}
@@ -0,0 +1,105 @@
+/// The feature flags an app checks, with labels for display.
+///
+/// Register every string passed to `has_feature`/`FeatureGate` in one
+/// place, then diff a license against it to spot flags that drifted
+/// between the app and the product's license configuration.
+#[derive(Debug, Clone, Default)]
+pub struct FeatureRegistry {
+	features: Vec<(String, String)>,
+}
+
+impl FeatureRegistry {
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Register `feature` with a user-facing label, replacing any earlier
+	/// label for the same feature.
+	pub fn register(&mut self, feature: impl Into<String>, label: impl Into<String>) -> &mut Self {
+		let feature = feature.into();
+		let label = label.into();
+		match self
+			.features
+			.iter_mut()
+			.find(|(known, _)| *known == feature)
+		{
+			Some(entry) => entry.1 = label,
+			None => self.features.push((feature, label)),
+		}
+		self
+	}
+
+	pub fn is_registered(&self, feature: &str) -> bool {
+		self.label(feature).is_some()
+	}
+
+	pub fn label(&self, feature: &str) -> Option<&str> {
+		self.features
+			.iter()
+			.find(|(known, _)| known == feature)
+			.map(|(_, label)| label.as_str())
+	}
+
+	/// The registered label, or the raw flag for unregistered features.
+	pub fn display_name<'a>(&'a self, feature: &'a str) -> &'a str {
+		self.label(feature).unwrap_or(feature)
+	}
+
+	/// Registered features in registration order.
+	pub fn features(&self) -> impl Iterator<Item = &str> {
+		self.features.iter().map(|(feature, _)| feature.as_str())
+	}
+
+	/// Registered features the license doesn't grant, e.g. a flag the app
+	/// checks but the product never issues.
+	pub fn unknown_in_license(&self, license_features: &[String]) -> Vec<&str> {
+		self.features()
+			.filter(|feature| !license_features.iter().any(|granted| granted == feature))
+			.collect()
+	}
+
+	/// Features the license grants that no registered flag checks, e.g. a
+	/// renamed or forgotten flag.
+	pub fn unused_in_app<'a>(&self, license_features: &'a [String]) -> Vec<&'a str> {
+		license_features
+			.iter()
+			.map(String::as_str)
+			.filter(|feature| !self.is_registered(feature))
+			.collect()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn registry() -> FeatureRegistry {
+		let mut registry = FeatureRegistry::new();
+		registry
+			.register("pro", "Pro tools")
+			.register("sync", "Cloud sync");
+		registry
+	}
+
+	#[test]
+	fn test_register_and_label() {
+		let mut registry = registry();
+		registry.register("pro", "Pro toolkit");
+
+		assert_eq!(registry.features().collect::<Vec<_>>(), vec!["pro", "sync"]);
+		assert_eq!(registry.label("pro"), Some("Pro toolkit"));
+		assert_eq!(registry.label("beta"), None);
+		assert_eq!(registry.display_name("sync"), "Cloud sync");
+		assert_eq!(registry.display_name("beta"), "beta");
+	}
+
+	#[test]
+	fn test_diff_against_license() {
+		let registry = registry();
+		let license = vec!["pro".to_string(), "export".to_string()];
+
+		assert_eq!(registry.unknown_in_license(&license), vec!["sync"]);
+		assert_eq!(registry.unused_in_app(&license), vec!["export"]);
+		assert!(registry.unused_in_app(&[]).is_empty());
+	}
+}
@@ -1,4 +1,6 @@
 use tuish::LicenseCheckResult;
+#[cfg(feature = "sdk")]
+use tuish::Tuish;
 
 #[derive(Debug, Clone, Default)]
 pub struct LicenseState {
@@ -18,10 +20,56 @@ impl LicenseState {
 		}
 	}
 
+	/// Check the cached license with `tuish` and wrap the result.
+	#[cfg(feature = "sdk")]
+	pub fn from_tuish(tuish: &mut Tuish) -> Self {
+		Self::with_result(tuish.check_license())
+	}
+
+	/// Re-check the cached license, replacing the result and clearing any error.
+	#[cfg(feature = "sdk")]
+	pub fn refresh(&mut self, tuish: &mut Tuish) {
+		*self = Self::from_tuish(tuish);
+	}
+
+	/// One-word summary shown by the status widgets.
+	pub fn status_label(&self) -> &'static str {
+		match (self.is_valid(), self.is_trial()) {
+			(true, true) => "Licensed (trial)",
+			(true, false) => "Licensed",
+			(false, _) => "Unlicensed",
+		}
+	}
+
+	/// How to fix an invalid license, from the reason it was rejected.
+	pub fn remediation(&self) -> Option<&'static str> {
+		self.result
+			.as_ref()
+			.filter(|result| !result.valid)
+			.and_then(|result| result.reason)
+			.map(|reason| reason.remediation())
+	}
+
 	pub fn is_valid(&self) -> bool {
 		self.result.as_ref().is_some_and(|result| result.valid)
 	}
 
+	pub fn is_trial(&self) -> bool {
+		self.result
+			.as_ref()
+			.and_then(|result| result.license.as_ref())
+			.is_some_and(|license| license.is_trial())
+	}
+
+	/// Whether the license's support/updates window is open.
+	pub fn support_active(&self) -> bool {
+		self.result
+			.as_ref()
+			.filter(|result| result.valid)
+			.and_then(|result| result.license.as_ref())
+			.is_some_and(|license| license.support_active())
+	}
+
 	pub fn has_feature(&self, feature: &str) -> bool {
 		let Some(result) = &self.result else {
 			return false;
@@ -32,3 +80,81 @@ impl LicenseState {
 		license.features.iter().any(|item| item == feature)
 	}
 }
+
+#[cfg(test)]
+pub(crate) mod tests {
+	use super::*;
+	#[cfg(feature = "sdk")]
+	use tempfile::TempDir;
+	#[cfg(feature = "sdk")]
+	use tuish::signing::{generate_keypair, sign_license};
+	#[cfg(feature = "sdk")]
+	use tuish::LicensePayload;
+	use tuish::{LicenseDetails, LicenseStatus, ResultSource};
+
+	/// A valid, non-expiring license for `prod_1` with `features`.
+	pub(crate) fn licensed(features: &[&str]) -> LicenseState {
+		LicenseState::with_result(LicenseCheckResult {
+			valid: true,
+			license: Some(LicenseDetails {
+				id: "lic_1".to_string(),
+				product_id: "prod_1".to_string(),
+				product_name: None,
+				features: features.iter().map(|f| f.to_string()).collect(),
+				status: LicenseStatus::Active,
+				issued_at: 0,
+				expires_at: None,
+				support_expires_at: None,
+			}),
+			reason: None,
+			source: ResultSource::Offline,
+			stale: false,
+			mismatch_detail: None,
+			warning: None,
+		})
+	}
+
+	#[cfg(feature = "sdk")]
+	fn test_tuish(storage: &TempDir, public_key: &str) -> Tuish {
+		Tuish::builder()
+			.product_id("prod_test")
+			.public_key(public_key)
+			.storage_dir(storage.path().to_string_lossy().to_string())
+			.build()
+			.unwrap()
+	}
+
+	#[cfg(feature = "sdk")]
+	#[test]
+	fn test_from_tuish_and_refresh() {
+		let (signing_key, public_key) = generate_keypair(Some([7u8; 32]));
+		let payload = LicensePayload {
+			lid: "lic_1".to_string(),
+			pid: "prod_test".to_string(),
+			cid: "cus_1".to_string(),
+			did: "dev_1".to_string(),
+			features: vec!["pro".to_string()],
+			iat: 0,
+			exp: None,
+			mid: None,
+			mids: Vec::new(),
+			entitlements: Vec::new(),
+			support_exp: None,
+			nbf: None,
+			feature_groups: Vec::new(),
+		};
+		let license = sign_license(&payload, &signing_key).unwrap();
+		let storage = TempDir::new().unwrap();
+		let mut tuish = test_tuish(&storage, &public_key);
+
+		let mut state = LicenseState::from_tuish(&mut tuish);
+		assert!(!state.is_valid());
+
+		tuish.import_license(&license).unwrap();
+		state.error = Some("offline".to_string());
+		state.refresh(&mut tuish);
+		assert!(state.is_valid());
+		assert!(state.has_feature("pro"));
+		assert!(state.error.is_none());
+	}
+}
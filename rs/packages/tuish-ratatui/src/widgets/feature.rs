@@ -1,4 +1,4 @@
-use crate::LicenseState;
+use crate::{FeatureRegistry, LicenseState};
 use ratatui::buffer::Buffer;
 use ratatui::layout::Rect;
 use ratatui::text::Line;
@@ -7,24 +7,48 @@ use ratatui::widgets::{Block, Borders, Paragraph, Widget};
 pub struct FeatureGate<'a> {
 	state: &'a LicenseState,
 	feature: &'a str,
+	require_support: bool,
+	registry: Option<&'a FeatureRegistry>,
 }
 
 impl<'a> FeatureGate<'a> {
 	pub fn new(state: &'a LicenseState, feature: &'a str) -> Self {
-		Self { state, feature }
+		Self {
+			state,
+			feature,
+			require_support: false,
+			registry: None,
+		}
+	}
+
+	/// Show the feature's registered label instead of the raw flag.
+	pub fn registry(mut self, registry: &'a FeatureRegistry) -> Self {
+		self.registry = Some(registry);
+		self
+	}
+
+	/// Only unlock the feature while the support/updates window is open,
+	/// e.g. for features shipped after a perpetual license's support lapsed.
+	pub fn require_support(mut self, require: bool) -> Self {
+		self.require_support = require;
+		self
 	}
 
 	pub fn enabled(&self) -> bool {
 		self.state.has_feature(self.feature)
+			&& (!self.require_support || self.state.support_active())
 	}
 }
 
 impl<'a> Widget for FeatureGate<'a> {
 	fn render(self, area: Rect, buf: &mut Buffer) {
+		let name = self
+			.registry
+			.map_or(self.feature, |registry| registry.display_name(self.feature));
 		let message = if self.enabled() {
-			format!("Feature unlocked: {}", self.feature)
+			format!("Feature unlocked: {name}")
 		} else {
-			format!("Feature locked: {}", self.feature)
+			format!("Feature locked: {name}")
 		};
 		let block = Block::default().borders(Borders::ALL).title("Feature");
 		Paragraph::new(vec![Line::from(message)])
@@ -32,3 +56,22 @@ impl<'a> Widget for FeatureGate<'a> {
 			.render(area, buf);
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::widgets::text::tests::render_rows;
+
+	#[test]
+	fn test_renders_registered_label() {
+		let state = LicenseState::new();
+		let mut registry = FeatureRegistry::new();
+		registry.register("pro", "Pro tools");
+
+		let rows = render_rows(FeatureGate::new(&state, "pro").registry(&registry), 30, 3);
+		assert_eq!(rows[1], "│Feature locked: Pro tools   │");
+
+		let rows = render_rows(FeatureGate::new(&state, "pro"), 30, 3);
+		assert_eq!(rows[1], "│Feature locked: pro         │");
+	}
+}
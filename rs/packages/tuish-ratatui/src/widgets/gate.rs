@@ -24,6 +24,8 @@ impl<'a> LicenseGate<'a> {
 		self
 	}
 
+	/// Message for unlicensed users, in place of the rejection reason's
+	/// remediation hint.
 	pub fn unlicensed(mut self, message: &'a str) -> Self {
 		self.unlicensed = Some(message);
 		self
@@ -35,7 +37,9 @@ impl<'a> Widget for LicenseGate<'a> {
 		let message = if self.state.is_valid() {
 			self.licensed.unwrap_or("Licensed")
 		} else {
-			self.unlicensed.unwrap_or("License required")
+			self.unlicensed
+				.or(self.state.remediation())
+				.unwrap_or("License required")
 		};
 
 		let block = Block::default().borders(Borders::ALL).title("License Gate");
@@ -44,3 +48,36 @@ impl<'a> Widget for LicenseGate<'a> {
 			.render(area, buf);
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::widgets::text::tests::render_rows;
+	use tuish::{LicenseCheckResult, LicenseInvalidReason, ResultSource};
+
+	fn rejected(reason: LicenseInvalidReason) -> LicenseState {
+		LicenseState::with_result(LicenseCheckResult {
+			valid: false,
+			license: None,
+			reason: Some(reason),
+			source: ResultSource::Offline,
+			stale: false,
+			mismatch_detail: None,
+			warning: None,
+		})
+	}
+
+	#[test]
+	fn test_unlicensed_defaults_to_remediation() {
+		let state = rejected(LicenseInvalidReason::Expired);
+		let rows = render_rows(LicenseGate::new(&state), 30, 3);
+		assert_eq!(rows[1], "│Renew your subscription     │");
+
+		let rows = render_rows(LicenseGate::new(&state).unlicensed("Upgrade to Pro"), 30, 3);
+		assert_eq!(rows[1], "│Upgrade to Pro              │");
+
+		let state = LicenseState::new();
+		let rows = render_rows(LicenseGate::new(&state), 30, 3);
+		assert_eq!(rows[1], "│License required            │");
+	}
+}
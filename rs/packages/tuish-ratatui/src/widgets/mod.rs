@@ -1,9 +1,12 @@
 mod feature;
 mod gate;
+mod panel;
 mod purchase;
 mod status;
+mod text;
 
 pub use feature::FeatureGate;
 pub use gate::LicenseGate;
+pub use panel::{LicensePanel, LicensePanelAction, LicensePanelState};
 pub use purchase::PurchaseFlow;
 pub use status::LicenseStatus;
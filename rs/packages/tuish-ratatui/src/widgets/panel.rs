@@ -0,0 +1,231 @@
+use crate::LicenseState;
+use crossterm::event::{Event, KeyCode, KeyEventKind};
+use ratatui::buffer::Buffer;
+use ratatui::layout::{Constraint, Layout, Rect};
+use ratatui::style::{Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Paragraph, StatefulWidget, Widget, Wrap};
+
+use super::text::fit_words;
+
+/// Something the user asked the panel to do; the host runs it with the SDK.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LicensePanelAction {
+	/// Re-validate the license online (`Tuish::refresh_now`)
+	Refresh,
+	/// Start a purchase (`Tuish::purchase_in_browser` or the terminal flow)
+	Buy,
+	/// Restore a license bought on another machine or backed up earlier
+	/// (`Tuish::import_license`)
+	Restore,
+	/// Release this machine's seat (`Tuish::deactivate`)
+	Deactivate,
+}
+
+impl LicensePanelAction {
+	/// Buttons in display order.
+	pub const ALL: [Self; 4] = [Self::Refresh, Self::Buy, Self::Restore, Self::Deactivate];
+
+	pub fn label(self) -> &'static str {
+		match self {
+			Self::Refresh => "Refresh",
+			Self::Buy => "Buy",
+			Self::Restore => "Restore",
+			Self::Deactivate => "Deactivate",
+		}
+	}
+}
+
+/// Cursor state for a `LicensePanel`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LicensePanelState {
+	selected: usize,
+}
+
+impl LicensePanelState {
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// The highlighted button.
+	pub fn selected(&self) -> LicensePanelAction {
+		LicensePanelAction::ALL[self.selected]
+	}
+
+	pub fn select_next(&mut self) {
+		self.selected = (self.selected + 1) % LicensePanelAction::ALL.len();
+	}
+
+	pub fn select_previous(&mut self) {
+		let count = LicensePanelAction::ALL.len();
+		self.selected = (self.selected + count - 1) % count;
+	}
+
+	/// Move the cursor with arrows or Tab; Enter or Space returns the
+	/// highlighted action. Other events are ignored.
+	pub fn handle_event(&mut self, event: &Event) -> Option<LicensePanelAction> {
+		let Event::Key(key) = event else {
+			return None;
+		};
+		if key.kind != KeyEventKind::Press {
+			return None;
+		}
+
+		match key.code {
+			KeyCode::Right | KeyCode::Down | KeyCode::Tab => self.select_next(),
+			KeyCode::Left | KeyCode::Up | KeyCode::BackTab => self.select_previous(),
+			KeyCode::Enter | KeyCode::Char(' ') => return Some(self.selected()),
+			_ => {}
+		}
+		None
+	}
+}
+
+/// Self-contained license panel: status, feature list and action buttons.
+///
+/// Rendering only reads the `LicenseState` and the cursor; feed key events
+/// to `LicensePanelState::handle_event` and run the returned action.
+pub struct LicensePanel<'a> {
+	state: &'a LicenseState,
+	title: Option<&'a str>,
+}
+
+impl<'a> LicensePanel<'a> {
+	pub fn new(state: &'a LicenseState) -> Self {
+		Self { state, title: None }
+	}
+
+	pub fn title(mut self, title: &'a str) -> Self {
+		self.title = Some(title);
+		self
+	}
+}
+
+impl<'a> StatefulWidget for LicensePanel<'a> {
+	type State = LicensePanelState;
+
+	fn render(self, area: Rect, buf: &mut Buffer, state: &mut Self::State) {
+		let block = Block::default()
+			.borders(Borders::ALL)
+			.title(self.title.unwrap_or("License"));
+		let inner = block.inner(area);
+		block.render(area, buf);
+
+		let [body, buttons] =
+			Layout::vertical([Constraint::Min(0), Constraint::Length(1)]).areas(inner);
+
+		let mut lines = vec![Line::from(self.state.status_label())];
+		let features = self
+			.state
+			.result
+			.as_ref()
+			.and_then(|result| result.license.as_ref())
+			.map(|license| license.features.as_slice())
+			.unwrap_or_default();
+		if !features.is_empty() {
+			lines.push(Line::from("Features:"));
+			for feature in features {
+				lines.push(Line::from(fit_words(&format!("• {feature}"), body.width)));
+			}
+		}
+		if let Some(error) = &self.state.error {
+			lines.push(Line::from(fit_words(
+				&format!("Error: {error}"),
+				body.width,
+			)));
+		}
+		Paragraph::new(lines)
+			.wrap(Wrap { trim: true })
+			.render(body, buf);
+
+		let mut spans = Vec::new();
+		for action in LicensePanelAction::ALL {
+			if !spans.is_empty() {
+				spans.push(Span::raw(" "));
+			}
+			let style = if action == state.selected() {
+				Style::default().add_modifier(Modifier::REVERSED)
+			} else {
+				Style::default()
+			};
+			spans.push(Span::styled(format!("[{}]", action.label()), style));
+		}
+		Line::from(spans).render(buttons, buf);
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::state::tests::licensed;
+	use crate::widgets::text::tests::{render_buffer, render_rows, Stateful};
+	use crossterm::event::{KeyEvent, KeyModifiers};
+
+	fn key(code: KeyCode) -> Event {
+		Event::Key(KeyEvent::new(code, KeyModifiers::NONE))
+	}
+
+	#[test]
+	fn test_navigation_wraps_around() {
+		let mut state = LicensePanelState::new();
+		assert_eq!(state.selected(), LicensePanelAction::Refresh);
+
+		assert_eq!(state.handle_event(&key(KeyCode::Right)), None);
+		assert_eq!(state.handle_event(&key(KeyCode::Tab)), None);
+		assert_eq!(state.selected(), LicensePanelAction::Restore);
+
+		state.handle_event(&key(KeyCode::Left));
+		state.handle_event(&key(KeyCode::Up));
+		state.handle_event(&key(KeyCode::Up));
+		assert_eq!(state.selected(), LicensePanelAction::Deactivate);
+
+		state.handle_event(&key(KeyCode::Down));
+		assert_eq!(state.selected(), LicensePanelAction::Refresh);
+	}
+
+	#[test]
+	fn test_enter_emits_selected_action() {
+		let mut state = LicensePanelState::new();
+		state.handle_event(&key(KeyCode::Right));
+
+		assert_eq!(
+			state.handle_event(&key(KeyCode::Enter)),
+			Some(LicensePanelAction::Buy)
+		);
+		assert_eq!(
+			state.handle_event(&key(KeyCode::Char(' '))),
+			Some(LicensePanelAction::Buy)
+		);
+		assert_eq!(state.handle_event(&key(KeyCode::Char('x'))), None);
+
+		let mut release = KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE);
+		release.kind = KeyEventKind::Release;
+		assert_eq!(state.handle_event(&Event::Key(release)), None);
+	}
+
+	#[test]
+	fn test_renders_status_features_and_buttons() {
+		let license = licensed(&["export", "sync"]);
+		let mut state = LicensePanelState::new();
+		let rows = render_rows(Stateful(LicensePanel::new(&license), &mut state), 40, 7);
+
+		assert_eq!(rows[1], "│Licensed                              │");
+		assert_eq!(rows[2], "│Features:                             │");
+		assert_eq!(rows[3], "│• export                              │");
+		assert_eq!(rows[4], "│• sync                                │");
+		assert_eq!(rows[5], "│[Refresh] [Buy] [Restore] [Deactivate]│");
+	}
+
+	#[test]
+	fn test_highlights_only_selected_button() {
+		let license = licensed(&[]);
+		let mut state = LicensePanelState::new();
+		state.handle_event(&key(KeyCode::Up));
+		let buffer = render_buffer(Stateful(LicensePanel::new(&license), &mut state), 40, 3);
+
+		// Buttons start after the left border: "[Refresh] [Buy] [Restore] [Deactivate]"
+		let reversed = |x: u16| buffer[(x, 1)].modifier.contains(Modifier::REVERSED);
+		assert!(reversed(27) && reversed(38));
+		assert!(!reversed(1) && !reversed(11));
+	}
+}
@@ -1,40 +1,127 @@
-use crate::LicenseState;
+use crate::{format_amount, LicenseState};
 use ratatui::buffer::Buffer;
 use ratatui::layout::Rect;
 use ratatui::text::Line;
-use ratatui::widgets::{Block, Borders, Paragraph, Widget};
+use ratatui::widgets::{Block, Borders, Paragraph, Widget, Wrap};
 
+use super::text::fit_words;
+
+/// Purchase prompt that wraps its message to the pane width.
 pub struct PurchaseFlow<'a> {
 	state: &'a LicenseState,
 	message: Option<&'a str>,
+	price: Option<(i64, &'a str)>,
+	scroll: u16,
 }
 
 impl<'a> PurchaseFlow<'a> {
 	pub fn new(state: &'a LicenseState) -> Self {
-		Self { state, message: None }
+		Self {
+			state,
+			message: None,
+			price: None,
+			scroll: 0,
+		}
 	}
 
+	/// Message to show, in place of the rejection reason's remediation hint.
 	pub fn message(mut self, message: &'a str) -> Self {
 		self.message = Some(message);
 		self
 	}
+
+	/// Show the price, in the currency's minor unit (e.g. from
+	/// `PurchaseInitResponse::amount` and `currency`).
+	pub fn price(mut self, amount: i64, currency: &'a str) -> Self {
+		self.price = Some((amount, currency));
+		self
+	}
+
+	/// Skip this many wrapped lines, for content taller than the pane.
+	pub fn scroll(mut self, offset: u16) -> Self {
+		self.scroll = offset;
+		self
+	}
 }
 
 impl<'a> Widget for PurchaseFlow<'a> {
 	fn render(self, area: Rect, buf: &mut Buffer) {
+		let block = Block::default().borders(Borders::ALL).title("Purchase");
+		let width = block.inner(area).width;
+
 		let mut lines = Vec::new();
 		lines.push(Line::from("Purchase flow"));
-		lines.push(Line::from(
+		if let Some((amount, currency)) = self.price {
+			lines.push(Line::from(format!(
+				"Price: {}",
+				format_amount(amount, currency)
+			)));
+		}
+		lines.push(Line::from(fit_words(
 			self
 				.message
+				.or(self.state.remediation())
 				.unwrap_or("Integrate checkout UI for unlicensed users."),
-		));
+			width,
+		)));
 
 		if self.state.is_valid() {
 			lines.push(Line::from("License already active."));
 		}
 
-		let block = Block::default().borders(Borders::ALL).title("Purchase");
-		Paragraph::new(lines).block(block).render(area, buf);
+		Paragraph::new(lines)
+			.block(block)
+			.wrap(Wrap { trim: true })
+			.scroll((self.scroll, 0))
+			.render(area, buf);
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::widgets::text::tests::render_rows;
+
+	#[test]
+	fn test_message_wraps_in_narrow_pane() {
+		let state = LicenseState::new();
+		let rows = render_rows(PurchaseFlow::new(&state), 20, 7);
+
+		assert_eq!(rows[1], "│Purchase flow     │");
+		assert_eq!(rows[2], "│Integrate checkout│");
+		assert_eq!(rows[3], "│UI for unlicensed │");
+		assert_eq!(rows[4], "│users.            │");
+	}
+
+	#[test]
+	fn test_price_is_formatted() {
+		let state = LicenseState::new();
+		let rows = render_rows(
+			PurchaseFlow::new(&state)
+				.price(1999, "usd")
+				.message("Buy now"),
+			20,
+			5,
+		);
+
+		assert_eq!(rows[2], "│Price: $19.99     │");
+		assert_eq!(rows[3], "│Buy now           │");
+	}
+
+	#[test]
+	fn test_message_defaults_to_remediation() {
+		let state = LicenseState::with_result(tuish::LicenseCheckResult {
+			valid: false,
+			license: None,
+			reason: Some(tuish::LicenseInvalidReason::MachineMismatch),
+			source: tuish::ResultSource::Offline,
+			stale: false,
+			mismatch_detail: None,
+			warning: None,
+		});
+		let rows = render_rows(PurchaseFlow::new(&state), 30, 6);
+
+		assert_eq!(rows[2], "│Deactivate on your old      │");
+		assert_eq!(rows[3], "│machine then rebind here    │");
 	}
 }
@@ -1,50 +1,130 @@
-use crate::LicenseState;
+use crate::{FeatureRegistry, LicenseState};
 use ratatui::buffer::Buffer;
 use ratatui::layout::Rect;
 use ratatui::text::Line;
-use ratatui::widgets::{Block, Borders, Paragraph, Widget};
+use ratatui::widgets::{Block, Borders, Paragraph, Widget, Wrap};
 
+use super::text::fit_words;
+
+/// License summary that wraps long feature lists and errors to the pane width.
 pub struct LicenseStatus<'a> {
 	state: &'a LicenseState,
 	title: Option<&'a str>,
+	scroll: u16,
+	registry: Option<&'a FeatureRegistry>,
 }
 
 impl<'a> LicenseStatus<'a> {
 	pub fn new(state: &'a LicenseState) -> Self {
-		Self { state, title: None }
+		Self {
+			state,
+			title: None,
+			scroll: 0,
+			registry: None,
+		}
 	}
 
 	pub fn title(mut self, title: &'a str) -> Self {
 		self.title = Some(title);
 		self
 	}
+
+	/// Skip this many wrapped lines, for content taller than the pane.
+	pub fn scroll(mut self, offset: u16) -> Self {
+		self.scroll = offset;
+		self
+	}
+
+	/// List features by their registered labels instead of the raw flags.
+	pub fn registry(mut self, registry: &'a FeatureRegistry) -> Self {
+		self.registry = Some(registry);
+		self
+	}
 }
 
 impl<'a> Widget for LicenseStatus<'a> {
 	fn render(self, area: Rect, buf: &mut Buffer) {
-		let mut lines = Vec::new();
-		if self.state.is_valid() {
-			lines.push(Line::from("Licensed"));
-		} else {
-			lines.push(Line::from("Unlicensed"));
-		}
+		let block = Block::default().borders(Borders::ALL).title(
+			self.title.unwrap_or("License Status"),
+		);
+		let width = block.inner(area).width;
+
+		let mut lines = vec![Line::from(self.state.status_label())];
 
 		if let Some(result) = &self.state.result {
 			if let Some(license) = &result.license {
 				if !license.features.is_empty() {
-					lines.push(Line::from(format!(
-						"Features: {}",
-						license.features.join(", ")
+					let names: Vec<&str> = license
+						.features
+						.iter()
+						.map(|feature| {
+							self.registry
+								.map_or(feature.as_str(), |registry| registry.display_name(feature))
+						})
+						.collect();
+					lines.push(Line::from(fit_words(
+						&format!("Features: {}", names.join(", ")),
+						width,
 					)));
 				}
 			}
 		} else if let Some(error) = &self.state.error {
-			lines.push(Line::from(format!("Error: {error}")));
+			lines.push(Line::from(fit_words(&format!("Error: {error}"), width)));
 		}
 
-		let block = Block::default().borders(Borders::ALL).title(
-			self.title.unwrap_or("License Status"),
-		);
-		Paragraph::new(lines).block(block).render(area, buf);
+		Paragraph::new(lines)
+			.block(block)
+			.wrap(Wrap { trim: true })
+			.scroll((self.scroll, 0))
+			.render(area, buf);
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::state::tests::licensed;
+	use crate::widgets::text::tests::render_rows;
+
+	#[test]
+	fn test_features_wrap_in_narrow_pane() {
+		let state = licensed(&["alpha", "beta", "gamma", "delta"]);
+		let rows = render_rows(LicenseStatus::new(&state), 20, 6);
+
+		assert_eq!(rows[1], "│Licensed          │");
+		assert_eq!(rows[2], "│Features: alpha,  │");
+		assert_eq!(rows[3], "│beta, gamma, delta│");
+	}
+
+	#[test]
+	fn test_features_use_registry_labels() {
+		let state = licensed(&["pro", "beta"]);
+		let mut registry = FeatureRegistry::new();
+		registry.register("pro", "Pro tools");
+		let rows = render_rows(LicenseStatus::new(&state).registry(&registry), 30, 4);
+
+		assert_eq!(rows[2], "│Features: Pro tools, beta   │");
+	}
+
+	#[test]
+	fn test_long_words_are_truncated() {
+		let state = LicenseState {
+			result: None,
+			error: Some("request to https://api.tuish.dev/v1/licenses/validate failed".to_string()),
+		};
+		let rows = render_rows(LicenseStatus::new(&state), 20, 7);
+
+		assert_eq!(rows[2], "│Error: request to │");
+		assert_eq!(rows[3], "│https://api.tuish…│");
+		assert_eq!(rows[4], "│failed            │");
+	}
+
+	#[test]
+	fn test_scroll_skips_wrapped_lines() {
+		let state = licensed(&["alpha", "beta", "gamma", "delta"]);
+		let rows = render_rows(LicenseStatus::new(&state).scroll(1), 20, 6);
+
+		assert_eq!(rows[1], "│Features: alpha,  │");
+		assert_eq!(rows[2], "│beta, gamma, delta│");
 	}
 }
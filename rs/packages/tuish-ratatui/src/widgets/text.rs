@@ -0,0 +1,65 @@
+/// Shorten words wider than `width` to fit, ending them with an ellipsis.
+///
+/// Wrapping moves words to the next line, but a single word wider than the
+/// pane (a license ID, a URL) would still be split or clipped.
+pub(crate) fn fit_words(text: &str, width: u16) -> String {
+	let width = usize::from(width);
+	if width == 0 {
+		return text.to_string();
+	}
+
+	text.split(' ')
+		.map(|word| {
+			if word.chars().count() <= width {
+				return word.to_string();
+			}
+			let mut short: String = word.chars().take(width - 1).collect();
+			short.push('…');
+			short
+		})
+		.collect::<Vec<_>>()
+		.join(" ")
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+	use super::*;
+	use ratatui::backend::TestBackend;
+	use ratatui::buffer::Buffer;
+	use ratatui::layout::Rect;
+	use ratatui::widgets::{StatefulWidget, Widget};
+	use ratatui::Terminal;
+
+	/// A stateful widget paired with its state, rendered as a plain `Widget`
+	pub(crate) struct Stateful<'s, W: StatefulWidget>(pub W, pub &'s mut W::State);
+
+	impl<W: StatefulWidget> Widget for Stateful<'_, W> {
+		fn render(self, area: Rect, buf: &mut Buffer) {
+			self.0.render(area, buf, self.1);
+		}
+	}
+
+	/// Render `widget` on a `width` x `height` terminal and return its buffer
+	pub(crate) fn render_buffer(widget: impl Widget, width: u16, height: u16) -> Buffer {
+		let mut terminal = Terminal::new(TestBackend::new(width, height)).unwrap();
+		terminal
+			.draw(|frame| frame.render_widget(widget, frame.area()))
+			.unwrap();
+		terminal.backend().buffer().clone()
+	}
+
+	/// Render `widget` on a `width` x `height` terminal and return its rows
+	pub(crate) fn render_rows(widget: impl Widget, width: u16, height: u16) -> Vec<String> {
+		let buffer = render_buffer(widget, width, height);
+		(0..height)
+			.map(|y| (0..width).map(|x| buffer[(x, y)].symbol()).collect())
+			.collect()
+	}
+
+	#[test]
+	fn test_fit_words() {
+		assert_eq!(fit_words("short words stay", 6), "short words stay");
+		assert_eq!(fit_words("id lic_0123456789", 6), "id lic_0…");
+		assert_eq!(fit_words("anything", 0), "anything");
+	}
+}
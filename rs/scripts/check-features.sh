@@ -0,0 +1,21 @@
+#!/usr/bin/env sh
+# Check that the verification core builds without default features and
+# does not pull in an async runtime, HTTP stack, or `tracing`.
+#
+# Run from anywhere; meant for CI rather than `cargo test`.
+set -eu
+
+cd "$(dirname "$0")/.."
+
+cargo check --quiet --lib --no-default-features
+cargo check --quiet --lib --no-default-features --features http,storage
+
+tree=$(cargo tree --no-default-features --edges normal --prefix none)
+for forbidden in tokio reqwest dirs tracing; do
+	if printf '%s\n' "$tree" | grep -q "^$forbidden "; then
+		echo "minimal build depends on $forbidden" >&2
+		exit 1
+	fi
+done
+
+echo "feature checks passed"
@@ -0,0 +1,204 @@
+//! Local audit trail of license activity
+//!
+//! For apps that must keep a record of every license decision. Register an
+//! [`AuditSink`] with `LicenseManager::set_audit_sink` (or set
+//! `TuishConfig::audit_log` for the built-in [`JsonlFileSink`]) and every
+//! check, online refresh and save is reported as an [`AuditEvent`].
+//!
+//! Auditing is off by default. Events carry the license ID but never the
+//! license key.
+//!
+//! # Example
+//!
+//! ```rust,no_run
+//! use tuish::audit::JsonlFileSink;
+//! use tuish::{LicenseManager, TuishConfig};
+//!
+//! # fn example() -> Result<(), tuish::TuishError> {
+//! let mut manager = LicenseManager::new(TuishConfig::new("prod_123", "key..."))?;
+//! manager.set_audit_sink(Box::new(JsonlFileSink::new("/var/log/my-app/licenses.jsonl")));
+//!
+//! // Appends one line: {"timestamp":...,"action":"check","productId":"prod_123",...}
+//! manager.check_license();
+//! # Ok(())
+//! # }
+//! ```
+
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use crate::error::LicenseInvalidReason;
+use crate::logging::warn;
+use crate::types::{LicenseCheckResult, ResultSource};
+
+/// What produced an audit event
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AuditAction {
+    /// Offline check of the cached license
+    Check,
+    /// Online validation of the cached license
+    Refresh,
+    /// A license key was verified and saved
+    Save,
+}
+
+/// One license decision, as passed to an `AuditSink`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AuditEvent {
+    /// When the decision was made (Unix timestamp ms)
+    pub timestamp: i64,
+    /// What produced the decision
+    pub action: AuditAction,
+    /// Product the check was for
+    pub product_id: String,
+    /// ID of the license involved, if one was read
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub license_id: Option<String>,
+    /// Whether the license was valid
+    pub valid: bool,
+    /// Why the license was invalid
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reason: Option<LicenseInvalidReason>,
+    /// Where the result came from
+    pub source: ResultSource,
+    /// Whether a stale cached license was accepted
+    #[serde(default)]
+    pub stale: bool,
+}
+
+impl AuditEvent {
+    /// Describe `result` as an event stamped with the current time
+    pub(crate) fn from_result(
+        action: AuditAction,
+        product_id: &str,
+        result: &LicenseCheckResult,
+    ) -> Self {
+        Self {
+            timestamp: current_time_millis(),
+            action,
+            product_id: product_id.to_string(),
+            license_id: result.license.as_ref().map(|license| license.id.clone()),
+            valid: result.valid,
+            reason: result.reason,
+            source: result.source,
+            stale: result.stale,
+        }
+    }
+}
+
+/// Receives an `AuditEvent` for every license check, refresh and save
+///
+/// `record` runs inline on the checking thread and cannot fail the check;
+/// sinks should handle their own errors and stay quick.
+pub trait AuditSink: Send + Sync {
+    /// Record one event
+    fn record(&self, event: &AuditEvent);
+}
+
+impl fmt::Debug for dyn AuditSink {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("AuditSink")
+    }
+}
+
+/// Appends each event as one line of JSON to a file
+///
+/// The file and its parent directory are created on first use. Write
+/// errors are logged and otherwise ignored, so a full disk never blocks a
+/// license check.
+#[derive(Debug, Clone)]
+pub struct JsonlFileSink {
+    path: PathBuf,
+}
+
+impl JsonlFileSink {
+    /// Create a sink appending to `path`
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+
+    /// The file events are appended to
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    fn append(&self, event: &AuditEvent) -> std::io::Result<()> {
+        let mut line = serde_json::to_string(event)?;
+        line.push('\n');
+
+        if let Some(parent) = self
+            .path
+            .parent()
+            .filter(|parent| !parent.as_os_str().is_empty())
+        {
+            std::fs::create_dir_all(parent)?;
+        }
+        // One write per line, so concurrent appenders don't interleave
+        std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)?
+            .write_all(line.as_bytes())
+    }
+}
+
+impl AuditSink for JsonlFileSink {
+    fn record(&self, event: &AuditEvent) {
+        if let Err(e) = self.append(event) {
+            warn!(path = ?self.path, error = %e, "Failed to write audit event");
+        }
+    }
+}
+
+/// Get the current time in milliseconds since Unix epoch.
+fn current_time_millis() -> i64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_millis() as i64)
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn test_event(action: AuditAction) -> AuditEvent {
+        AuditEvent {
+            timestamp: 1_700_000_000_000,
+            action,
+            product_id: "prod_test".to_string(),
+            license_id: None,
+            valid: false,
+            reason: Some(LicenseInvalidReason::NotFound),
+            source: ResultSource::NotFound,
+            stale: false,
+        }
+    }
+
+    #[test]
+    fn test_jsonl_sink_appends_lines() {
+        let temp_dir = TempDir::new().unwrap();
+        let sink = JsonlFileSink::new(temp_dir.path().join("logs/audit.jsonl"));
+
+        sink.record(&test_event(AuditAction::Check));
+        sink.record(&test_event(AuditAction::Refresh));
+
+        let contents = std::fs::read_to_string(sink.path()).unwrap();
+        let events: Vec<AuditEvent> = contents
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(
+            events,
+            vec![
+                test_event(AuditAction::Check),
+                test_event(AuditAction::Refresh)
+            ]
+        );
+    }
+}
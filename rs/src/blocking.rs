@@ -0,0 +1,547 @@
+//! Blocking HTTP client for the Tuish API
+//!
+//! A synchronous counterpart to [`TuishClient`](crate::TuishClient) for
+//! integrators that do not run an async runtime. It exposes the same
+//! endpoints, sends the same headers, and parses responses with the same
+//! logic as the async client.
+//!
+//! Like `reqwest::blocking`, this client must not be created or used from
+//! inside an async runtime.
+//!
+//! # Example
+//!
+//! ```rust,no_run
+//! use tuish::TuishClientBlocking;
+//!
+//! # fn example() -> Result<(), tuish::TuishError> {
+//! let client = TuishClientBlocking::new("https://api.tuish.dev", "your-api-key")?;
+//!
+//! let status = client.get_checkout_status("sess_xxx")?;
+//! println!("Status: {:?}", status.status);
+//! # Ok(())
+//! # }
+//! ```
+
+use reqwest::blocking::Client;
+use reqwest::StatusCode;
+use serde::{de::DeserializeOwned, Serialize};
+use std::time::Duration;
+
+use crate::client::{
+    health_result, new_idempotency_key, normalize_api_prefix, parse_response, validate_period,
+    AuthMethod, DeprecationNotice, OtpResponse, RequestContext, DEFAULT_API_URL,
+    DEFAULT_TIMEOUT_SECS,
+};
+use crate::error::TuishError;
+use crate::secret::SecretString;
+use crate::types::{
+    AnalyticsSummary, CheckoutInitRequest, CheckoutInitResponse, CheckoutStatusResponse, Developer,
+    LicenseDeactivateRequest, LicenseDeactivateResponse, LicenseRebindRequest,
+    LicenseRebindResponse, LicenseValidateRequest, LicenseValidateResponse, LoginInitRequest,
+    LoginInitResponse, LoginVerifyRequest, LoginVerifyResponse, PurchaseConfirmRequest,
+    PurchaseConfirmResponse, PurchaseInitRequest, PurchaseInitResponse, RevocationListResponse,
+    TrialStartRequest, TrialStartResponse,
+};
+
+/// Blocking HTTP client for the Tuish API
+///
+/// See [`TuishClient`](crate::TuishClient) for endpoint documentation.
+#[derive(Debug, Clone)]
+pub struct TuishClientBlocking {
+    http: Client,
+    context: RequestContext,
+}
+
+impl TuishClientBlocking {
+    /// Create a new blocking client with the default timeout
+    ///
+    /// # Errors
+    ///
+    /// Returns `TuishError::NetworkError` if the HTTP client cannot be built.
+    pub fn new(base_url: &str, api_key: &str) -> Result<Self, TuishError> {
+        Self::with_config(
+            base_url,
+            api_key,
+            Duration::from_secs(DEFAULT_TIMEOUT_SECS),
+            false,
+        )
+    }
+
+    /// Create a new blocking client with custom configuration
+    ///
+    /// # Arguments
+    ///
+    /// * `base_url` - The API base URL
+    /// * `api_key` - Your API key
+    /// * `timeout` - Request timeout duration
+    /// * `debug` - Enable debug logging
+    pub fn with_config(
+        base_url: &str,
+        api_key: &str,
+        timeout: Duration,
+        debug: bool,
+    ) -> Result<Self, TuishError> {
+        let http = Client::builder()
+            .timeout(timeout)
+            .build()
+            .map_err(|e| TuishError::NetworkError(format!("Failed to build HTTP client: {}", e)))?;
+
+        Ok(Self {
+            http,
+            context: RequestContext::new(base_url, api_key, debug),
+        })
+    }
+
+    /// Create a blocking client with the default API URL
+    pub fn with_api_key(api_key: &str) -> Result<Self, TuishError> {
+        Self::new(DEFAULT_API_URL, api_key)
+    }
+
+    /// Set how many times a request is retried after a transport failure
+    pub fn with_retries(mut self, retries: u32) -> Self {
+        self.context.max_retries = retries;
+        self
+    }
+
+    /// Refuse to make any request; see `TuishClient::with_offline`
+    pub fn with_offline(mut self, offline: bool) -> Self {
+        self.context.offline = offline;
+        self
+    }
+
+    /// Mount all endpoints under a path prefix; see `TuishClient::with_api_prefix`
+    pub fn with_api_prefix(mut self, prefix: &str) -> Self {
+        self.context.api_prefix = normalize_api_prefix(prefix);
+        self
+    }
+
+    /// Pin the API version sent as `Tuish-Api-Version`
+    pub fn with_api_version(mut self, version: impl Into<String>) -> Self {
+        self.context.api_version = version.into();
+        self
+    }
+
+    /// Get the API version sent with each request
+    pub fn api_version(&self) -> &str {
+        &self.context.api_version
+    }
+
+    /// Register a callback for deprecation notices
+    pub fn on_deprecation(&mut self, callback: Box<dyn Fn(&DeprecationNotice) + Send + Sync>) {
+        self.context.deprecation.set(callback);
+    }
+
+    /// Set the identity token for authenticated requests
+    pub fn set_identity_token(&mut self, token: Option<String>) {
+        self.context.identity_token = token.map(SecretString::from);
+    }
+
+    /// Get the current identity token
+    pub fn identity_token(&self) -> Option<&str> {
+        self.context.identity_token.as_deref()
+    }
+
+    /// Check if an identity token is set
+    pub fn has_identity_token(&self) -> bool {
+        self.context.identity_token.is_some()
+    }
+
+    /// Set the correlation ID sent as `X-Request-Id` on every request
+    pub fn set_request_id(&mut self, request_id: Option<String>) {
+        self.context.request_id = request_id;
+    }
+
+    /// Get the current correlation ID
+    pub fn request_id(&self) -> Option<&str> {
+        self.context.request_id.as_deref()
+    }
+
+    /// Enable or disable debug logging
+    pub fn set_debug(&mut self, debug: bool) {
+        self.context.debug = debug;
+    }
+
+    // =========================================================================
+    // Internal Request Helpers
+    // =========================================================================
+
+    /// Make a GET request
+    fn get<T: DeserializeOwned>(&self, path: &str, auth: AuthMethod) -> Result<T, TuishError> {
+        self.request(reqwest::Method::GET, path, Option::<()>::None, auth, None)
+    }
+
+    /// Make a POST request
+    fn post<T: DeserializeOwned, B: Serialize>(
+        &self,
+        path: &str,
+        body: Option<B>,
+        auth: AuthMethod,
+    ) -> Result<T, TuishError> {
+        self.request(reqwest::Method::POST, path, body, auth, None)
+    }
+
+    /// Make a POST request carrying an `Idempotency-Key` header
+    fn post_idempotent<T: DeserializeOwned, B: Serialize>(
+        &self,
+        path: &str,
+        body: B,
+        auth: AuthMethod,
+        idempotency_key: &str,
+    ) -> Result<T, TuishError> {
+        self.request(
+            reqwest::Method::POST,
+            path,
+            Some(body),
+            auth,
+            Some(idempotency_key),
+        )
+    }
+
+    /// Make an HTTP request with the specified method, body, and authentication
+    fn request<T: DeserializeOwned, B: Serialize>(
+        &self,
+        method: reqwest::Method,
+        path: &str,
+        body: Option<B>,
+        auth: AuthMethod,
+        idempotency_key: Option<&str>,
+    ) -> Result<T, TuishError> {
+        let (status, response_text) = self.send(method, path, body, auth, idempotency_key)?;
+        parse_response(&response_text, status)
+    }
+
+    /// Send a request and read the response body, whatever its status
+    fn send<B: Serialize>(
+        &self,
+        method: reqwest::Method,
+        path: &str,
+        body: Option<B>,
+        auth: AuthMethod,
+        idempotency_key: Option<&str>,
+    ) -> Result<(StatusCode, String), TuishError> {
+        let (url, headers) = self.context.prepare(&method, path, auth, idempotency_key)?;
+        let mut request = self.http.request(method, &url).headers(headers);
+
+        if let Some(b) = body {
+            request = request.json(&b);
+        }
+
+        let response = self.send_with_retries(request)?;
+        let status = response.status();
+        let headers = response.headers().clone();
+        let response_text = response.text()?;
+        self.context
+            .received(path, &headers, status, &response_text);
+
+        Ok((status, response_text))
+    }
+
+    /// Send a request, retrying transport failures up to `max_retries` times
+    fn send_with_retries(
+        &self,
+        request: reqwest::blocking::RequestBuilder,
+    ) -> Result<reqwest::blocking::Response, TuishError> {
+        let mut attempt = 0;
+        loop {
+            let Some(attempt_request) = request.try_clone() else {
+                return Ok(request.send()?);
+            };
+
+            match attempt_request.send() {
+                Ok(response) => return Ok(response),
+                Err(e) => match self.context.retry_delay(&mut attempt, &e) {
+                    Some(delay) => std::thread::sleep(delay),
+                    None => return Err(e.into()),
+                },
+            }
+        }
+    }
+
+    // =========================================================================
+    // Health Endpoints
+    // =========================================================================
+
+    /// Blocking version of `TuishClient::ping`
+    pub fn ping(&self) -> Result<(), TuishError> {
+        let (status, response_text) = self.send(
+            reqwest::Method::GET,
+            "/v1/health",
+            Option::<()>::None,
+            AuthMethod::ApiKey,
+            None,
+        )?;
+        health_result(status, &response_text)
+    }
+
+    // =========================================================================
+    // Checkout Endpoints
+    // =========================================================================
+
+    /// Blocking version of `TuishClient::init_checkout`
+    pub fn init_checkout(
+        &self,
+        req: CheckoutInitRequest,
+    ) -> Result<CheckoutInitResponse, TuishError> {
+        self.post("/v1/checkout/init", Some(req), AuthMethod::ApiKey)
+    }
+
+    /// Blocking version of `TuishClient::get_checkout_status`
+    pub fn get_checkout_status(
+        &self,
+        session_id: &str,
+    ) -> Result<CheckoutStatusResponse, TuishError> {
+        let path = format!("/v1/checkout/status/{}", session_id);
+        self.get(&path, AuthMethod::None)
+    }
+
+    // =========================================================================
+    // Auth Endpoints
+    // =========================================================================
+
+    /// Blocking version of `TuishClient::request_login_otp`
+    pub fn request_login_otp(&self, email: &str) -> Result<LoginInitResponse, TuishError> {
+        let req = LoginInitRequest {
+            email: email.to_string(),
+        };
+        self.post("/v1/auth/login/init", Some(req), AuthMethod::None)
+    }
+
+    /// Blocking version of `TuishClient::verify_login`
+    ///
+    /// Stores the returned identity token for subsequent requests.
+    pub fn verify_login(
+        &mut self,
+        req: LoginVerifyRequest,
+    ) -> Result<LoginVerifyResponse, TuishError> {
+        let response: LoginVerifyResponse =
+            self.post("/v1/auth/login/verify", Some(req), AuthMethod::None)?;
+        self.context.identity_token = Some(response.identity_token.as_str().into());
+        Ok(response)
+    }
+
+    // =========================================================================
+    // License Endpoints
+    // =========================================================================
+
+    /// Blocking version of `TuishClient::validate_license`
+    pub fn validate_license(
+        &self,
+        req: LicenseValidateRequest,
+    ) -> Result<LicenseValidateResponse, TuishError> {
+        self.post("/v1/licenses/validate", Some(req), AuthMethod::ApiKey)
+    }
+
+    /// Blocking version of `TuishClient::deactivate_license`
+    pub fn deactivate_license(
+        &self,
+        license_key: &str,
+        machine_fingerprint: &str,
+    ) -> Result<LicenseDeactivateResponse, TuishError> {
+        let req = LicenseDeactivateRequest {
+            license_key: license_key.to_string(),
+            machine_fingerprint: machine_fingerprint.to_string(),
+        };
+        let key = new_idempotency_key();
+        self.post_idempotent("/v1/licenses/deactivate", req, AuthMethod::ApiKey, &key)
+    }
+
+    /// Blocking version of `TuishClient::rebind_license`
+    pub fn rebind_license(
+        &self,
+        license_key: &str,
+        machine_fingerprint: &str,
+    ) -> Result<LicenseRebindResponse, TuishError> {
+        let req = LicenseRebindRequest {
+            license_key: license_key.to_string(),
+            machine_fingerprint: machine_fingerprint.to_string(),
+        };
+        self.post("/v1/licenses/rebind", Some(req), AuthMethod::ApiKey)
+    }
+
+    /// Blocking version of `TuishClient::get_revocation_list`
+    pub fn get_revocation_list(
+        &self,
+        product_id: &str,
+    ) -> Result<RevocationListResponse, TuishError> {
+        let path = format!("/v1/products/{}/revocations", product_id);
+        self.get(&path, AuthMethod::ApiKey)
+    }
+
+    // =========================================================================
+    // Trial Endpoints
+    // =========================================================================
+
+    /// Blocking version of `TuishClient::start_trial`
+    pub fn start_trial(
+        &self,
+        product_id: &str,
+        machine_fingerprint: &str,
+    ) -> Result<TrialStartResponse, TuishError> {
+        let req = TrialStartRequest {
+            product_id: product_id.to_string(),
+            machine_fingerprint: machine_fingerprint.to_string(),
+        };
+        let key = new_idempotency_key();
+        self.post_idempotent("/v1/trials/start", req, AuthMethod::ApiKey, &key)
+    }
+
+    // =========================================================================
+    // Account Endpoints
+    // =========================================================================
+
+    /// Blocking version of `TuishClient::whoami`
+    pub fn whoami(&self) -> Result<Developer, TuishError> {
+        self.get("/v1/me", AuthMethod::ApiKey)
+    }
+
+    // =========================================================================
+    // Analytics Endpoints
+    // =========================================================================
+
+    /// Blocking version of `TuishClient::get_analytics`
+    pub fn get_analytics(&self, period: &str) -> Result<AnalyticsSummary, TuishError> {
+        validate_period(period)?;
+        let path = format!("/v1/analytics?period={}", period);
+        self.get(&path, AuthMethod::ApiKey)
+    }
+
+    // =========================================================================
+    // Purchase Endpoints (for returning customers)
+    // =========================================================================
+
+    /// Blocking version of `TuishClient::init_purchase`
+    pub fn init_purchase(&self, product_id: &str) -> Result<PurchaseInitResponse, TuishError> {
+        let req = PurchaseInitRequest {
+            product_id: product_id.to_string(),
+        };
+        self.post("/v1/purchase/init", Some(req), AuthMethod::IdentityToken)
+    }
+
+    /// Blocking version of `TuishClient::request_purchase_otp`
+    pub fn request_purchase_otp(&self) -> Result<OtpResponse, TuishError> {
+        self.post::<OtpResponse, ()>("/v1/purchase/otp", None, AuthMethod::IdentityToken)
+    }
+
+    /// Blocking version of `TuishClient::confirm_purchase`
+    pub fn confirm_purchase(
+        &self,
+        req: PurchaseConfirmRequest,
+    ) -> Result<PurchaseConfirmResponse, TuishError> {
+        let key = req
+            .idempotency_key
+            .clone()
+            .unwrap_or_else(new_idempotency_key);
+        self.post_idempotent("/v1/purchase/confirm", req, AuthMethod::IdentityToken, &key)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::CheckoutStatus;
+    use wiremock::matchers::{header, method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    /// Start a mock server on a runtime that outlives the blocking calls
+    fn start_server() -> (tokio::runtime::Runtime, MockServer) {
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        let server = runtime.block_on(MockServer::start());
+        (runtime, server)
+    }
+
+    #[test]
+    fn test_blocking_client_construction() {
+        let client = TuishClientBlocking::new("https://api.tuish.dev/", "test-api-key").unwrap();
+        assert_eq!(client.context.base_url, "https://api.tuish.dev");
+        assert!(!client.has_identity_token());
+        assert!(client.request_id().is_none());
+    }
+
+    #[test]
+    fn test_blocking_get_checkout_status() {
+        let (runtime, server) = start_server();
+        runtime.block_on(
+            Mock::given(method("GET"))
+                .and(path("/v1/checkout/status/sess_123"))
+                .and(header("X-Request-Id", "req_test"))
+                .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                    "success": true,
+                    "data": { "status": "complete", "license": "lic.key.sig" }
+                })))
+                .expect(1)
+                .mount(&server),
+        );
+
+        let mut client = TuishClientBlocking::new(&server.uri(), "test-api-key").unwrap();
+        client.set_request_id(Some("req_test".to_string()));
+        let status = client.get_checkout_status("sess_123").unwrap();
+
+        assert_eq!(status.status, CheckoutStatus::Complete);
+        assert_eq!(status.license.as_deref(), Some("lic.key.sig"));
+    }
+
+    #[test]
+    fn test_blocking_api_key_and_errors() {
+        let (runtime, server) = start_server();
+        runtime.block_on(
+            Mock::given(method("POST"))
+                .and(path("/v1/checkout/init"))
+                .and(header("X-API-Key", "test-api-key"))
+                .respond_with(ResponseTemplate::new(404).set_body_json(serde_json::json!({
+                    "error": { "message": "Product not found" }
+                })))
+                .mount(&server),
+        );
+
+        let client = TuishClientBlocking::new(&server.uri(), "test-api-key").unwrap();
+        let result = client.init_checkout(CheckoutInitRequest {
+            product_id: "prod_missing".to_string(),
+            email: None,
+            success_url: None,
+            cancel_url: None,
+        });
+
+        match result {
+            Err(TuishError::ApiError { status, message }) => {
+                assert_eq!(status, 404);
+                assert_eq!(message, "Product not found");
+            }
+            other => panic!("expected ApiError, got {:?}", other),
+        }
+
+        // Identity-token endpoints fail locally without a token
+        assert!(matches!(
+            client.request_purchase_otp(),
+            Err(TuishError::ApiError { status: 401, .. })
+        ));
+    }
+
+    #[test]
+    fn test_blocking_ping() {
+        let (runtime, server) = start_server();
+        runtime.block_on(
+            Mock::given(method("GET"))
+                .and(path("/v1/health"))
+                .and(header("X-API-Key", "good-key"))
+                .respond_with(ResponseTemplate::new(200))
+                .mount(&server),
+        );
+        runtime.block_on(
+            Mock::given(method("GET"))
+                .and(path("/v1/health"))
+                .respond_with(ResponseTemplate::new(401))
+                .mount(&server),
+        );
+
+        assert!(TuishClientBlocking::new(&server.uri(), "good-key")
+            .unwrap()
+            .ping()
+            .is_ok());
+        assert!(matches!(
+            TuishClientBlocking::new(&server.uri(), "bad-key")
+                .unwrap()
+                .ping(),
+            Err(TuishError::ApiError { status: 401, .. })
+        ));
+    }
+}
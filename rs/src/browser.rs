@@ -4,7 +4,7 @@
 //! default web browser. Used primarily for the browser checkout flow.
 
 use crate::error::TuishError;
-use tracing::debug;
+use crate::logging::debug;
 
 /// Open a URL in the default web browser
 ///
@@ -110,6 +110,175 @@ pub fn open_url_with(url: &str, browser: &str) -> Result<(), TuishError> {
     Ok(())
 }
 
+/// Path the checkout success redirect is sent to
+#[cfg(all(feature = "browser", feature = "http"))]
+const CALLBACK_PATH: &str = "/callback";
+
+/// Path the checkout cancel redirect is sent to
+#[cfg(all(feature = "browser", feature = "http"))]
+const CANCEL_PATH: &str = "/cancel";
+
+/// Largest request head read from the browser
+#[cfg(all(feature = "browser", feature = "http"))]
+const MAX_REQUEST_HEAD: usize = 8 * 1024;
+
+/// How long a connection may take to send its request head
+#[cfg(all(feature = "browser", feature = "http"))]
+const REQUEST_HEAD_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(5);
+
+/// Where the browser was redirected after checkout
+#[cfg(all(feature = "browser", feature = "http"))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CheckoutRedirect {
+    /// The browser reached the success URL
+    Success,
+    /// The browser reached the cancel URL
+    Cancelled,
+}
+
+/// One-shot loopback HTTP server that detects the checkout redirect
+///
+/// Bind it before creating the checkout session, pass `success_url()` and
+/// `cancel_url()` in `CheckoutOptions`, then `accept()` resolves as soon as
+/// the browser lands on either. Other requests (e.g. `/favicon.ico`) get a
+/// 404 and are ignored.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use tuish::browser::{CheckoutRedirect, RedirectListener};
+///
+/// # async fn example() -> Result<(), tuish::TuishError> {
+/// let listener = RedirectListener::bind(0).await?;
+/// println!("success URL: {}", listener.success_url());
+///
+/// if listener.accept().await? == CheckoutRedirect::Success {
+///     println!("Checkout finished in the browser");
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[cfg(all(feature = "browser", feature = "http"))]
+#[derive(Debug)]
+pub struct RedirectListener {
+    listener: tokio::net::TcpListener,
+    port: u16,
+}
+
+#[cfg(all(feature = "browser", feature = "http"))]
+impl RedirectListener {
+    /// Listen on `127.0.0.1:<port>`; port `0` picks a free port
+    pub async fn bind(port: u16) -> Result<Self, TuishError> {
+        let listener = tokio::net::TcpListener::bind(("127.0.0.1", port))
+            .await
+            .map_err(|e| {
+                TuishError::NetworkError(format!("failed to bind redirect listener: {}", e))
+            })?;
+        let port = listener
+            .local_addr()
+            .map_err(|e| TuishError::NetworkError(e.to_string()))?
+            .port();
+
+        debug!(port = port, "Redirect listener bound");
+        Ok(Self { listener, port })
+    }
+
+    /// The port actually bound
+    pub fn port(&self) -> u16 {
+        self.port
+    }
+
+    /// `http://127.0.0.1:<port>/callback`, for `CheckoutOptions::with_success_url`
+    pub fn success_url(&self) -> String {
+        format!("http://127.0.0.1:{}{}", self.port, CALLBACK_PATH)
+    }
+
+    /// `http://127.0.0.1:<port>/cancel`, for `CheckoutOptions::with_cancel_url`
+    pub fn cancel_url(&self) -> String {
+        format!("http://127.0.0.1:{}{}", self.port, CANCEL_PATH)
+    }
+
+    /// Wait for the browser to hit the success or cancel URL
+    ///
+    /// Replies with a short page telling the user to return to the
+    /// terminal. Connections that stay silent for a few seconds are
+    /// dropped so they can't block the redirect. Has no overall timeout of
+    /// its own; wrap it in `tokio::time::timeout` if needed.
+    pub async fn accept(&self) -> Result<CheckoutRedirect, TuishError> {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        loop {
+            let (mut stream, _) = self
+                .listener
+                .accept()
+                .await
+                .map_err(|e| TuishError::NetworkError(e.to_string()))?;
+
+            let mut head = Vec::new();
+            let read_head = async {
+                let mut buf = [0u8; 1024];
+                while head.len() < MAX_REQUEST_HEAD && !head.windows(4).any(|w| w == b"\r\n\r\n") {
+                    match stream.read(&mut buf).await {
+                        Ok(0) | Err(_) => break,
+                        Ok(n) => head.extend_from_slice(&buf[..n]),
+                    }
+                }
+            };
+            if tokio::time::timeout(REQUEST_HEAD_TIMEOUT, read_head)
+                .await
+                .is_err()
+            {
+                debug!("Dropping idle redirect connection");
+                continue;
+            }
+
+            let redirect = parse_redirect(&head);
+            let (status, body) = match redirect {
+                Some(CheckoutRedirect::Success) => (
+                    "200 OK",
+                    "Purchase complete. You can close this tab and return to the terminal.",
+                ),
+                Some(CheckoutRedirect::Cancelled) => (
+                    "200 OK",
+                    "Checkout cancelled. You can close this tab and return to the terminal.",
+                ),
+                None => ("404 Not Found", "Not found"),
+            };
+            let response = format!(
+                "HTTP/1.1 {}\r\nContent-Type: text/html; charset=utf-8\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                status,
+                body.len(),
+                body
+            );
+            // The redirect counts even if the browser hangs up early
+            let _ = stream.write_all(response.as_bytes()).await;
+            let _ = stream.shutdown().await;
+
+            if let Some(redirect) = redirect {
+                debug!(redirect = ?redirect, "Checkout redirect received");
+                return Ok(redirect);
+            }
+        }
+    }
+}
+
+/// Map a raw request head to the redirect it represents
+#[cfg(all(feature = "browser", feature = "http"))]
+fn parse_redirect(head: &[u8]) -> Option<CheckoutRedirect> {
+    let head = std::str::from_utf8(head).ok()?;
+    let mut parts = head.lines().next()?.split_whitespace();
+    if parts.next()? != "GET" {
+        return None;
+    }
+    let target = parts.next()?;
+    let path = target.split_once('?').map_or(target, |(path, _)| path);
+    match path {
+        CALLBACK_PATH => Some(CheckoutRedirect::Success),
+        CANCEL_PATH => Some(CheckoutRedirect::Cancelled),
+        _ => None,
+    }
+}
+
 /// Check if the browser feature is available
 ///
 /// Returns `true` if the browser feature is enabled and browser
@@ -165,6 +334,78 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[cfg(all(feature = "browser", feature = "http"))]
+    async fn simulate_get(port: u16, target: &str) -> String {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        let mut stream = tokio::net::TcpStream::connect(("127.0.0.1", port))
+            .await
+            .unwrap();
+        let request = format!("GET {} HTTP/1.1\r\nHost: 127.0.0.1\r\n\r\n", target);
+        stream.write_all(request.as_bytes()).await.unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).await.unwrap();
+        response
+    }
+
+    #[tokio::test]
+    #[cfg(all(feature = "browser", feature = "http"))]
+    async fn test_redirect_listener_resolves_on_callback() {
+        let listener = RedirectListener::bind(0).await.unwrap();
+        let port = listener.port();
+        assert_eq!(
+            listener.success_url(),
+            format!("http://127.0.0.1:{}/callback", port)
+        );
+
+        let browser = tokio::spawn(async move {
+            let favicon = simulate_get(port, "/favicon.ico").await;
+            let callback = simulate_get(port, "/callback?session_id=sess_1").await;
+            (favicon, callback)
+        });
+
+        let redirect = listener.accept().await.unwrap();
+        assert_eq!(redirect, CheckoutRedirect::Success);
+
+        let (favicon, callback) = browser.await.unwrap();
+        assert!(favicon.starts_with("HTTP/1.1 404"));
+        assert!(callback.starts_with("HTTP/1.1 200"));
+        assert!(callback.contains("return to the terminal"));
+    }
+
+    #[tokio::test(start_paused = true)]
+    #[cfg(all(feature = "browser", feature = "http"))]
+    async fn test_redirect_listener_drops_idle_connection() {
+        let listener = RedirectListener::bind(0).await.unwrap();
+        let port = listener.port();
+
+        // A connection that never sends a request must not block the redirect
+        let idle = tokio::net::TcpStream::connect(("127.0.0.1", port))
+            .await
+            .unwrap();
+        let browser = tokio::spawn(async move {
+            tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+            simulate_get(port, "/cancel").await
+        });
+
+        let redirect = listener.accept().await.unwrap();
+        assert_eq!(redirect, CheckoutRedirect::Cancelled);
+        assert!(browser.await.unwrap().starts_with("HTTP/1.1 200"));
+        drop(idle);
+    }
+
+    #[test]
+    #[cfg(all(feature = "browser", feature = "http"))]
+    fn test_parse_redirect() {
+        assert_eq!(
+            parse_redirect(b"GET /cancel HTTP/1.1\r\n\r\n"),
+            Some(CheckoutRedirect::Cancelled)
+        );
+        assert_eq!(parse_redirect(b"POST /callback HTTP/1.1\r\n\r\n"), None);
+        assert_eq!(parse_redirect(b"GET /callbackx HTTP/1.1\r\n\r\n"), None);
+        assert_eq!(parse_redirect(b""), None);
+    }
+
     // Note: We don't test actual browser opening in unit tests as it would
     // open a browser window. Integration tests could use a mock browser or
     // verify the URL is properly formatted.
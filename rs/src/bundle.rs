@@ -0,0 +1,264 @@
+//! Portable license bundles
+//!
+//! A bundle wraps a license key in an armored text block so it can be shipped
+//! as a `.tuishlic` file or pasted into an email without getting mangled:
+//!
+//! ```text
+//! -----BEGIN TUISH LICENSE-----
+//! Issued-To: alice@example.com
+//! Notes: Team seat 3 of 5
+//!
+//! eyJhbGciOiJlZDI1NTE5IiwidmVyIjoxfQ.eyJsaWQiOiJsaWNfMTIzIn0.c2ln...
+//! -----END TUISH LICENSE-----
+//! ```
+//!
+//! Headers are optional. The key is wrapped at 64 columns and whitespace is
+//! ignored when reading it back. Text outside the BEGIN/END lines is ignored.
+
+use crate::crypto::is_valid_license_format;
+use crate::error::TuishError;
+
+/// File extension for license bundles (without the leading dot)
+pub const BUNDLE_EXTENSION: &str = "tuishlic";
+
+const BEGIN_LINE: &str = "-----BEGIN TUISH LICENSE-----";
+const END_LINE: &str = "-----END TUISH LICENSE-----";
+const ISSUED_TO_HEADER: &str = "Issued-To";
+const NOTES_HEADER: &str = "Notes";
+const LINE_WIDTH: usize = 64;
+
+/// A license key plus optional metadata, serializable as armored text
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LicenseBundle {
+    /// The license key (header.payload.signature)
+    pub license_key: String,
+
+    /// Who the license was issued to (e.g., an email address)
+    pub issued_to: Option<String>,
+
+    /// Free-form notes
+    pub notes: Option<String>,
+}
+
+impl LicenseBundle {
+    /// Create a bundle for a license key
+    pub fn new(license_key: impl Into<String>) -> Self {
+        Self {
+            license_key: license_key.into(),
+            issued_to: None,
+            notes: None,
+        }
+    }
+
+    /// Set who the license was issued to
+    pub fn with_issued_to(mut self, issued_to: impl Into<String>) -> Self {
+        self.issued_to = Some(issued_to.into());
+        self
+    }
+
+    /// Set free-form notes
+    pub fn with_notes(mut self, notes: impl Into<String>) -> Self {
+        self.notes = Some(notes.into());
+        self
+    }
+
+    /// Check whether `input` contains an armored bundle (a BEGIN line).
+    pub fn is_bundle(input: &str) -> bool {
+        input.lines().any(|line| line.trim() == BEGIN_LINE)
+    }
+
+    /// Render the bundle as armored text.
+    ///
+    /// Line breaks inside header values are replaced with spaces.
+    pub fn to_bundle_string(&self) -> String {
+        let mut out = String::new();
+        out.push_str(BEGIN_LINE);
+        out.push('\n');
+
+        if let Some(issued_to) = &self.issued_to {
+            push_header(&mut out, ISSUED_TO_HEADER, issued_to);
+        }
+        if let Some(notes) = &self.notes {
+            push_header(&mut out, NOTES_HEADER, notes);
+        }
+        out.push('\n');
+
+        let key = self.license_key.trim();
+        for start in (0..key.len()).step_by(LINE_WIDTH) {
+            out.push_str(&key[start..key.len().min(start + LINE_WIDTH)]);
+            out.push('\n');
+        }
+
+        out.push_str(END_LINE);
+        out.push('\n');
+        out
+    }
+
+    /// Parse armored bundle text.
+    ///
+    /// # Errors
+    ///
+    /// Returns `TuishError::InvalidLicense` if the BEGIN/END lines are
+    /// missing, a header line is malformed, or the enclosed key is not a
+    /// well-formed license.
+    pub fn from_bundle_string(input: &str) -> Result<Self, TuishError> {
+        let mut lines = input.lines().map(str::trim);
+
+        if !lines.by_ref().any(|line| line == BEGIN_LINE) {
+            return Err(bundle_error("missing BEGIN line"));
+        }
+
+        let mut bundle = Self::new(String::new());
+        let mut in_headers = true;
+        let mut found_end = false;
+
+        for line in lines.by_ref() {
+            if line == END_LINE {
+                found_end = true;
+                break;
+            }
+
+            if in_headers {
+                if line.is_empty() {
+                    in_headers = false;
+                    continue;
+                }
+                let (name, value) = line
+                    .split_once(':')
+                    .ok_or_else(|| bundle_error("malformed header line"))?;
+                let value = Some(value.trim().to_string());
+                match name.trim() {
+                    ISSUED_TO_HEADER => bundle.issued_to = value,
+                    NOTES_HEADER => bundle.notes = value,
+                    // Unknown headers are ignored for forward compatibility
+                    _ => {}
+                }
+                continue;
+            }
+
+            bundle.license_key.extend(line.split_whitespace());
+        }
+
+        if !found_end {
+            return Err(bundle_error("missing END line"));
+        }
+        if bundle.license_key.is_empty() {
+            return Err(bundle_error("no license key"));
+        }
+        if !is_valid_license_format(&bundle.license_key) {
+            return Err(bundle_error("enclosed license key is malformed"));
+        }
+
+        Ok(bundle)
+    }
+}
+
+fn push_header(out: &mut String, name: &str, value: &str) {
+    let value: String = value
+        .chars()
+        .map(|c| if c == '\r' || c == '\n' { ' ' } else { c })
+        .collect();
+    out.push_str(name);
+    out.push_str(": ");
+    out.push_str(value.trim());
+    out.push('\n');
+}
+
+fn bundle_error(message: &str) -> TuishError {
+    TuishError::InvalidLicense(format!("license bundle: {}", message))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::crypto::to_base64url;
+
+    fn test_license_key() -> String {
+        let header = to_base64url(br#"{"alg":"ed25519","ver":1}"#);
+        let payload = to_base64url(
+            br#"{"lid":"lic_123","pid":"prod_test","cid":"cus_456","did":"dev_789","features":["pro"],"iat":1704067200000,"exp":null,"mid":null}"#,
+        );
+        let sig = to_base64url(&[0u8; 64]);
+        format!("{}.{}.{}", header, payload, sig)
+    }
+
+    #[test]
+    fn test_bundle_roundtrip() {
+        let bundle = LicenseBundle::new(test_license_key())
+            .with_issued_to("alice@example.com")
+            .with_notes("Team seat 3 of 5");
+
+        let text = bundle.to_bundle_string();
+        assert!(text.starts_with(BEGIN_LINE));
+        assert!(text.trim_end().ends_with(END_LINE));
+        assert!(text
+            .lines()
+            .all(|line| line.len() <= LINE_WIDTH || line.starts_with("Notes")));
+
+        let parsed = LicenseBundle::from_bundle_string(&text).unwrap();
+        assert_eq!(parsed, bundle);
+    }
+
+    #[test]
+    fn test_bundle_roundtrip_without_headers() {
+        let bundle = LicenseBundle::new(test_license_key());
+        let parsed = LicenseBundle::from_bundle_string(&bundle.to_bundle_string()).unwrap();
+        assert_eq!(parsed, bundle);
+    }
+
+    #[test]
+    fn test_bundle_multiline_notes_are_flattened() {
+        let bundle = LicenseBundle::new(test_license_key()).with_notes("line one\nline two");
+        let parsed = LicenseBundle::from_bundle_string(&bundle.to_bundle_string()).unwrap();
+        assert_eq!(parsed.notes.as_deref(), Some("line one line two"));
+    }
+
+    #[test]
+    fn test_bundle_tolerates_surrounding_text_and_crlf() {
+        let text = LicenseBundle::new(test_license_key())
+            .with_issued_to("bob")
+            .to_bundle_string()
+            .replace('\n', "\r\n");
+        let email = format!("Hi Bob,\r\n\r\nYour license:\r\n\r\n  {}\r\nThanks!", text);
+
+        let parsed = LicenseBundle::from_bundle_string(&email).unwrap();
+        assert_eq!(parsed.license_key, test_license_key());
+        assert_eq!(parsed.issued_to.as_deref(), Some("bob"));
+    }
+
+    #[test]
+    fn test_is_bundle() {
+        let key = test_license_key();
+        assert!(LicenseBundle::is_bundle(
+            &LicenseBundle::new(&key).to_bundle_string()
+        ));
+        assert!(!LicenseBundle::is_bundle(&key));
+    }
+
+    #[test]
+    fn test_bundle_malformed_input() {
+        let key = test_license_key();
+
+        // Bare key, no armor
+        assert!(LicenseBundle::from_bundle_string(&key).is_err());
+
+        // Missing END line
+        let truncated = format!("{}\n\n{}\n", BEGIN_LINE, key);
+        assert!(LicenseBundle::from_bundle_string(&truncated).is_err());
+
+        // Empty body
+        let empty = format!("{}\n\n{}\n", BEGIN_LINE, END_LINE);
+        assert!(LicenseBundle::from_bundle_string(&empty).is_err());
+
+        // Header without a colon
+        let bad_header = format!("{}\nIssued-To alice\n\n{}\n{}\n", BEGIN_LINE, key, END_LINE);
+        assert!(LicenseBundle::from_bundle_string(&bad_header).is_err());
+
+        // Garbage key
+        let garbage = format!("{}\n\nnot-a-license\n{}\n", BEGIN_LINE, END_LINE);
+        match LicenseBundle::from_bundle_string(&garbage) {
+            Err(TuishError::InvalidLicense(msg)) => assert!(msg.contains("malformed")),
+            other => panic!("expected InvalidLicense, got {:?}", other),
+        }
+    }
+}
@@ -24,24 +24,120 @@
 //! # }
 //! ```
 
-use reqwest::{header, Client, StatusCode};
+use reqwest::{header, Client, ClientBuilder, StatusCode};
 use serde::{de::DeserializeOwned, Serialize};
+use std::sync::Arc;
 use std::time::Duration;
-use tracing::{debug, instrument};
+#[cfg(feature = "tracing")]
+use tracing::instrument;
 
 use crate::error::TuishError;
+use crate::logging::{debug, warn};
+use crate::secret::SecretString;
 use crate::types::{
-    CheckoutInitRequest, CheckoutInitResponse, CheckoutStatusResponse, LicenseValidateRequest,
-    LicenseValidateResponse, LoginInitRequest, LoginInitResponse, LoginVerifyRequest,
-    LoginVerifyResponse, PurchaseConfirmRequest, PurchaseConfirmResponse, PurchaseInitRequest,
-    PurchaseInitResponse,
+    AnalyticsSummary, CheckoutInitRequest, CheckoutInitResponse, CheckoutStatusResponse, Developer,
+    LicenseDeactivateRequest, LicenseDeactivateResponse, LicenseRebindRequest,
+    LicenseRebindResponse, LicenseValidateRequest, LicenseValidateResponse, LoginInitRequest,
+    LoginInitResponse, LoginVerifyRequest, LoginVerifyResponse, PurchaseConfirmRequest,
+    PurchaseConfirmResponse, PurchaseInitRequest, PurchaseInitResponse, RevocationListResponse,
+    TrialStartRequest, TrialStartResponse,
 };
 
 /// Default API base URL
 pub const DEFAULT_API_URL: &str = "https://api.tuish.dev";
 
+/// Normalize an API path prefix to `/segment/...` with no trailing slash
+pub(crate) fn normalize_api_prefix(prefix: &str) -> String {
+    let prefix = prefix.trim_matches('/');
+    if prefix.is_empty() {
+        String::new()
+    } else {
+        format!("/{prefix}")
+    }
+}
+
 /// Default request timeout in seconds
-const DEFAULT_TIMEOUT_SECS: u64 = 30;
+pub(crate) const DEFAULT_TIMEOUT_SECS: u64 = 30;
+
+/// Base delay between retry attempts (multiplied by the attempt number)
+pub(crate) const RETRY_BACKOFF: Duration = Duration::from_millis(250);
+
+/// Header carrying the flow correlation ID on outgoing requests
+pub const REQUEST_ID_HEADER: &str = "X-Request-Id";
+
+/// Header letting the API deduplicate retried non-idempotent requests
+pub const IDEMPOTENCY_KEY_HEADER: &str = "Idempotency-Key";
+
+/// Header pinning the API version on outgoing requests
+pub const API_VERSION_HEADER: &str = "Tuish-Api-Version";
+
+/// Response header the API sets when the requested version is deprecated
+pub const API_DEPRECATED_HEADER: &str = "Tuish-Api-Deprecated";
+
+/// API version this client targets
+pub const DEFAULT_API_VERSION: &str = "1";
+
+/// Deprecation signalled by the API for the pinned version
+///
+/// Passed to the callback set with `TuishClient::on_deprecation`. Requests
+/// still succeed; this is a prompt to upgrade the SDK before the version is
+/// removed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DeprecationNotice {
+    /// The `Tuish-Api-Version` the request was sent with
+    pub api_version: String,
+    /// Path of the request that was answered with the notice
+    pub path: String,
+    /// Value of the `Tuish-Api-Deprecated` header (e.g. a sunset date or link)
+    pub message: String,
+}
+
+/// Callback registered with `on_deprecation`
+type DeprecationCallback = Arc<dyn Fn(&DeprecationNotice) + Send + Sync>;
+
+/// Optional deprecation callback, shared between clones of a client
+#[derive(Clone, Default)]
+pub(crate) struct DeprecationHook(Option<DeprecationCallback>);
+
+impl std::fmt::Debug for DeprecationHook {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(if self.0.is_some() {
+            "Some(<callback>)"
+        } else {
+            "None"
+        })
+    }
+}
+
+impl DeprecationHook {
+    pub(crate) fn set(&mut self, callback: Box<dyn Fn(&DeprecationNotice) + Send + Sync>) {
+        self.0 = Some(Arc::from(callback));
+    }
+
+    /// Log and report a deprecation signalled in `headers`
+    ///
+    /// Shared by the async and blocking clients.
+    pub(crate) fn check(&self, headers: &header::HeaderMap, api_version: &str, path: &str) {
+        let Some(value) = headers.get(API_DEPRECATED_HEADER) else {
+            return;
+        };
+
+        let notice = DeprecationNotice {
+            api_version: api_version.to_string(),
+            path: path.to_string(),
+            message: String::from_utf8_lossy(value.as_bytes()).into_owned(),
+        };
+        warn!(
+            api_version = %notice.api_version,
+            path = %notice.path,
+            message = %notice.message,
+            "Tuish API version is deprecated"
+        );
+        if let Some(callback) = &self.0 {
+            callback(&notice);
+        }
+    }
+}
 
 /// Simple OTP response for purchase flow
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
@@ -53,6 +149,114 @@ pub struct OtpResponse {
     pub expires_in: u32,
 }
 
+/// Settings applied to every request, and the steps around sending it
+///
+/// Shared by the async and blocking clients, which differ only in how the
+/// request is sent and the response body read.
+#[derive(Debug, Clone)]
+pub(crate) struct RequestContext {
+    pub(crate) base_url: String,
+    pub(crate) api_prefix: String,
+    pub(crate) api_key: String,
+    pub(crate) identity_token: Option<SecretString>,
+    pub(crate) request_id: Option<String>,
+    pub(crate) max_retries: u32,
+    pub(crate) offline: bool,
+    pub(crate) api_version: String,
+    pub(crate) deprecation: DeprecationHook,
+    pub(crate) debug: bool,
+}
+
+impl RequestContext {
+    pub(crate) fn new(base_url: &str, api_key: &str, debug: bool) -> Self {
+        Self {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            api_prefix: String::new(),
+            api_key: api_key.to_string(),
+            identity_token: None,
+            request_id: None,
+            max_retries: 0,
+            offline: false,
+            api_version: DEFAULT_API_VERSION.to_string(),
+            deprecation: DeprecationHook::default(),
+            debug,
+        }
+    }
+
+    /// Full URL of an API path such as `/v1/health`
+    fn url(&self, path: &str) -> String {
+        format!("{}{}{}", self.base_url, self.api_prefix, path)
+    }
+
+    /// URL and headers for a request to `path`
+    ///
+    /// Fails with `TuishError::Offline` when requests are disabled, or an
+    /// `ApiError` when a header cannot be built.
+    pub(crate) fn prepare(
+        &self,
+        method: &reqwest::Method,
+        path: &str,
+        auth: AuthMethod,
+        idempotency_key: Option<&str>,
+    ) -> Result<(String, header::HeaderMap), TuishError> {
+        if self.offline {
+            return Err(TuishError::Offline);
+        }
+
+        let url = self.url(path);
+        if self.debug {
+            debug!("[tuish] {} {}", method, url);
+        }
+
+        let mut headers = request_headers(
+            auth,
+            &self.api_key,
+            self.identity_token.as_deref(),
+            self.request_id.as_deref(),
+            &self.api_version,
+        )?;
+        if let Some(key) = idempotency_key {
+            headers.insert(IDEMPOTENCY_KEY_HEADER, idempotency_header(key)?);
+        }
+
+        Ok((url, headers))
+    }
+
+    /// Delay before retrying after `error`, or `None` once retries are spent
+    pub(crate) fn retry_delay(
+        &self,
+        attempt: &mut u32,
+        error: &reqwest::Error,
+    ) -> Option<Duration> {
+        if *attempt >= self.max_retries {
+            return None;
+        }
+
+        *attempt += 1;
+        debug!(attempt = *attempt, error = %error, "Request failed, retrying");
+        Some(RETRY_BACKOFF * *attempt)
+    }
+
+    /// Report deprecation and log the (redacted) response to `path`
+    pub(crate) fn received(
+        &self,
+        path: &str,
+        headers: &header::HeaderMap,
+        status: StatusCode,
+        response_text: &str,
+    ) {
+        self.deprecation.check(headers, &self.api_version, path);
+
+        if self.debug {
+            debug!(
+                "[tuish] Response {}: {}",
+                status,
+                crate::crypto::redact_license_keys(response_text)
+            );
+        }
+    }
+}
+
 /// HTTP client for the Tuish API
 ///
 /// This client handles all HTTP communication with the Tuish API,
@@ -60,10 +264,7 @@ pub struct OtpResponse {
 #[derive(Debug, Clone)]
 pub struct TuishClient {
     http: Client,
-    base_url: String,
-    api_key: String,
-    identity_token: Option<String>,
-    debug: bool,
+    context: RequestContext,
 }
 
 impl TuishClient {
@@ -74,6 +275,11 @@ impl TuishClient {
     /// * `base_url` - The API base URL (e.g., "https://api.tuish.dev")
     /// * `api_key` - Your API key for authenticated requests
     ///
+    /// # Panics
+    ///
+    /// Panics if the HTTP client cannot be built (e.g. the TLS backend fails
+    /// to initialize). Use `try_new` to handle that case as an error instead.
+    ///
     /// # Example
     ///
     /// ```rust
@@ -82,18 +288,37 @@ impl TuishClient {
     /// let client = TuishClient::new("https://api.tuish.dev", "your-api-key");
     /// ```
     pub fn new(base_url: &str, api_key: &str) -> Self {
-        let http = Client::builder()
-            .timeout(Duration::from_secs(DEFAULT_TIMEOUT_SECS))
-            .build()
-            .expect("Failed to build HTTP client");
+        Self::try_new(base_url, api_key).expect("Failed to build HTTP client")
+    }
 
-        Self {
-            http,
-            base_url: base_url.trim_end_matches('/').to_string(),
-            api_key: api_key.to_string(),
-            identity_token: None,
-            debug: false,
-        }
+    /// Create a new Tuish client, returning an error instead of panicking
+    ///
+    /// # Arguments
+    ///
+    /// * `base_url` - The API base URL (e.g., "https://api.tuish.dev")
+    /// * `api_key` - Your API key for authenticated requests
+    ///
+    /// # Errors
+    ///
+    /// Returns `TuishError::NetworkError` if the HTTP client cannot be built.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use tuish::TuishClient;
+    ///
+    /// # fn main() -> Result<(), tuish::TuishError> {
+    /// let client = TuishClient::try_new("https://api.tuish.dev", "your-api-key")?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn try_new(base_url: &str, api_key: &str) -> Result<Self, TuishError> {
+        Self::with_config(
+            base_url,
+            api_key,
+            Duration::from_secs(DEFAULT_TIMEOUT_SECS),
+            false,
+        )
     }
 
     /// Create a new client with custom configuration
@@ -110,17 +335,23 @@ impl TuishClient {
         timeout: Duration,
         debug: bool,
     ) -> Result<Self, TuishError> {
-        let http = Client::builder()
-            .timeout(timeout)
+        Self::from_builder(Client::builder().timeout(timeout), base_url, api_key, debug)
+    }
+
+    /// Build the HTTP client and wrap it, mapping builder failures to errors
+    fn from_builder(
+        builder: ClientBuilder,
+        base_url: &str,
+        api_key: &str,
+        debug: bool,
+    ) -> Result<Self, TuishError> {
+        let http = builder
             .build()
             .map_err(|e| TuishError::NetworkError(format!("Failed to build HTTP client: {}", e)))?;
 
         Ok(Self {
             http,
-            base_url: base_url.trim_end_matches('/').to_string(),
-            api_key: api_key.to_string(),
-            identity_token: None,
-            debug,
+            context: RequestContext::new(base_url, api_key, debug),
         })
     }
 
@@ -129,10 +360,75 @@ impl TuishClient {
     /// # Arguments
     ///
     /// * `api_key` - Your API key for authenticated requests
+    ///
+    /// # Panics
+    ///
+    /// Panics if the HTTP client cannot be built; see `try_new`.
     pub fn with_api_key(api_key: &str) -> Self {
         Self::new(DEFAULT_API_URL, api_key)
     }
 
+    /// Set how many times a request is retried after a transport failure
+    ///
+    /// Only connection-level errors (refused connections, timeouts) are
+    /// retried; responses with an error status are returned as-is. Defaults
+    /// to 0 (no retries).
+    ///
+    /// # Arguments
+    ///
+    /// * `retries` - Number of additional attempts after the first
+    pub fn with_retries(mut self, retries: u32) -> Self {
+        self.context.max_retries = retries;
+        self
+    }
+
+    /// Refuse to make any request
+    ///
+    /// Every call fails with `TuishError::Offline` before touching the
+    /// network. Set by `Tuish` when `TuishConfig::offline_only` is on.
+    pub fn with_offline(mut self, offline: bool) -> Self {
+        self.context.offline = offline;
+        self
+    }
+
+    /// Mount all endpoints under a path prefix
+    ///
+    /// For self-hosted deployments serving the API below the root, e.g.
+    /// `with_api_prefix("/tuish/api")` sends requests to
+    /// `{base_url}/tuish/api/v1/...`. Leading and trailing slashes are
+    /// optional; an empty prefix restores the default.
+    pub fn with_api_prefix(mut self, prefix: &str) -> Self {
+        self.context.api_prefix = normalize_api_prefix(prefix);
+        self
+    }
+
+    /// Get the path prefix placed before `/v1/...`, or `""` for none
+    pub fn api_prefix(&self) -> &str {
+        &self.context.api_prefix
+    }
+
+    /// Pin the API version sent as `Tuish-Api-Version`
+    ///
+    /// Defaults to `DEFAULT_API_VERSION`, the version this client targets.
+    pub fn with_api_version(mut self, version: impl Into<String>) -> Self {
+        self.context.api_version = version.into();
+        self
+    }
+
+    /// Get the API version sent with each request
+    pub fn api_version(&self) -> &str {
+        &self.context.api_version
+    }
+
+    /// Register a callback for deprecation notices
+    ///
+    /// Called whenever a response carries `Tuish-Api-Deprecated`, in
+    /// addition to a `warn!` log. Replaces any previous callback; clones
+    /// of this client share it.
+    pub fn on_deprecation(&mut self, callback: Box<dyn Fn(&DeprecationNotice) + Send + Sync>) {
+        self.context.deprecation.set(callback);
+    }
+
     /// Set the identity token for authenticated requests
     ///
     /// The identity token is obtained after successful login and is used
@@ -142,22 +438,39 @@ impl TuishClient {
     ///
     /// * `token` - The identity token (JWT), or None to clear it
     pub fn set_identity_token(&mut self, token: Option<String>) {
-        self.identity_token = token;
+        self.context.identity_token = token.map(SecretString::from);
     }
 
     /// Get the current identity token
     pub fn identity_token(&self) -> Option<&str> {
-        self.identity_token.as_deref()
+        self.context.identity_token.as_deref()
     }
 
     /// Check if an identity token is set
     pub fn has_identity_token(&self) -> bool {
-        self.identity_token.is_some()
+        self.context.identity_token.is_some()
+    }
+
+    /// Set the correlation ID sent as `X-Request-Id` on every request
+    ///
+    /// `Tuish` sets one ID per purchase flow so support can match client
+    /// logs to server logs.
+    ///
+    /// # Arguments
+    ///
+    /// * `request_id` - The correlation ID, or None to stop sending the header
+    pub fn set_request_id(&mut self, request_id: Option<String>) {
+        self.context.request_id = request_id;
+    }
+
+    /// Get the current correlation ID
+    pub fn request_id(&self) -> Option<&str> {
+        self.context.request_id.as_deref()
     }
 
     /// Enable or disable debug logging
     pub fn set_debug(&mut self, debug: bool) {
-        self.debug = debug;
+        self.context.debug = debug;
     }
 
     // =========================================================================
@@ -165,21 +478,44 @@ impl TuishClient {
     // =========================================================================
 
     /// Make a GET request
-    #[instrument(skip(self), fields(url = %url))]
+    #[cfg_attr(feature = "tracing", instrument(skip(self), fields(url = %url)))]
     async fn get<T: DeserializeOwned>(&self, url: &str, auth: AuthMethod) -> Result<T, TuishError> {
-        self.request(reqwest::Method::GET, url, Option::<()>::None, auth)
+        self.request(reqwest::Method::GET, url, Option::<()>::None, auth, None)
             .await
     }
 
     /// Make a POST request
-    #[instrument(skip(self, body), fields(url = %url))]
+    #[cfg_attr(feature = "tracing", instrument(skip(self, body), fields(url = %url)))]
     async fn post<T: DeserializeOwned, B: Serialize>(
         &self,
         url: &str,
         body: Option<B>,
         auth: AuthMethod,
     ) -> Result<T, TuishError> {
-        self.request(reqwest::Method::POST, url, body, auth).await
+        self.request(reqwest::Method::POST, url, body, auth, None)
+            .await
+    }
+
+    /// Make a POST request carrying an `Idempotency-Key` header
+    ///
+    /// The key is fixed before the first attempt, so transport retries
+    /// resend the same key and the API can discard duplicates.
+    #[cfg_attr(feature = "tracing", instrument(skip(self, body), fields(url = %url)))]
+    async fn post_idempotent<T: DeserializeOwned, B: Serialize>(
+        &self,
+        url: &str,
+        body: B,
+        auth: AuthMethod,
+        idempotency_key: &str,
+    ) -> Result<T, TuishError> {
+        self.request(
+            reqwest::Method::POST,
+            url,
+            Some(body),
+            auth,
+            Some(idempotency_key),
+        )
+        .await
     }
 
     /// Make an HTTP request with the specified method, body, and authentication
@@ -189,101 +525,98 @@ impl TuishClient {
         path: &str,
         body: Option<B>,
         auth: AuthMethod,
+        idempotency_key: Option<&str>,
     ) -> Result<T, TuishError> {
-        let url = format!("{}{}", self.base_url, path);
-
-        if self.debug {
-            debug!("[tuish] {} {}", method, url);
-        }
+        let (status, response_text) = self.send(method, path, body, auth, idempotency_key).await?;
+        parse_response(&response_text, status)
+    }
 
-        let mut request = self
-            .http
-            .request(method.clone(), &url)
-            .header(header::CONTENT_TYPE, "application/json")
-            .header(header::ACCEPT, "application/json");
-
-        // Add authentication headers
-        match auth {
-            AuthMethod::None => {}
-            AuthMethod::ApiKey => {
-                request = request.header("X-API-Key", &self.api_key);
-            }
-            AuthMethod::IdentityToken => {
-                if let Some(ref token) = self.identity_token {
-                    request = request.header(header::AUTHORIZATION, format!("Bearer {}", token));
-                } else {
-                    return Err(TuishError::ApiError {
-                        status: 401,
-                        message: "Identity token required but not set".to_string(),
-                    });
-                }
-            }
-        }
+    /// Send a request and read the response body, whatever its status
+    async fn send<B: Serialize>(
+        &self,
+        method: reqwest::Method,
+        path: &str,
+        body: Option<B>,
+        auth: AuthMethod,
+        idempotency_key: Option<&str>,
+    ) -> Result<(StatusCode, String), TuishError> {
+        let (url, headers) = self.context.prepare(&method, path, auth, idempotency_key)?;
+        let mut request = self.http.request(method, &url).headers(headers);
 
         // Add body for POST/PUT/PATCH requests
         if let Some(b) = body {
             request = request.json(&b);
         }
 
-        let response = request.send().await?;
+        let response = self.send_with_retries(request).await?;
         let status = response.status();
+        let headers = response.headers().clone();
         let response_text = response.text().await?;
+        self.context
+            .received(path, &headers, status, &response_text);
 
-        if self.debug {
-            debug!("[tuish] Response {}: {}", status, &response_text);
-        }
-
-        // Parse the response
-        self.parse_response(&response_text, status)
+        Ok((status, response_text))
     }
 
-    /// Parse an API response, handling both success and error cases
-    fn parse_response<T: DeserializeOwned>(
+    /// Send a request, retrying transport failures up to `max_retries` times
+    async fn send_with_retries(
         &self,
-        response_text: &str,
-        status: StatusCode,
-    ) -> Result<T, TuishError> {
-        // Try to parse as JSON
-        let json: serde_json::Value = serde_json::from_str(response_text).map_err(|_| {
-            TuishError::ParseError(format!(
-                "Invalid JSON response: {}",
-                &response_text[..response_text.len().min(100)]
-            ))
-        })?;
-
-        // Check for error response
-        if !status.is_success() {
-            let error_message = json
-                .get("error")
-                .and_then(|e| {
-                    // Handle both { error: string } and { error: { message: string } }
-                    if e.is_string() {
-                        e.as_str().map(String::from)
-                    } else {
-                        e.get("message").and_then(|m| m.as_str()).map(String::from)
-                    }
-                })
-                .or_else(|| json.get("message").and_then(|m| m.as_str()).map(String::from))
-                .unwrap_or_else(|| format!("Request failed with status {}", status));
-
-            return Err(TuishError::ApiError {
-                status: status.as_u16(),
-                message: error_message,
-            });
-        }
-
-        // Handle wrapped response { success: true, data: T }
-        if json.get("success").and_then(|s| s.as_bool()) == Some(true) {
-            if let Some(data) = json.get("data") {
-                return serde_json::from_value(data.clone()).map_err(|e| {
-                    TuishError::ParseError(format!("Failed to parse response data: {}", e))
-                });
+        request: reqwest::RequestBuilder,
+    ) -> Result<reqwest::Response, TuishError> {
+        let mut attempt = 0;
+        loop {
+            let Some(attempt_request) = request.try_clone() else {
+                return Ok(request.send().await?);
+            };
+
+            match attempt_request.send().await {
+                Ok(response) => return Ok(response),
+                Err(e) => match self.context.retry_delay(&mut attempt, &e) {
+                    Some(delay) => tokio::time::sleep(delay).await,
+                    None => return Err(e.into()),
+                },
             }
         }
+    }
 
-        // Try to parse the whole response as T
-        serde_json::from_value(json)
-            .map_err(|e| TuishError::ParseError(format!("Failed to parse response: {}", e)))
+    // =========================================================================
+    // Health Endpoints
+    // =========================================================================
+
+    /// Check that the API is reachable and the API key is accepted
+    ///
+    /// Call this before starting a purchase flow to fail fast with a clear
+    /// error instead of partway through.
+    ///
+    /// # Errors
+    ///
+    /// Returns `TuishError::ApiError` with status 401 and "invalid API key"
+    /// if the key is rejected, another `ApiError` for other non-2xx
+    /// responses, or `TuishError::NetworkError` if the API is unreachable.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use tuish::TuishClient;
+    ///
+    /// # async fn example() -> Result<(), tuish::TuishError> {
+    /// let client = TuishClient::new("https://api.tuish.dev", "your-api-key");
+    /// client.ping().await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg_attr(feature = "tracing", instrument(skip(self)))]
+    pub async fn ping(&self) -> Result<(), TuishError> {
+        let (status, response_text) = self
+            .send(
+                reqwest::Method::GET,
+                "/v1/health",
+                Option::<()>::None,
+                AuthMethod::ApiKey,
+                None,
+            )
+            .await?;
+        health_result(status, &response_text)
     }
 
     // =========================================================================
@@ -318,7 +651,7 @@ impl TuishClient {
     /// # Ok(())
     /// # }
     /// ```
-    #[instrument(skip(self))]
+    #[cfg_attr(feature = "tracing", instrument(skip(self)))]
     pub async fn init_checkout(
         &self,
         req: CheckoutInitRequest,
@@ -356,7 +689,7 @@ impl TuishClient {
     /// # Ok(())
     /// # }
     /// ```
-    #[instrument(skip(self))]
+    #[cfg_attr(feature = "tracing", instrument(skip(self)))]
     pub async fn get_checkout_status(
         &self,
         session_id: &str,
@@ -391,7 +724,7 @@ impl TuishClient {
     /// # Ok(())
     /// # }
     /// ```
-    #[instrument(skip(self))]
+    #[cfg_attr(feature = "tracing", instrument(skip(self)))]
     pub async fn request_login_otp(&self, email: &str) -> Result<LoginInitResponse, TuishError> {
         let req = LoginInitRequest {
             email: email.to_string(),
@@ -429,7 +762,7 @@ impl TuishClient {
     /// # Ok(())
     /// # }
     /// ```
-    #[instrument(skip(self, req))]
+    #[cfg_attr(feature = "tracing", instrument(skip(self, req)))]
     pub async fn verify_login(
         &mut self,
         req: LoginVerifyRequest,
@@ -439,7 +772,7 @@ impl TuishClient {
             .await?;
 
         // Store the identity token for subsequent requests
-        self.identity_token = Some(response.identity_token.clone());
+        self.context.identity_token = Some(response.identity_token.as_str().into());
 
         Ok(response)
     }
@@ -470,6 +803,7 @@ impl TuishClient {
     /// let result = client.validate_license(LicenseValidateRequest {
     ///     license_key: "license-key-here".to_string(),
     ///     machine_fingerprint: "machine-hash".to_string(),
+    ///     installation_id: None,
     /// }).await?;
     ///
     /// if result.valid {
@@ -480,7 +814,7 @@ impl TuishClient {
     /// # Ok(())
     /// # }
     /// ```
-    #[instrument(skip(self, req))]
+    #[cfg_attr(feature = "tracing", instrument(skip(self, req)))]
     pub async fn validate_license(
         &self,
         req: LicenseValidateRequest,
@@ -489,6 +823,215 @@ impl TuishClient {
             .await
     }
 
+    /// Release a license's seat on a machine
+    ///
+    /// Frees the seat so the license can be activated on another machine.
+    /// Sends a fresh `Idempotency-Key`, so a retry after a lost response is
+    /// not reported as a second release.
+    ///
+    /// # Arguments
+    ///
+    /// * `license_key` - The license key to deactivate
+    /// * `machine_fingerprint` - Fingerprint of the machine to release
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use tuish::{get_machine_fingerprint, TuishClient};
+    ///
+    /// # async fn example() -> Result<(), tuish::TuishError> {
+    /// let client = TuishClient::new("https://api.tuish.dev", "your-api-key");
+    ///
+    /// let result = client
+    ///     .deactivate_license("license-key-here", &get_machine_fingerprint())
+    ///     .await?;
+    /// println!("Deactivated: {}", result.deactivated);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg_attr(feature = "tracing", instrument(skip(self, license_key)))]
+    pub async fn deactivate_license(
+        &self,
+        license_key: &str,
+        machine_fingerprint: &str,
+    ) -> Result<LicenseDeactivateResponse, TuishError> {
+        let req = LicenseDeactivateRequest {
+            license_key: license_key.to_string(),
+            machine_fingerprint: machine_fingerprint.to_string(),
+        };
+        let key = new_idempotency_key();
+        self.post_idempotent("/v1/licenses/deactivate", req, AuthMethod::ApiKey, &key)
+            .await
+    }
+
+    /// Bind an existing license to a new machine
+    ///
+    /// Returns a freshly signed license whose machine ID matches
+    /// `machine_fingerprint`.
+    ///
+    /// # Arguments
+    ///
+    /// * `license_key` - The license key to rebind
+    /// * `machine_fingerprint` - Fingerprint of the new machine
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use tuish::{get_machine_fingerprint, TuishClient};
+    ///
+    /// # async fn example() -> Result<(), tuish::TuishError> {
+    /// let client = TuishClient::new("https://api.tuish.dev", "your-api-key");
+    ///
+    /// let rebound = client
+    ///     .rebind_license("license-key-here", &get_machine_fingerprint())
+    ///     .await?;
+    /// println!("New license: {}", rebound.license);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg_attr(feature = "tracing", instrument(skip(self, license_key)))]
+    pub async fn rebind_license(
+        &self,
+        license_key: &str,
+        machine_fingerprint: &str,
+    ) -> Result<LicenseRebindResponse, TuishError> {
+        let req = LicenseRebindRequest {
+            license_key: license_key.to_string(),
+            machine_fingerprint: machine_fingerprint.to_string(),
+        };
+        self.post("/v1/licenses/rebind", Some(req), AuthMethod::ApiKey)
+            .await
+    }
+
+    /// Fetch the signed revocation list for a product
+    ///
+    /// The returned token should be verified with `verify_revocation_list`
+    /// (or applied via `LicenseManager::set_revocation_list`) before use.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use tuish::TuishClient;
+    ///
+    /// # async fn example() -> Result<(), tuish::TuishError> {
+    /// let client = TuishClient::new("https://api.tuish.dev", "your-api-key");
+    ///
+    /// let response = client.get_revocation_list("prod_xxx").await?;
+    /// println!("Signed list: {}", response.list);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg_attr(feature = "tracing", instrument(skip(self)))]
+    pub async fn get_revocation_list(
+        &self,
+        product_id: &str,
+    ) -> Result<RevocationListResponse, TuishError> {
+        let path = format!("/v1/products/{}/revocations", product_id);
+        self.get(&path, AuthMethod::ApiKey).await
+    }
+
+    // =========================================================================
+    // Trial Endpoints
+    // =========================================================================
+
+    /// Start a time-limited trial for this machine
+    ///
+    /// Returns a signed trial license bound to `machine_fingerprint`. The
+    /// license carries the `trial` feature flag. Sends a fresh
+    /// `Idempotency-Key`, so a retry after a lost response returns the same
+    /// trial instead of being refused as a second one.
+    ///
+    /// # Arguments
+    ///
+    /// * `product_id` - Product ID to trial
+    /// * `machine_fingerprint` - Fingerprint of the current machine
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use tuish::{get_machine_fingerprint, TuishClient};
+    ///
+    /// # async fn example() -> Result<(), tuish::TuishError> {
+    /// let client = TuishClient::new("https://api.tuish.dev", "your-api-key");
+    ///
+    /// let trial = client.start_trial("prod_xxx", &get_machine_fingerprint()).await?;
+    /// println!("Trial ends at {}", trial.expires_at);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg_attr(feature = "tracing", instrument(skip(self)))]
+    pub async fn start_trial(
+        &self,
+        product_id: &str,
+        machine_fingerprint: &str,
+    ) -> Result<TrialStartResponse, TuishError> {
+        let req = TrialStartRequest {
+            product_id: product_id.to_string(),
+            machine_fingerprint: machine_fingerprint.to_string(),
+        };
+        let key = new_idempotency_key();
+        self.post_idempotent("/v1/trials/start", req, AuthMethod::ApiKey, &key)
+            .await
+    }
+
+    // =========================================================================
+    // Account Endpoints
+    // =========================================================================
+
+    /// Fetch the developer account the API key belongs to
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use tuish::TuishClient;
+    ///
+    /// # async fn example() -> Result<(), tuish::TuishError> {
+    /// let client = TuishClient::new("https://api.tuish.dev", "your-api-key");
+    ///
+    /// let developer = client.whoami().await?;
+    /// println!("Signed in as {}", developer.email);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg_attr(feature = "tracing", instrument(skip(self)))]
+    pub async fn whoami(&self) -> Result<Developer, TuishError> {
+        self.get("/v1/me", AuthMethod::ApiKey).await
+    }
+
+    // =========================================================================
+    // Analytics Endpoints
+    // =========================================================================
+
+    /// Fetch sales and license analytics for a time window
+    ///
+    /// `period` is a count followed by `d` (days), `w` (weeks) or `m`
+    /// (months), e.g. "7d" or "3m".
+    ///
+    /// # Errors
+    ///
+    /// Returns `TuishError::ParseError` without contacting the API if
+    /// `period` is malformed.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use tuish::TuishClient;
+    ///
+    /// # async fn example() -> Result<(), tuish::TuishError> {
+    /// let client = TuishClient::new("https://api.tuish.dev", "your-api-key");
+    ///
+    /// let summary = client.get_analytics("30d").await?;
+    /// println!("{} active licenses", summary.active_licenses);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg_attr(feature = "tracing", instrument(skip(self)))]
+    pub async fn get_analytics(&self, period: &str) -> Result<AnalyticsSummary, TuishError> {
+        validate_period(period)?;
+        let path = format!("/v1/analytics?period={}", period);
+        self.get(&path, AuthMethod::ApiKey).await
+    }
+
     // =========================================================================
     // Purchase Endpoints (for returning customers)
     // =========================================================================
@@ -519,7 +1062,7 @@ impl TuishClient {
     /// # Ok(())
     /// # }
     /// ```
-    #[instrument(skip(self))]
+    #[cfg_attr(feature = "tracing", instrument(skip(self)))]
     pub async fn init_purchase(
         &self,
         product_id: &str,
@@ -549,7 +1092,7 @@ impl TuishClient {
     /// # Ok(())
     /// # }
     /// ```
-    #[instrument(skip(self))]
+    #[cfg_attr(feature = "tracing", instrument(skip(self)))]
     pub async fn request_purchase_otp(&self) -> Result<OtpResponse, TuishError> {
         self.post::<OtpResponse, ()>("/v1/purchase/otp", None, AuthMethod::IdentityToken)
             .await
@@ -560,6 +1103,11 @@ impl TuishClient {
     /// Completes the purchase using a saved card and OTP verification.
     /// Returns the license key on success.
     ///
+    /// `req.idempotency_key` is sent as the `Idempotency-Key` header so a
+    /// retried confirmation never charges the card twice. Set it yourself
+    /// to keep it stable across your own retries; when unset a key is
+    /// generated for this call and reused only by its transport retries.
+    ///
     /// # Arguments
     ///
     /// * `req` - Purchase confirmation request
@@ -578,6 +1126,7 @@ impl TuishClient {
     ///     card_id: "card_yyy".to_string(),
     ///     otp_id: "otp_zzz".to_string(),
     ///     otp: "123456".to_string(),
+    ///     idempotency_key: Some(tuish::new_idempotency_key()),
     /// }).await?;
     ///
     /// if result.success {
@@ -586,19 +1135,221 @@ impl TuishClient {
     /// # Ok(())
     /// # }
     /// ```
-    #[instrument(skip(self, req))]
+    #[cfg_attr(feature = "tracing", instrument(skip(self, req)))]
     pub async fn confirm_purchase(
         &self,
         req: PurchaseConfirmRequest,
     ) -> Result<PurchaseConfirmResponse, TuishError> {
-        self.post("/v1/purchase/confirm", Some(req), AuthMethod::IdentityToken)
+        let key = req
+            .idempotency_key
+            .clone()
+            .unwrap_or_else(new_idempotency_key);
+        self.post_idempotent("/v1/purchase/confirm", req, AuthMethod::IdentityToken, &key)
             .await
     }
 }
 
+/// Generate a correlation ID for a multi-request flow (`req_` + 16 hex chars)
+///
+/// Pass it to `TuishClient::set_request_id` to tag your own request flows.
+pub fn new_request_id() -> String {
+    format!("req_{:016x}", random_u64())
+}
+
+/// Generate a key for the `Idempotency-Key` header (`idem_` + 32 hex chars)
+///
+/// Create one per logical operation and reuse it for every retry of that
+/// operation, e.g. in `PurchaseConfirmRequest::idempotency_key`.
+pub fn new_idempotency_key() -> String {
+    format!("idem_{:016x}{:016x}", random_u64(), random_u64())
+}
+
+/// Unique-per-call random value without pulling in an RNG crate
+fn random_u64() -> u64 {
+    use std::collections::hash_map::RandomState;
+    use std::hash::BuildHasher;
+    use std::sync::atomic::{AtomicU64, Ordering};
+
+    static COUNTER: AtomicU64 = AtomicU64::new(0);
+    let seed = (
+        std::time::SystemTime::now(),
+        COUNTER.fetch_add(1, Ordering::Relaxed),
+    );
+    RandomState::new().hash_one(seed)
+}
+
+/// Header value for an idempotency key
+fn idempotency_header(key: &str) -> Result<header::HeaderValue, TuishError> {
+    header::HeaderValue::from_str(key).map_err(|_| TuishError::ApiError {
+        status: 400,
+        message: format!("invalid {} header value", IDEMPOTENCY_KEY_HEADER),
+    })
+}
+
+/// Build the headers for an API request
+fn request_headers(
+    auth: AuthMethod,
+    api_key: &str,
+    identity_token: Option<&str>,
+    request_id: Option<&str>,
+    api_version: &str,
+) -> Result<header::HeaderMap, TuishError> {
+    let invalid = |name: &str| TuishError::ApiError {
+        status: 400,
+        message: format!("invalid {} header value", name),
+    };
+
+    let mut headers = header::HeaderMap::new();
+    headers.insert(
+        header::CONTENT_TYPE,
+        header::HeaderValue::from_static("application/json"),
+    );
+    headers.insert(
+        header::ACCEPT,
+        header::HeaderValue::from_static("application/json"),
+    );
+    let value =
+        header::HeaderValue::from_str(api_version).map_err(|_| invalid(API_VERSION_HEADER))?;
+    headers.insert(API_VERSION_HEADER, value);
+
+    if let Some(request_id) = request_id {
+        let value =
+            header::HeaderValue::from_str(request_id).map_err(|_| invalid(REQUEST_ID_HEADER))?;
+        headers.insert(REQUEST_ID_HEADER, value);
+    }
+
+    // Add authentication headers
+    match auth {
+        AuthMethod::None => {}
+        AuthMethod::ApiKey => {
+            let value = header::HeaderValue::from_str(api_key).map_err(|_| invalid("X-API-Key"))?;
+            headers.insert("X-API-Key", value);
+        }
+        AuthMethod::IdentityToken => {
+            let token = identity_token.ok_or_else(|| TuishError::ApiError {
+                status: 401,
+                message: "Identity token required but not set".to_string(),
+            })?;
+            let value = header::HeaderValue::from_str(&format!("Bearer {}", token))
+                .map_err(|_| invalid("Authorization"))?;
+            headers.insert(header::AUTHORIZATION, value);
+        }
+    }
+
+    Ok(headers)
+}
+
+/// Interpret a health check response
+///
+/// Any 2xx is healthy regardless of body. Shared by the async and blocking clients.
+pub(crate) fn health_result(status: StatusCode, response_text: &str) -> Result<(), TuishError> {
+    if status.is_success() {
+        return Ok(());
+    }
+
+    if status == StatusCode::UNAUTHORIZED {
+        return Err(TuishError::ApiError {
+            status: status.as_u16(),
+            message: "invalid API key".to_string(),
+        });
+    }
+
+    match parse_response::<serde_json::Value>(response_text, status) {
+        Err(e @ TuishError::ApiError { .. }) => Err(e),
+        _ => Err(TuishError::ApiError {
+            status: status.as_u16(),
+            message: format!("Request failed with status {}", status),
+        }),
+    }
+}
+
+/// Check an analytics period is a count followed by `d`, `w` or `m`
+///
+/// Shared by the async and blocking clients.
+pub(crate) fn validate_period(period: &str) -> Result<(), TuishError> {
+    let count = period.strip_suffix(['d', 'w', 'm']).unwrap_or("");
+    if count.is_empty() || !count.bytes().all(|b| b.is_ascii_digit()) {
+        return Err(TuishError::ParseError(format!(
+            "invalid analytics period '{}': expected a number followed by d, w or m (e.g. 7d)",
+            period
+        )));
+    }
+    Ok(())
+}
+
+/// Parse an API response, handling both success and error cases
+///
+/// Shared by the async and blocking clients.
+pub(crate) fn parse_response<T: DeserializeOwned>(
+    response_text: &str,
+    status: StatusCode,
+) -> Result<T, TuishError> {
+    // Try to parse as JSON
+    let json: serde_json::Value = serde_json::from_str(response_text).map_err(|_| {
+        TuishError::ParseError(format!(
+            "Invalid JSON response: {}",
+            &response_text[..response_text.len().min(100)]
+        ))
+    })?;
+
+    // Check for error response
+    if !status.is_success() {
+        let error_message = json
+            .get("error")
+            .and_then(|e| {
+                // Handle both { error: string } and { error: { message: string } }
+                if e.is_string() {
+                    e.as_str().map(String::from)
+                } else {
+                    e.get("message").and_then(|m| m.as_str()).map(String::from)
+                }
+            })
+            .or_else(|| {
+                json.get("message")
+                    .and_then(|m| m.as_str())
+                    .map(String::from)
+            })
+            .unwrap_or_else(|| format!("Request failed with status {}", status));
+
+        return Err(TuishError::ApiError {
+            status: status.as_u16(),
+            message: error_message,
+        });
+    }
+
+    // Handle wrapped response { success: true, data: T }
+    if json.get("success").and_then(|s| s.as_bool()) == Some(true) {
+        if let Some(data) = json.get("data") {
+            return from_value_with_path(data.clone(), "Failed to parse response data");
+        }
+    }
+
+    // Try to parse the whole response as T
+    from_value_with_path(json, "Failed to parse response")
+}
+
+/// Deserialize a response body, naming the offending field on failure
+///
+/// serde alone reports e.g. "invalid type: null, expected a string" with no
+/// hint of where; this adds the JSON path (`license.expiresAt`) so a changed
+/// API response is easy to pin down.
+fn from_value_with_path<T: DeserializeOwned>(
+    value: serde_json::Value,
+    context: &str,
+) -> Result<T, TuishError> {
+    serde_path_to_error::deserialize(value).map_err(|e| {
+        let path = e.path().to_string();
+        if path == "." {
+            TuishError::ParseError(format!("{}: {}", context, e.inner()))
+        } else {
+            TuishError::ParseError(format!("{} at `{}`: {}", context, path, e.inner()))
+        }
+    })
+}
+
 /// Authentication method for API requests
 #[derive(Debug, Clone, Copy)]
-enum AuthMethod {
+pub(crate) enum AuthMethod {
     /// No authentication required
     None,
     /// Use X-API-Key header
@@ -614,15 +1365,91 @@ mod tests {
     #[test]
     fn test_client_construction() {
         let client = TuishClient::new("https://api.tuish.dev", "test-api-key");
-        assert_eq!(client.base_url, "https://api.tuish.dev");
-        assert_eq!(client.api_key, "test-api-key");
-        assert!(client.identity_token.is_none());
+        assert_eq!(client.context.base_url, "https://api.tuish.dev");
+        assert_eq!(client.context.api_key, "test-api-key");
+        assert!(client.identity_token().is_none());
+    }
+
+    #[test]
+    fn test_try_new_surfaces_build_errors() {
+        let client = TuishClient::try_new("https://api.tuish.dev/", "test-api-key").unwrap();
+        assert_eq!(client.context.base_url, "https://api.tuish.dev");
+
+        // An invalid header value makes the reqwest builder fail
+        let result = TuishClient::from_builder(
+            Client::builder().user_agent("bad\nagent"),
+            "https://api.tuish.dev",
+            "test-api-key",
+            false,
+        );
+        assert!(matches!(result, Err(TuishError::NetworkError(_))));
     }
 
     #[test]
     fn test_client_strips_trailing_slash() {
         let client = TuishClient::new("https://api.tuish.dev/", "test-api-key");
-        assert_eq!(client.base_url, "https://api.tuish.dev");
+        assert_eq!(client.context.base_url, "https://api.tuish.dev");
+    }
+
+    #[test]
+    fn test_api_prefix_urls() {
+        let client = TuishClient::new("https://example.com/", "test-api-key");
+        assert_eq!(client.api_prefix(), "");
+        assert_eq!(
+            client.context.url("/v1/health"),
+            "https://example.com/v1/health"
+        );
+
+        for prefix in ["tuish/api", "/tuish/api", "/tuish/api/", "tuish/api/"] {
+            let client = client.clone().with_api_prefix(prefix);
+            assert_eq!(client.api_prefix(), "/tuish/api");
+            assert_eq!(
+                client.context.url("/v1/health"),
+                "https://example.com/tuish/api/v1/health"
+            );
+        }
+
+        let client = client.with_api_prefix("/");
+        assert_eq!(
+            client.context.url("/v1/health"),
+            "https://example.com/v1/health"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_api_prefix_requests() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/tuish/api/v1/health"))
+            .respond_with(ResponseTemplate::new(200))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let client = TuishClient::new(&server.uri(), "test-key").with_api_prefix("/tuish/api/");
+        client.ping().await.unwrap();
+        assert!(TuishClient::new(&server.uri(), "test-key")
+            .ping()
+            .await
+            .is_err());
+    }
+
+    #[test]
+    fn test_request_id_management() {
+        let mut client = TuishClient::new("https://api.tuish.dev", "test-api-key");
+        assert!(client.request_id().is_none());
+
+        let id = new_request_id();
+        assert!(id.starts_with("req_") && id.len() == 20);
+        assert_ne!(id, new_request_id());
+
+        client.set_request_id(Some(id.clone()));
+        assert_eq!(client.request_id(), Some(id.as_str()));
+        client.set_request_id(None);
+        assert!(client.request_id().is_none());
     }
 
     #[test]
@@ -642,40 +1469,309 @@ mod tests {
         assert!(!client.has_identity_token());
     }
 
+    #[test]
+    fn test_with_retries() {
+        let client = TuishClient::new("https://api.tuish.dev", "test-key");
+        assert_eq!(client.context.max_retries, 0);
+
+        let client = client.with_retries(3);
+        assert_eq!(client.context.max_retries, 3);
+    }
+
+    #[tokio::test]
+    async fn test_retries_exhausted_returns_network_error() {
+        let client = TuishClient::with_config(
+            "http://127.0.0.1:1",
+            "test-key",
+            Duration::from_secs(1),
+            false,
+        )
+        .unwrap()
+        .with_retries(1);
+
+        let result = client.get_checkout_status("sess_123").await;
+        assert!(matches!(result, Err(TuishError::NetworkError(_))));
+    }
+
+    #[tokio::test]
+    async fn test_confirm_purchase_retry_reuses_idempotency_key() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        // First attempt outlives the client timeout, as if the response was lost
+        Mock::given(method("POST"))
+            .and(path("/v1/purchase/confirm"))
+            .respond_with(ResponseTemplate::new(200).set_delay(Duration::from_secs(5)))
+            .up_to_n_times(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/v1/purchase/confirm"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "success": true,
+                "license": "license-key"
+            })))
+            .mount(&server)
+            .await;
+
+        let mut client =
+            TuishClient::with_config(&server.uri(), "test-key", Duration::from_millis(200), false)
+                .unwrap()
+                .with_retries(1);
+        client.set_identity_token(Some("token".to_string()));
+
+        let request = PurchaseConfirmRequest {
+            product_id: "prod_123".to_string(),
+            card_id: "card_123".to_string(),
+            otp_id: "otp_123".to_string(),
+            otp: "123456".to_string(),
+            idempotency_key: None,
+        };
+        let result = client.confirm_purchase(request).await.unwrap();
+        assert!(result.success);
+
+        let keys: Vec<_> = server
+            .received_requests()
+            .await
+            .unwrap()
+            .iter()
+            .map(|request| request.headers.get(IDEMPOTENCY_KEY_HEADER).cloned())
+            .collect();
+        assert_eq!(keys.len(), 2);
+        let key = keys[0].as_ref().expect("Idempotency-Key header missing");
+        assert!(key.to_str().unwrap().starts_with("idem_"));
+        assert_eq!(keys[0], keys[1]);
+    }
+
+    #[tokio::test]
+    async fn test_api_version_and_deprecation() {
+        use std::sync::Mutex;
+        use wiremock::matchers::{header, method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/v1/health"))
+            .and(header(API_VERSION_HEADER, "0"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header(API_DEPRECATED_HEADER, "sunset 2027-01-01")
+                    .set_body_json(serde_json::json!({ "status": "ok" })),
+            )
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/v1/health"))
+            .and(header(API_VERSION_HEADER, DEFAULT_API_VERSION))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_json(serde_json::json!({ "status": "ok" })),
+            )
+            .mount(&server)
+            .await;
+
+        let notices = Arc::new(Mutex::new(Vec::new()));
+        let sink = Arc::clone(&notices);
+
+        // Current version: no notice
+        let mut client = TuishClient::new(&server.uri(), "key");
+        client.on_deprecation(Box::new(move |notice| {
+            sink.lock().unwrap().push(notice.clone())
+        }));
+        client.ping().await.unwrap();
+        assert!(notices.lock().unwrap().is_empty());
+
+        // Pinned old version: the callback sees the notice
+        let client = client.with_api_version("0");
+        assert_eq!(client.api_version(), "0");
+        client.ping().await.unwrap();
+        assert_eq!(
+            *notices.lock().unwrap(),
+            vec![DeprecationNotice {
+                api_version: "0".to_string(),
+                path: "/v1/health".to_string(),
+                message: "sunset 2027-01-01".to_string(),
+            }]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_ping() {
+        use wiremock::matchers::{header, method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/v1/health"))
+            .and(header("X-API-Key", "good-key"))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_json(serde_json::json!({ "status": "ok" })),
+            )
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/v1/health"))
+            .respond_with(ResponseTemplate::new(401).set_body_json(serde_json::json!({
+                "error": { "message": "Unauthorized" }
+            })))
+            .mount(&server)
+            .await;
+
+        // Healthy
+        assert!(TuishClient::new(&server.uri(), "good-key")
+            .ping()
+            .await
+            .is_ok());
+
+        // Unauthorized
+        match TuishClient::new(&server.uri(), "bad-key").ping().await {
+            Err(TuishError::ApiError { status, message }) => {
+                assert_eq!(status, 401);
+                assert_eq!(message, "invalid API key");
+            }
+            other => panic!("expected ApiError, got {:?}", other),
+        }
+
+        // Unreachable
+        let client = TuishClient::with_config(
+            "http://127.0.0.1:1",
+            "good-key",
+            Duration::from_secs(1),
+            false,
+        )
+        .unwrap();
+        assert!(matches!(
+            client.ping().await,
+            Err(TuishError::NetworkError(_))
+        ));
+    }
+
+    #[test]
+    fn test_health_result_other_errors() {
+        assert!(health_result(StatusCode::NO_CONTENT, "").is_ok());
+
+        let err = health_result(
+            StatusCode::SERVICE_UNAVAILABLE,
+            r#"{"error":"maintenance"}"#,
+        )
+        .unwrap_err();
+        assert!(
+            matches!(err, TuishError::ApiError { status: 503, ref message } if message == "maintenance")
+        );
+
+        let err = health_result(StatusCode::BAD_GATEWAY, "<html>").unwrap_err();
+        assert!(matches!(err, TuishError::ApiError { status: 502, .. }));
+    }
+
+    #[test]
+    fn test_validate_period() {
+        for period in ["7d", "30d", "2w", "12m", "0d"] {
+            assert!(validate_period(period).is_ok(), "{period}");
+        }
+        for period in ["", "d", "7", "7y", "-7d", "7 d", "7dd", "7д"] {
+            assert!(
+                matches!(validate_period(period), Err(TuishError::ParseError(_))),
+                "{period}"
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn test_get_analytics() {
+        use wiremock::matchers::{header, method, path, query_param};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/v1/analytics"))
+            .and(query_param("period", "30d"))
+            .and(header("X-API-Key", "test-key"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "success": true,
+                "data": {
+                    "period": "30d",
+                    "activeLicenses": 42,
+                    "newCustomers": 7,
+                    "revenue": 34300,
+                    "currency": "usd",
+                    "byProduct": [{
+                        "productId": "prod_1",
+                        "productName": "Pro",
+                        "activeLicenses": 42,
+                        "newCustomers": 7,
+                        "revenue": 34300
+                    }]
+                }
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let client = TuishClient::new(&server.uri(), "test-key");
+        let summary = client.get_analytics("30d").await.unwrap();
+        assert_eq!(summary.active_licenses, 42);
+        assert_eq!(summary.by_product[0].product_name, "Pro");
+
+        // Rejected before any request is sent
+        assert!(matches!(
+            client.get_analytics("30 days").await,
+            Err(TuishError::ParseError(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_whoami() {
+        use wiremock::matchers::{header, method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/v1/me"))
+            .and(header("X-API-Key", "test-key"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "success": true,
+                "data": { "id": "dev_123", "email": "dev@example.com" }
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let developer = TuishClient::new(&server.uri(), "test-key")
+            .whoami()
+            .await
+            .unwrap();
+        assert_eq!(developer.id, "dev_123");
+        assert_eq!(developer.email, "dev@example.com");
+        assert!(developer.name.is_none());
+    }
+
     #[test]
     fn test_with_api_key_constructor() {
         let client = TuishClient::with_api_key("test-key");
-        assert_eq!(client.base_url, DEFAULT_API_URL);
-        assert_eq!(client.api_key, "test-key");
+        assert_eq!(client.context.base_url, DEFAULT_API_URL);
+        assert_eq!(client.context.api_key, "test-key");
     }
 
     #[test]
     fn test_parse_success_response() {
-        let client = TuishClient::new("https://api.tuish.dev", "test-key");
-
         // Test direct response
         let json = r#"{"sessionId":"sess_123","checkoutUrl":"https://checkout.example.com"}"#;
-        let result: CheckoutInitResponse = client
-            .parse_response(json, StatusCode::OK)
-            .expect("Failed to parse");
+        let result: CheckoutInitResponse =
+            parse_response(json, StatusCode::OK).expect("Failed to parse");
         assert_eq!(result.session_id, "sess_123");
 
         // Test wrapped response
         let json = r#"{"success":true,"data":{"sessionId":"sess_456","checkoutUrl":"https://checkout.example.com"}}"#;
-        let result: CheckoutInitResponse = client
-            .parse_response(json, StatusCode::OK)
-            .expect("Failed to parse");
+        let result: CheckoutInitResponse =
+            parse_response(json, StatusCode::OK).expect("Failed to parse");
         assert_eq!(result.session_id, "sess_456");
     }
 
     #[test]
     fn test_parse_error_response() {
-        let client = TuishClient::new("https://api.tuish.dev", "test-key");
-
         // Test simple error
         let json = r#"{"error":"Not found"}"#;
-        let result: Result<CheckoutInitResponse, _> =
-            client.parse_response(json, StatusCode::NOT_FOUND);
+        let result: Result<CheckoutInitResponse, _> = parse_response(json, StatusCode::NOT_FOUND);
         match result {
             Err(TuishError::ApiError { status, message }) => {
                 assert_eq!(status, 404);
@@ -686,8 +1782,7 @@ mod tests {
 
         // Test structured error
         let json = r#"{"error":{"code":"invalid_request","message":"Invalid product ID"}}"#;
-        let result: Result<CheckoutInitResponse, _> =
-            client.parse_response(json, StatusCode::BAD_REQUEST);
+        let result: Result<CheckoutInitResponse, _> = parse_response(json, StatusCode::BAD_REQUEST);
         match result {
             Err(TuishError::ApiError { status, message }) => {
                 assert_eq!(status, 400);
@@ -699,10 +1794,8 @@ mod tests {
 
     #[test]
     fn test_parse_invalid_json() {
-        let client = TuishClient::new("https://api.tuish.dev", "test-key");
-
         let json = "not valid json";
-        let result: Result<CheckoutInitResponse, _> = client.parse_response(json, StatusCode::OK);
+        let result: Result<CheckoutInitResponse, _> = parse_response(json, StatusCode::OK);
         match result {
             Err(TuishError::ParseError(msg)) => {
                 assert!(msg.contains("Invalid JSON"));
@@ -711,6 +1804,28 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_parse_response_names_bad_field() {
+        let json = r#"{"checkoutUrl":"https://checkout.example.com"}"#;
+        let result: Result<CheckoutInitResponse, _> = parse_response(json, StatusCode::OK);
+        match result {
+            Err(TuishError::ParseError(msg)) => assert!(msg.contains("missing field `sessionId`")),
+            other => panic!("Expected ParseError, got {:?}", other),
+        }
+
+        let json = r#"{"success":true,"data":{"sessionId":null,"checkoutUrl":"https://checkout.example.com"}}"#;
+        let result: Result<CheckoutInitResponse, _> = parse_response(json, StatusCode::OK);
+        match result {
+            Err(TuishError::ParseError(msg)) => {
+                assert_eq!(
+                    msg,
+                    "Failed to parse response data at `sessionId`: invalid type: null, expected a string"
+                );
+            }
+            other => panic!("Expected ParseError, got {:?}", other),
+        }
+    }
+
     #[test]
     fn test_otp_response_deserialization() {
         let json = r#"{"otpId":"otp_123","expiresIn":300}"#;
@@ -7,10 +7,12 @@
 use base64::engine::general_purpose::URL_SAFE_NO_PAD;
 use base64::Engine;
 use ed25519_dalek::{Signature, Verifier, VerifyingKey};
-use tracing::{debug, trace};
+use std::borrow::Cow;
+use std::time::Duration;
 
-use crate::error::TuishError;
-use crate::types::{LicenseHeader, LicensePayload};
+use crate::error::{TuishError, UnmetExpectation};
+use crate::logging::{debug, trace};
+use crate::types::{Expectations, LicenseHeader, LicensePayload, RevocationList};
 
 /// Size of a raw Ed25519 public key in bytes
 const ED25519_PUBLIC_KEY_SIZE: usize = 32;
@@ -21,6 +23,25 @@ const SPKI_ED25519_SIZE: usize = 44;
 /// SPKI header for Ed25519 keys (12 bytes)
 const SPKI_HEADER_SIZE: usize = 12;
 
+/// Default clock skew tolerated when checking a license's `nbf` claim
+pub const DEFAULT_CLOCK_SKEW: Duration = Duration::from_secs(60);
+
+/// Longest license key input accepted, in bytes (16 KiB)
+///
+/// Real licenses are well under 1 KiB. Longer input is rejected before any
+/// decoding, so untrusted pastes and files cannot make verification do
+/// unbounded work. Use `parse_license_with_max_len` for a different limit.
+pub const MAX_LICENSE_KEY_LEN: usize = 16 * 1024;
+
+/// Quote characters stripped from around a pasted license key
+const LICENSE_QUOTES: &[char] = &[
+    '"', '\'', '`', '\u{201c}', '\u{201d}', '\u{2018}', '\u{2019}',
+];
+
+/// PEM armor lines for an SPKI public key
+const PEM_BEGIN: &str = "-----BEGIN PUBLIC KEY-----";
+const PEM_END: &str = "-----END PUBLIC KEY-----";
+
 // ============================================================================
 // Public API
 // ============================================================================
@@ -30,13 +51,15 @@ const SPKI_HEADER_SIZE: usize = 12;
 /// This function performs complete offline verification of a license:
 /// 1. Parses the license string into header, payload, and signature
 /// 2. Verifies the Ed25519 signature over "header.payload"
-/// 3. Checks if the license has expired
-/// 4. Checks if the license is bound to the correct machine (if provided)
+/// 3. Checks the license is not used before its `nbf` (not-before) time,
+///    allowing `DEFAULT_CLOCK_SKEW`
+/// 4. Checks if the license has expired
+/// 5. Checks if the license is bound to the correct machine (if provided)
 ///
 /// # Arguments
 ///
 /// * `license_key` - The full license string (base64url: header.payload.signature)
-/// * `public_key` - The Ed25519 public key (SPKI base64 or 64-char hex)
+/// * `public_key` - The Ed25519 public key (SPKI base64, PEM, or 64-char hex)
 /// * `machine_id` - Optional machine ID to verify binding
 ///
 /// # Returns
@@ -48,8 +71,10 @@ const SPKI_HEADER_SIZE: usize = 12;
 /// * `TuishError::InvalidLicense` - License format is invalid
 /// * `TuishError::InvalidPublicKey` - Public key format is invalid
 /// * `TuishError::InvalidSignature` - Signature verification failed
+/// * `TuishError::NotYetValid` - License `nbf` is still in the future
 /// * `TuishError::ExpiredLicense` - License has expired
-/// * `TuishError::InvalidMachineId` - License is bound to a different machine
+/// * `TuishError::InvalidMachineId` - License is bound to other machines
+///   (neither `mid` nor any entry in `mids` matches `machine_id`)
 ///
 /// # Example
 ///
@@ -68,44 +93,138 @@ pub fn verify_license(
     license_key: &str,
     public_key: &str,
     machine_id: Option<&str>,
+) -> Result<LicensePayload, TuishError> {
+    verify_license_with_skew(license_key, public_key, machine_id, DEFAULT_CLOCK_SKEW)
+}
+
+/// Verify a license, tolerating `clock_skew` on the `nbf` check.
+///
+/// Same as `verify_license`, but a license whose `nbf` is at most
+/// `clock_skew` in the future is accepted, for machines whose clock runs
+/// slightly behind the issuer's.
+pub fn verify_license_with_skew(
+    license_key: &str,
+    public_key: &str,
+    machine_id: Option<&str>,
+    clock_skew: Duration,
 ) -> Result<LicensePayload, TuishError> {
     debug!("Verifying license");
+    check_license_len(license_key.len(), MAX_LICENSE_KEY_LEN)?;
+    let license_key = &*normalize_license_key(license_key);
 
     // Parse the license
     let (_header, payload, signature_bytes) = parse_license(license_key)?;
     trace!(license_id = %payload.lid, "Parsed license");
 
-    // Verify signature
-    let parts: Vec<&str> = license_key.split('.').collect();
-    if parts.len() != 3 {
-        return Err(TuishError::InvalidLicense(
-            "license must have exactly 3 parts".to_string(),
-        ));
-    }
+    // Signed message is everything before the last '.'
+    let message = license_key
+        .rsplit_once('.')
+        .map(|(message, _)| message)
+        .ok_or_else(|| {
+            TuishError::InvalidLicense("license must have exactly 3 parts".to_string())
+        })?;
+
+    check_verified_license(
+        payload,
+        message,
+        &signature_bytes,
+        public_key,
+        machine_id,
+        clock_skew,
+    )
+}
 
-    let message = format!("{}.{}", parts[0], parts[1]);
-    verify_signature(public_key, &message, &signature_bytes)?;
+/// Verify a license supplied as its three separate base64url parts.
+///
+/// Equivalent to `verify_license` on `"{header}.{payload}.{signature}"`,
+/// for integrators that store the parts separately (e.g. as database
+/// columns) and would otherwise have to reassemble the string.
+///
+/// # Errors
+///
+/// Same as `verify_license`.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use tuish::verify_components;
+///
+/// # fn example(header: &str, payload: &str, signature: &str, public_key: &str) {
+/// match verify_components(header, payload, signature, public_key, None) {
+///     Ok(payload) => println!("License valid for product: {}", payload.pid),
+///     Err(e) => eprintln!("Verification failed: {}", e),
+/// }
+/// # }
+/// ```
+pub fn verify_components(
+    header_b64: &str,
+    payload_b64: &str,
+    signature_b64: &str,
+    public_key: &str,
+    machine_id: Option<&str>,
+) -> Result<LicensePayload, TuishError> {
+    debug!("Verifying license components");
+    check_license_len(
+        header_b64.len() + payload_b64.len() + signature_b64.len() + 2,
+        MAX_LICENSE_KEY_LEN,
+    )?;
+
+    let (_header, payload, signature_bytes) =
+        parse_components(header_b64, payload_b64, signature_b64)?;
+    trace!(license_id = %payload.lid, "Parsed license");
+
+    let message = format!("{}.{}", header_b64, payload_b64);
+    check_verified_license(
+        payload,
+        &message,
+        &signature_bytes,
+        public_key,
+        machine_id,
+        DEFAULT_CLOCK_SKEW,
+    )
+}
+
+/// Signature, `nbf`, expiry and machine checks shared by the `verify_*` functions
+fn check_verified_license(
+    payload: LicensePayload,
+    message: &str,
+    signature_bytes: &[u8],
+    public_key: &str,
+    machine_id: Option<&str>,
+    clock_skew: Duration,
+) -> Result<LicensePayload, TuishError> {
+    // Verify signature
+    verify_signature(public_key, message, signature_bytes)?;
     trace!("Signature verified");
 
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_millis() as i64)
+        .unwrap_or(0);
+
+    // Check the license has started
+    if let Some(nbf) = payload.nbf {
+        if now.saturating_add(clock_skew.as_millis() as i64) < nbf {
+            debug!(not_before = nbf, now = now, "License not yet valid");
+            return Err(TuishError::NotYetValid);
+        }
+    }
+
     // Check expiration
     if let Some(exp) = payload.exp {
-        let now = std::time::SystemTime::now()
-            .duration_since(std::time::UNIX_EPOCH)
-            .map(|d| d.as_millis() as i64)
-            .unwrap_or(0);
-
         if exp < now {
             debug!(expiration = exp, now = now, "License expired");
             return Err(TuishError::ExpiredLicense);
         }
     }
 
-    // Check machine ID if provided and license is bound
-    if let (Some(required_mid), Some(license_mid)) = (machine_id, &payload.mid) {
-        if required_mid != license_mid {
+    // Check machine ID if provided and license is bound (to `mid` or `mids`)
+    if let Some(required_mid) = machine_id {
+        if !payload.allows_machine(required_mid) {
             debug!(
                 expected = required_mid,
-                actual = license_mid,
+                actual = ?payload.mid,
+                allowed = payload.mids.len(),
                 "Machine ID mismatch"
             );
             return Err(TuishError::InvalidMachineId);
@@ -116,10 +235,88 @@ pub fn verify_license(
     Ok(payload)
 }
 
+/// Verify a license and check it against the caller's `Expectations`.
+///
+/// Runs `verify_license` first, so every error it returns is passed through
+/// unchanged. A valid license is then checked for the expected product,
+/// each required feature (granted and not expired, see
+/// `LicensePayload::feature_active`) and the minimum remaining time, in that
+/// order.
+///
+/// # Errors
+///
+/// Everything `verify_license` returns, plus
+/// `TuishError::ExpectationUnmet` with the first expectation that failed.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use tuish::{verify_license_expecting, Expectations, TuishError, UnmetExpectation};
+///
+/// # fn example(license: &str, public_key: &str) {
+/// let expect = Expectations::new().product_id("prod_xyz789").require_feature("pro");
+/// match verify_license_expecting(license, public_key, None, &expect) {
+///     Ok(_) => println!("Pro unlocked"),
+///     Err(TuishError::ExpectationUnmet(UnmetExpectation::FeatureMissing(feature))) => {
+///         println!("Upgrade to unlock {feature}")
+///     }
+///     Err(e) => eprintln!("Verification failed: {}", e),
+/// }
+/// # }
+/// ```
+pub fn verify_license_expecting(
+    license_key: &str,
+    public_key: &str,
+    machine_id: Option<&str>,
+    expect: &Expectations,
+) -> Result<LicensePayload, TuishError> {
+    let payload = verify_license(license_key, public_key, machine_id)?;
+    check_expectations(&payload, expect).map_err(|unmet| {
+        debug!(license_id = %payload.lid, reason = %unmet, "License expectation unmet");
+        TuishError::ExpectationUnmet(unmet)
+    })?;
+    Ok(payload)
+}
+
+fn check_expectations(
+    payload: &LicensePayload,
+    expect: &Expectations,
+) -> Result<(), UnmetExpectation> {
+    if let Some(expected) = &expect.product_id {
+        if &payload.pid != expected {
+            return Err(UnmetExpectation::ProductMismatch {
+                expected: expected.clone(),
+                actual: payload.pid.clone(),
+            });
+        }
+    }
+
+    if let Some(feature) = expect
+        .require_features
+        .iter()
+        .find(|feature| !payload.feature_active(feature))
+    {
+        return Err(UnmetExpectation::FeatureMissing(feature.clone()));
+    }
+
+    if let (Some(required), Some(remaining_ms)) = (expect.min_remaining, payload.time_remaining()) {
+        let remaining = Duration::from_millis(remaining_ms.max(0) as u64);
+        if remaining < required {
+            return Err(UnmetExpectation::InsufficientTime {
+                remaining,
+                required,
+            });
+        }
+    }
+
+    Ok(())
+}
+
 /// Parse a license string into its components without verifying the signature.
 ///
 /// This is useful for extracting license information for display purposes
-/// before performing full verification.
+/// before performing full verification. The key is normalized first; see
+/// `normalize_license_key`.
 ///
 /// # Arguments
 ///
@@ -131,10 +328,28 @@ pub fn verify_license(
 ///
 /// # Errors
 ///
-/// Returns `TuishError::InvalidLicense` if the format is invalid.
+/// Returns `TuishError::InvalidLicense` if the format is invalid or the
+/// input is longer than `MAX_LICENSE_KEY_LEN`.
 pub fn parse_license(
     license_key: &str,
 ) -> Result<(LicenseHeader, LicensePayload, Vec<u8>), TuishError> {
+    parse_license_with_max_len(license_key, MAX_LICENSE_KEY_LEN)
+}
+
+/// `parse_license` with a custom input length limit, in bytes.
+///
+/// The limit applies to the raw input, before normalization.
+///
+/// # Errors
+///
+/// Returns `TuishError::InvalidLicense("license too large")` if
+/// `license_key` is longer than `max_len`, otherwise as `parse_license`.
+pub fn parse_license_with_max_len(
+    license_key: &str,
+    max_len: usize,
+) -> Result<(LicenseHeader, LicensePayload, Vec<u8>), TuishError> {
+    check_license_len(license_key.len(), max_len)?;
+    let license_key = normalize_license_key(license_key);
     let parts: Vec<&str> = license_key.split('.').collect();
 
     if parts.len() != 3 {
@@ -144,17 +359,182 @@ pub fn parse_license(
         )));
     }
 
-    let header_b64 = parts[0];
-    let payload_b64 = parts[1];
-    let signature_b64 = parts[2];
+    parse_components(parts[0], parts[1], parts[2])
+}
+
+/// A license split into its base64url segments, borrowed from the input
+///
+/// Returned by `parse_license_borrowed`. Nothing is decoded up front: the
+/// signature can be checked with `verify_signature` before paying for the
+/// JSON `header` and `payload`, and checking it does not allocate.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LicenseParts<'a> {
+    /// Base64url header segment
+    pub header_b64: &'a str,
+    /// Base64url payload segment
+    pub payload_b64: &'a str,
+    /// Base64url signature segment
+    pub signature_b64: &'a str,
+    /// `header_b64.payload_b64`, the signed message
+    message: &'a str,
+}
+
+impl<'a> LicenseParts<'a> {
+    /// The signed message, `"{header_b64}.{payload_b64}"`
+    pub fn message(&self) -> &'a str {
+        self.message
+    }
+
+    /// Decode the header, checking the algorithm is Ed25519
+    ///
+    /// # Errors
+    ///
+    /// Returns `TuishError::InvalidLicense` as `parse_license` does.
+    pub fn header(&self) -> Result<LicenseHeader, TuishError> {
+        decode_header(self.header_b64)
+    }
+
+    /// Decode the payload
+    ///
+    /// # Errors
+    ///
+    /// Returns `TuishError::InvalidLicense` as `parse_license` does.
+    pub fn payload(&self) -> Result<LicensePayload, TuishError> {
+        decode_payload(self.payload_b64)
+    }
+
+    /// Decode the signature onto the stack
+    ///
+    /// # Errors
+    ///
+    /// Returns `TuishError::InvalidLicense` if the segment is not base64url,
+    /// or `TuishError::InvalidSignature` if it is not 64 bytes long.
+    pub fn signature_bytes(&self) -> Result<[u8; 64], TuishError> {
+        let mut signature = [0u8; 64];
+        let len = URL_SAFE_NO_PAD
+            .decode_slice(self.signature_b64, &mut signature)
+            .map_err(|e| match e {
+                base64::DecodeSliceError::DecodeError(e) => {
+                    TuishError::InvalidLicense(format!("invalid signature base64: {}", e))
+                }
+                base64::DecodeSliceError::OutputSliceTooSmall => TuishError::InvalidSignature,
+            })?;
+        if len != signature.len() {
+            return Err(TuishError::InvalidSignature);
+        }
+        Ok(signature)
+    }
+
+    /// Check the signature over `message` against `public_key`
+    ///
+    /// Only the signature is checked: decode the `payload` afterwards for
+    /// the expiry, machine and product checks `verify_license` performs.
+    ///
+    /// # Errors
+    ///
+    /// Same as `verify_signature`, plus the `signature_bytes` errors.
+    pub fn verify_signature(&self, public_key: &str) -> Result<(), TuishError> {
+        verify_signature_bytes(
+            public_key,
+            self.message.as_bytes(),
+            &self.signature_bytes()?,
+        )
+    }
+}
 
+/// Split a license into borrowed segments without decoding anything.
+///
+/// For servers verifying licenses in bulk: unlike `parse_license`, this
+/// allocates nothing. Surrounding whitespace and quotes are trimmed, but a
+/// key with whitespace inside (e.g. a line-wrapped paste) is rejected, as
+/// removing it would need a copy; pass such keys through
+/// `normalize_license_key` first.
+///
+/// # Errors
+///
+/// Returns `TuishError::InvalidLicense` if the key is longer than
+/// `MAX_LICENSE_KEY_LEN`, contains whitespace, or does not have three
+/// non-empty parts.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use tuish::parse_license_borrowed;
+///
+/// # fn example(license_key: &str, public_key: &str) -> Result<(), tuish::TuishError> {
+/// let parts = parse_license_borrowed(license_key)?;
+/// parts.verify_signature(public_key)?;
+/// let payload = parts.payload()?;
+/// println!("Genuine license {}", payload.lid);
+/// # Ok(())
+/// # }
+/// ```
+pub fn parse_license_borrowed(license_key: &str) -> Result<LicenseParts<'_>, TuishError> {
+    check_license_len(license_key.len(), MAX_LICENSE_KEY_LEN)?;
+    let license_key =
+        license_key.trim_matches(|c: char| c.is_whitespace() || LICENSE_QUOTES.contains(&c));
+    if license_key.contains(char::is_whitespace) {
+        return Err(TuishError::InvalidLicense(
+            "whitespace inside license; normalize it first".to_string(),
+        ));
+    }
+
+    let mut parts = license_key.splitn(4, '.');
+    let (Some(header_b64), Some(payload_b64), Some(signature_b64), None) =
+        (parts.next(), parts.next(), parts.next(), parts.next())
+    else {
+        return Err(TuishError::InvalidLicense(format!(
+            "expected 3 parts, got {}",
+            license_key.split('.').count()
+        )));
+    };
+    if header_b64.is_empty() || payload_b64.is_empty() || signature_b64.is_empty() {
+        return Err(TuishError::InvalidLicense(
+            "empty part in license".to_string(),
+        ));
+    }
+
+    Ok(LicenseParts {
+        header_b64,
+        payload_b64,
+        signature_b64,
+        message: &license_key[..header_b64.len() + 1 + payload_b64.len()],
+    })
+}
+
+/// Reject license input over `max_len` bytes, before any decoding.
+fn check_license_len(len: usize, max_len: usize) -> Result<(), TuishError> {
+    if len > max_len {
+        return Err(TuishError::InvalidLicense("license too large".to_string()));
+    }
+    Ok(())
+}
+
+/// Decode the three base64url parts of a license.
+fn parse_components(
+    header_b64: &str,
+    payload_b64: &str,
+    signature_b64: &str,
+) -> Result<(LicenseHeader, LicensePayload, Vec<u8>), TuishError> {
     if header_b64.is_empty() || payload_b64.is_empty() || signature_b64.is_empty() {
         return Err(TuishError::InvalidLicense(
             "empty part in license".to_string(),
         ));
     }
 
-    // Decode header
+    let header = decode_header(header_b64)?;
+    let payload = decode_payload(payload_b64)?;
+
+    // Decode signature
+    let signature_bytes = URL_SAFE_NO_PAD
+        .decode(signature_b64)
+        .map_err(|e| TuishError::InvalidLicense(format!("invalid signature base64: {}", e)))?;
+
+    Ok((header, payload, signature_bytes))
+}
+
+/// Decode and validate a license header segment.
+fn decode_header(header_b64: &str) -> Result<LicenseHeader, TuishError> {
     let header_bytes = URL_SAFE_NO_PAD
         .decode(header_b64)
         .map_err(|e| TuishError::InvalidLicense(format!("invalid header base64: {}", e)))?;
@@ -162,7 +542,6 @@ pub fn parse_license(
     let header: LicenseHeader = serde_json::from_slice(&header_bytes)
         .map_err(|e| TuishError::InvalidLicense(format!("invalid header JSON: {}", e)))?;
 
-    // Validate header
     if header.alg != "ed25519" {
         return Err(TuishError::InvalidLicense(format!(
             "unsupported algorithm: {}",
@@ -170,27 +549,24 @@ pub fn parse_license(
         )));
     }
 
-    // Decode payload
+    Ok(header)
+}
+
+/// Decode a license payload segment.
+fn decode_payload(payload_b64: &str) -> Result<LicensePayload, TuishError> {
     let payload_bytes = URL_SAFE_NO_PAD
         .decode(payload_b64)
         .map_err(|e| TuishError::InvalidLicense(format!("invalid payload base64: {}", e)))?;
 
-    let payload: LicensePayload = serde_json::from_slice(&payload_bytes)
-        .map_err(|e| TuishError::InvalidLicense(format!("invalid payload JSON: {}", e)))?;
-
-    // Decode signature
-    let signature_bytes = URL_SAFE_NO_PAD
-        .decode(signature_b64)
-        .map_err(|e| TuishError::InvalidLicense(format!("invalid signature base64: {}", e)))?;
-
-    Ok((header, payload, signature_bytes))
+    serde_json::from_slice(&payload_bytes)
+        .map_err(|e| TuishError::InvalidLicense(format!("invalid payload JSON: {}", e)))
 }
 
 /// Verify an Ed25519 signature over a message.
 ///
 /// # Arguments
 ///
-/// * `public_key` - The Ed25519 public key (SPKI base64 or 64-char hex)
+/// * `public_key` - The Ed25519 public key (SPKI base64, PEM, or 64-char hex)
 /// * `message` - The message that was signed
 /// * `signature` - The signature bytes
 ///
@@ -202,6 +578,155 @@ pub fn verify_signature(
     public_key: &str,
     message: &str,
     signature: &[u8],
+) -> Result<(), TuishError> {
+    verify_signature_bytes(public_key, message.as_bytes(), signature)
+}
+
+/// Verify a Tuish webhook signature.
+///
+/// Webhooks carry a `Tuish-Signature` header of the form
+/// `t=<unix seconds>,v1=<base64url signature>`, where the signature is
+/// Ed25519 over `"<t>." + body`, with `<t>` exactly as it appears in the
+/// header. Several `v1` entries may be present during key rotation; any one
+/// matching is accepted.
+///
+/// # Arguments
+///
+/// * `body` - The raw request body, exactly as received
+/// * `signature_header` - The value of the `Tuish-Signature` header
+/// * `public_key` - The Ed25519 public key (SPKI base64, PEM, or 64-char hex)
+/// * `tolerance` - Maximum allowed difference between `t` and now
+///
+/// # Errors
+///
+/// * `TuishError::ParseError` - Header is malformed
+/// * `TuishError::StaleWebhook` - Timestamp is outside `tolerance`
+/// * `TuishError::InvalidPublicKey` - Public key format is invalid
+/// * `TuishError::InvalidSignature` - No signature matches the body
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use std::time::Duration;
+/// use tuish::verify_webhook;
+///
+/// # fn example(body: &[u8], header: &str) -> Result<(), tuish::TuishError> {
+/// verify_webhook(body, header, "your-public-key-hex", Duration::from_secs(300))?;
+/// // Safe to process the event
+/// # Ok(())
+/// # }
+/// ```
+pub fn verify_webhook(
+    body: &[u8],
+    signature_header: &str,
+    public_key: &str,
+    tolerance: Duration,
+) -> Result<(), TuishError> {
+    let mut timestamp = None;
+    let mut signatures = Vec::new();
+    for part in signature_header.split(',') {
+        match part.trim().split_once('=') {
+            Some(("t", value)) => timestamp = value.parse::<i64>().ok().map(|t| (value, t)),
+            Some(("v1", value)) => signatures.push(value),
+            _ => {}
+        }
+    }
+
+    let (raw_timestamp, timestamp) = timestamp.ok_or_else(|| {
+        TuishError::ParseError("webhook signature header missing timestamp".to_string())
+    })?;
+    if signatures.is_empty() {
+        return Err(TuishError::ParseError(
+            "webhook signature header missing v1 signature".to_string(),
+        ));
+    }
+
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs() as i64)
+        .unwrap_or(0);
+    let age = now.saturating_sub(timestamp);
+    if age.unsigned_abs() > tolerance.as_secs() {
+        debug!(
+            timestamp = timestamp,
+            now = now,
+            "Webhook timestamp outside tolerance"
+        );
+        return Err(TuishError::StaleWebhook { age_secs: age });
+    }
+
+    // Signed over the header's `t` exactly as sent, not the parsed number
+    let mut message = format!("{}.", raw_timestamp).into_bytes();
+    message.extend_from_slice(body);
+
+    for signature in signatures {
+        let Ok(signature_bytes) = URL_SAFE_NO_PAD.decode(signature) else {
+            continue;
+        };
+        match verify_signature_bytes(public_key, &message, &signature_bytes) {
+            Ok(()) => return Ok(()),
+            Err(TuishError::InvalidPublicKey(e)) => return Err(TuishError::InvalidPublicKey(e)),
+            Err(_) => continue,
+        }
+    }
+
+    Err(TuishError::InvalidSignature)
+}
+
+/// Verify a signed revocation list and return its contents.
+///
+/// The list uses the same `header.payload.signature` format and signing key
+/// as licenses; the payload is a JSON `RevocationList`.
+///
+/// # Errors
+///
+/// * `TuishError::InvalidLicense` - Token format is invalid
+/// * `TuishError::InvalidPublicKey` - Public key format is invalid
+/// * `TuishError::InvalidSignature` - Signature verification failed
+pub fn verify_revocation_list(
+    signed_list: &str,
+    public_key: &str,
+) -> Result<RevocationList, TuishError> {
+    let parts: Vec<&str> = signed_list.trim().split('.').collect();
+    if parts.len() != 3 || parts.iter().any(|part| part.is_empty()) {
+        return Err(TuishError::InvalidLicense(
+            "revocation list must have 3 non-empty parts".to_string(),
+        ));
+    }
+
+    let header_bytes = URL_SAFE_NO_PAD
+        .decode(parts[0])
+        .map_err(|e| TuishError::InvalidLicense(format!("invalid header base64: {}", e)))?;
+    let header: LicenseHeader = serde_json::from_slice(&header_bytes)
+        .map_err(|e| TuishError::InvalidLicense(format!("invalid header JSON: {}", e)))?;
+    if header.alg != "ed25519" {
+        return Err(TuishError::InvalidLicense(format!(
+            "unsupported algorithm: {}",
+            header.alg
+        )));
+    }
+
+    let signature = URL_SAFE_NO_PAD
+        .decode(parts[2])
+        .map_err(|e| TuishError::InvalidLicense(format!("invalid signature base64: {}", e)))?;
+    verify_signature(
+        public_key,
+        &format!("{}.{}", parts[0], parts[1]),
+        &signature,
+    )?;
+
+    let payload_bytes = URL_SAFE_NO_PAD
+        .decode(parts[1])
+        .map_err(|e| TuishError::InvalidLicense(format!("invalid payload base64: {}", e)))?;
+    serde_json::from_slice(&payload_bytes)
+        .map_err(|e| TuishError::InvalidLicense(format!("invalid revocation list JSON: {}", e)))
+}
+
+/// Verify an Ed25519 signature over raw message bytes.
+fn verify_signature_bytes(
+    public_key: &str,
+    message: &[u8],
+    signature: &[u8],
 ) -> Result<(), TuishError> {
     let key_bytes = parse_public_key(public_key)?;
 
@@ -212,7 +737,7 @@ pub fn verify_signature(
     let signature = Signature::from_slice(signature)?;
 
     verifying_key
-        .verify(message.as_bytes(), &signature)
+        .verify(message, &signature)
         .map_err(|_| TuishError::InvalidSignature)?;
 
     Ok(())
@@ -253,16 +778,200 @@ pub fn get_license_time_remaining(license_key: &str) -> Option<i64> {
     extract_license_payload(license_key).and_then(|p| p.time_remaining())
 }
 
+/// Redact a license key so it can be logged safely.
+///
+/// Keeps the header and the first and last four characters of the signature,
+/// e.g. `eyJhbGciOiJlZDI1NTE5IiwidmVyIjoxfQ.[redacted].AbCd…WxYz`. The payload
+/// is never included. Input that is not `header.payload.signature` is fully
+/// redacted.
+pub fn redact_license(license_key: &str) -> String {
+    let parts: Vec<&str> = license_key.trim().split('.').collect();
+    match parts.as_slice() {
+        [header, _, signature] if signature.is_ascii() && signature.len() > 8 => format!(
+            "{}.[redacted].{}…{}",
+            header,
+            &signature[..4],
+            &signature[signature.len() - 4..]
+        ),
+        _ => "[redacted]".to_string(),
+    }
+}
+
+/// Redact every license key (or other signed `eyJ…` token) found in `text`.
+///
+/// Use this before logging API responses, which may embed license keys.
+/// Trailing dots, e.g. a full stop after a key in a sentence, are kept
+/// outside the token; any token with three or more segments is redacted.
+pub fn redact_license_keys(text: &str) -> String {
+    let is_token_char = |c: char| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.');
+
+    let mut redacted = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find("eyJ") {
+        let end = rest[start..]
+            .find(|c: char| !is_token_char(c))
+            .map_or(rest.len(), |len| start + len);
+        let token = rest[start..end].trim_end_matches('.');
+        let end = start + token.len();
+
+        redacted.push_str(&rest[..start]);
+        if token.split('.').count() >= 3 {
+            redacted.push_str(&redact_license(token));
+        } else {
+            redacted.push_str(token);
+        }
+        rest = &rest[end..];
+    }
+    redacted.push_str(rest);
+    redacted
+}
+
+/// Clean up a license key pasted from an email or chat.
+///
+/// Trims surrounding whitespace and quotes (straight, curly, or backticks)
+/// and removes whitespace inside the key, such as CRLF line wraps. None of
+/// these characters occur in base64url, so a well-formed key is returned
+/// unchanged.
+///
+/// # Example
+///
+/// ```rust
+/// use tuish::crypto::normalize_license_key;
+///
+/// assert_eq!(normalize_license_key(" \"abc.de\r\nf.ghi\"\n"), "abc.def.ghi");
+/// ```
+pub fn normalize_license_key(license_key: &str) -> Cow<'_, str> {
+    let trimmed =
+        license_key.trim_matches(|c: char| c.is_whitespace() || LICENSE_QUOTES.contains(&c));
+    if trimmed.contains(char::is_whitespace) {
+        Cow::Owned(trimmed.chars().filter(|c| !c.is_whitespace()).collect())
+    } else {
+        Cow::Borrowed(trimmed)
+    }
+}
+
 // ============================================================================
 // Internal Functions
 // ============================================================================
 
-/// Parse a public key from SPKI base64 or hex format.
+/// Strip PEM armor from a public key, returning the base64 body.
+///
+/// Accepts `-----BEGIN PUBLIC KEY-----` blocks with LF or CRLF line endings.
+/// Any other input is returned trimmed and otherwise unchanged.
+pub(crate) fn strip_pem(public_key: &str) -> Cow<'_, str> {
+    let trimmed = public_key.trim();
+    if !trimmed.starts_with(PEM_BEGIN) {
+        return Cow::Borrowed(trimmed);
+    }
+
+    Cow::Owned(
+        trimmed
+            .lines()
+            .map(str::trim)
+            .skip_while(|line| *line != PEM_BEGIN)
+            .skip(1)
+            .take_while(|line| *line != PEM_END)
+            .collect(),
+    )
+}
+
+/// Ed25519 SPKI header as it appears at the start of the base64 key
+const SPKI_BASE64_PREFIX: &[u8] = b"MCowBQYDK2VwAyEA";
+
+/// Length of a base64-encoded SPKI key, including padding
+const SPKI_BASE64_LEN: usize = 60;
+
+/// Compile-time shape check behind `include_public_key!`.
+///
+/// Panics (a build error in const context) unless `public_key` looks like
+/// an Ed25519 key in one of the builder's formats: SPKI base64, PEM-armored
+/// SPKI, or 64-character hex. Returns the input unchanged.
+#[doc(hidden)]
+pub const fn checked_public_key(public_key: &str) -> &str {
+    if !is_public_key_shape(public_key.as_bytes()) {
+        panic!("invalid public key: expected SPKI base64 (MCow...), a PEM public key, or 64-character hex string");
+    }
+    public_key
+}
+
+const fn is_public_key_shape(bytes: &[u8]) -> bool {
+    let mut start = 0;
+    let mut end = bytes.len();
+    while start < end && bytes[start].is_ascii_whitespace() {
+        start += 1;
+    }
+    while end > start && bytes[end - 1].is_ascii_whitespace() {
+        end -= 1;
+    }
+
+    if has_prefix_at(bytes, start, PEM_BEGIN.as_bytes()) {
+        let body = start + PEM_BEGIN.len();
+        let footer = end.saturating_sub(PEM_END.len());
+        return footer >= body
+            && has_prefix_at(bytes, footer, PEM_END.as_bytes())
+            && is_spki_base64(bytes, body, footer, true);
+    }
+
+    if end - start == 64 {
+        let mut i = start;
+        while i < end && bytes[i].is_ascii_hexdigit() {
+            i += 1;
+        }
+        if i == end {
+            return true;
+        }
+    }
+
+    is_spki_base64(bytes, start, end, false)
+}
+
+/// Whether `bytes[start..end]` is a base64 Ed25519 SPKI key, optionally
+/// split across lines
+const fn is_spki_base64(bytes: &[u8], start: usize, end: usize, allow_whitespace: bool) -> bool {
+    let mut count = 0;
+    let mut i = start;
+    while i < end {
+        let b = bytes[i];
+        i += 1;
+        if allow_whitespace && b.is_ascii_whitespace() {
+            continue;
+        }
+        let valid = if count < SPKI_BASE64_PREFIX.len() {
+            b == SPKI_BASE64_PREFIX[count]
+        } else {
+            b.is_ascii_alphanumeric() || b == b'+' || b == b'/' || b == b'='
+        };
+        if !valid {
+            return false;
+        }
+        count += 1;
+    }
+    count == SPKI_BASE64_LEN
+}
+
+const fn has_prefix_at(bytes: &[u8], at: usize, prefix: &[u8]) -> bool {
+    if at + prefix.len() > bytes.len() {
+        return false;
+    }
+    let mut i = 0;
+    while i < prefix.len() {
+        if bytes[at + i] != prefix[i] {
+            return false;
+        }
+        i += 1;
+    }
+    true
+}
+
+/// Parse a public key from SPKI base64, PEM, or hex format.
 ///
 /// Supports:
 /// - SPKI base64 format (44 bytes when decoded, 12-byte header + 32-byte key)
+/// - PEM-armored SPKI (`-----BEGIN PUBLIC KEY-----`)
 /// - Raw hex format (64 characters = 32 bytes)
 fn parse_public_key(public_key: &str) -> Result<[u8; ED25519_PUBLIC_KEY_SIZE], TuishError> {
+    let public_key = &*strip_pem(public_key);
+
     // Try SPKI base64 format first (starts with MC4C for Ed25519)
     if public_key.starts_with("MC4C") || public_key.starts_with("MCow") {
         return parse_spki_public_key(public_key);
@@ -383,6 +1092,8 @@ pub(crate) fn hex_to_bytes(hex: &str) -> Result<Vec<u8>, TuishError> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::test_support::{sign_test_claims, sign_test_license};
+    use serde_json::json;
 
     // Test key pair generated for testing purposes
     // Private key (PKCS8): MC4CAQAwBQYDK2VwBCIEIPMrmRQ5jnPY7gHDw8MlHLn3Wdm3Rqnj9I+9AW9N2VKa
@@ -421,6 +1132,63 @@ mod tests {
         assert!(result.is_ok());
     }
 
+    #[test]
+    fn test_checked_public_key_accepts_builder_formats() {
+        const KEY: &str = checked_public_key(TEST_PUBLIC_KEY_HEX);
+        assert_eq!(KEY, TEST_PUBLIC_KEY_HEX);
+
+        let pem = format!(
+            "-----BEGIN PUBLIC KEY-----\r\n{}\r\n{}\r\n-----END PUBLIC KEY-----\r\n",
+            &TEST_PUBLIC_KEY_SPKI[..32],
+            &TEST_PUBLIC_KEY_SPKI[32..]
+        );
+        for key in [
+            TEST_PUBLIC_KEY_SPKI,
+            &format!("{}\n", TEST_PUBLIC_KEY_HEX),
+            &pem,
+        ] {
+            assert!(is_public_key_shape(key.as_bytes()), "{key:?}");
+        }
+    }
+
+    #[test]
+    fn test_checked_public_key_rejects_malformed() {
+        for key in [
+            "",
+            &TEST_PUBLIC_KEY_HEX[1..],
+            &TEST_PUBLIC_KEY_SPKI[..59],
+            "MCowBQYDK2VwAyEAz3HnN8J8O+kCNz4h1HoK LLQGpMZ9Pu7xH7c7N4KNQN4=",
+            "-----BEGIN PUBLIC KEY-----\n-----END PUBLIC KEY-----",
+            &format!("-----BEGIN PUBLIC KEY-----\n{}\n", TEST_PUBLIC_KEY_SPKI),
+        ] {
+            assert!(!is_public_key_shape(key.as_bytes()), "{key:?}");
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "invalid public key")]
+    fn test_checked_public_key_panics() {
+        checked_public_key("not a key");
+    }
+
+    #[test]
+    fn test_parse_public_key_pem() {
+        let spki = parse_public_key(TEST_PUBLIC_KEY_SPKI).unwrap();
+        let lf = format!(
+            "-----BEGIN PUBLIC KEY-----\n{}\n{}\n-----END PUBLIC KEY-----\n",
+            &TEST_PUBLIC_KEY_SPKI[..32],
+            &TEST_PUBLIC_KEY_SPKI[32..]
+        );
+        let crlf = lf.replace('\n', "\r\n");
+
+        assert_eq!(parse_public_key(&lf).unwrap(), spki);
+        assert_eq!(parse_public_key(&crlf).unwrap(), spki);
+        assert_eq!(bytes_to_hex(&spki), TEST_PUBLIC_KEY_HEX);
+
+        // Armor with no body
+        assert!(parse_public_key("-----BEGIN PUBLIC KEY-----\n-----END PUBLIC KEY-----").is_err());
+    }
+
     #[test]
     fn test_parse_public_key_invalid() {
         // Too short
@@ -637,4 +1405,535 @@ mod tests {
         // Invalid format
         assert!(get_license_time_remaining("invalid").is_none());
     }
+
+    fn signed_webhook(body: &[u8], timestamp: impl std::fmt::Display) -> (String, String) {
+        use ed25519_dalek::{Signer, SigningKey};
+
+        let key = SigningKey::from_bytes(&[9u8; 32]);
+        let mut message = format!("{}.", timestamp).into_bytes();
+        message.extend_from_slice(body);
+        let signature = to_base64url(&key.sign(&message).to_bytes());
+        (
+            format!("t={},v1={}", timestamp, signature),
+            bytes_to_hex(key.verifying_key().as_bytes()),
+        )
+    }
+
+    fn now_secs() -> i64 {
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_secs() as i64
+    }
+
+    #[test]
+    fn test_verify_webhook_valid() {
+        let body = br#"{"type":"purchase.completed","data":{"licenseId":"lic_1"}}"#;
+        let (header, public_key) = signed_webhook(body, now_secs());
+
+        assert!(verify_webhook(body, &header, &public_key, Duration::from_secs(300)).is_ok());
+
+        // Extra whitespace and an unrelated rotated signature are tolerated
+        let rotated = format!("{}, v1=AAAA", header.replace(',', ", "));
+        assert!(verify_webhook(body, &rotated, &public_key, Duration::from_secs(300)).is_ok());
+    }
+
+    #[test]
+    fn test_verify_webhook_tampered_body() {
+        let body = br#"{"type":"purchase.completed","amount":1999}"#;
+        let (header, public_key) = signed_webhook(body, now_secs());
+
+        let tampered = br#"{"type":"purchase.completed","amount":1}"#;
+        assert!(matches!(
+            verify_webhook(tampered, &header, &public_key, Duration::from_secs(300)),
+            Err(TuishError::InvalidSignature)
+        ));
+    }
+
+    #[test]
+    fn test_verify_webhook_stale_timestamp() {
+        let body = br#"{"type":"license.revoked"}"#;
+        let (header, public_key) = signed_webhook(body, now_secs() - 3600);
+
+        assert!(matches!(
+            verify_webhook(body, &header, &public_key, Duration::from_secs(300)),
+            Err(TuishError::StaleWebhook { .. })
+        ));
+        // Accepted with a wide enough tolerance
+        assert!(verify_webhook(body, &header, &public_key, Duration::from_secs(7200)).is_ok());
+    }
+
+    #[test]
+    fn test_verify_webhook_signs_raw_timestamp() {
+        let body = br#"{"type":"license.created"}"#;
+        let now = now_secs();
+        let padded = format!("0{}", now);
+        let (header, public_key) = signed_webhook(body, &padded);
+        let tolerance = Duration::from_secs(300);
+
+        assert!(verify_webhook(body, &header, &public_key, tolerance).is_ok());
+
+        // Same instant, but not the bytes that were signed
+        let restated = header.replace(&format!("t={}", padded), &format!("t={}", now));
+        assert!(matches!(
+            verify_webhook(body, &restated, &public_key, tolerance),
+            Err(TuishError::InvalidSignature)
+        ));
+    }
+
+    #[test]
+    fn test_verify_webhook_malformed_header() {
+        let body = b"{}";
+        let (_, public_key) = signed_webhook(body, now_secs());
+        let tolerance = Duration::from_secs(300);
+
+        assert!(matches!(
+            verify_webhook(body, "v1=abc", &public_key, tolerance),
+            Err(TuishError::ParseError(_))
+        ));
+        assert!(matches!(
+            verify_webhook(body, &format!("t={}", now_secs()), &public_key, tolerance),
+            Err(TuishError::ParseError(_))
+        ));
+    }
+
+    #[test]
+    fn test_verify_revocation_list() {
+        let (token, public_key) = sign_test_claims(
+            &json!({"productId":"prod_x","revokedIds":["lic_bad"],"issuedAt":1000}),
+        );
+
+        let list = verify_revocation_list(&token, &public_key).unwrap();
+        assert_eq!(list.product_id, "prod_x");
+        assert!(list.is_revoked("lic_bad"));
+        assert_eq!(list.issued_at, 1000);
+
+        // Wrong key
+        assert!(matches!(
+            verify_revocation_list(&token, TEST_PUBLIC_KEY_HEX),
+            Err(TuishError::InvalidSignature)
+        ));
+
+        // Tampered payload (an attacker removing an id)
+        let parts: Vec<&str> = token.split('.').collect();
+        let forged = format!(
+            "{}.{}.{}",
+            parts[0],
+            to_base64url(br#"{"productId":"prod_x","revokedIds":[],"issuedAt":1000}"#),
+            parts[2]
+        );
+        assert!(verify_revocation_list(&forged, &public_key).is_err());
+        assert!(verify_revocation_list("not-a-list", &public_key).is_err());
+    }
+
+    #[test]
+    fn test_verify_license_machine_allowlist() {
+        let (license, public_key) = sign_test_license(
+            json!({"lid":"x","pid":"y","cid":"z","did":"w","features":[],"iat":0,"exp":null,"mid":"fp_a","mids":["fp_b","fp_c"]}),
+        );
+
+        for fingerprint in ["fp_a", "fp_b", "fp_c"] {
+            assert!(verify_license(&license, &public_key, Some(fingerprint)).is_ok());
+        }
+        assert!(matches!(
+            verify_license(&license, &public_key, Some("fp_d")),
+            Err(TuishError::InvalidMachineId)
+        ));
+    }
+
+    fn license_not_before(nbf: i64) -> (String, String) {
+        sign_test_license(
+            json!({"lid":"x","pid":"y","cid":"z","did":"w","features":[],"iat":0,"exp":null,"mid":null,"nbf":nbf}),
+        )
+    }
+
+    #[test]
+    fn test_verify_license_not_yet_valid() {
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_millis() as i64;
+        let minute = Duration::from_secs(60);
+
+        // Started a moment ago
+        let (started, public_key) = license_not_before(now - 1000);
+        assert!(verify_license_with_skew(&started, &public_key, None, Duration::ZERO).is_ok());
+
+        // Starts in 10 minutes: rejected unless the skew covers it
+        let (future, _) = license_not_before(now + 10 * 60_000);
+        assert!(matches!(
+            verify_license_with_skew(&future, &public_key, None, Duration::ZERO),
+            Err(TuishError::NotYetValid)
+        ));
+        assert!(matches!(
+            verify_license_with_skew(&future, &public_key, None, 9 * minute),
+            Err(TuishError::NotYetValid)
+        ));
+        assert!(verify_license_with_skew(&future, &public_key, None, 11 * minute).is_ok());
+
+        // `verify_license` tolerates `DEFAULT_CLOCK_SKEW`
+        let (within_skew, _) = license_not_before(now + 30_000);
+        assert!(verify_license(&within_skew, &public_key, None).is_ok());
+        assert!(matches!(
+            verify_license(&future, &public_key, None),
+            Err(TuishError::NotYetValid)
+        ));
+        assert_eq!(
+            extract_license_payload(&future).unwrap().nbf,
+            Some(now + 10 * 60_000)
+        );
+    }
+
+    #[test]
+    fn test_pasted_license_keys_are_normalized() {
+        let (license, public_key) = sign_test_license(
+            json!({"lid":"x","pid":"y","cid":"z","did":"w","features":[],"iat":0,"exp":null,"mid":null}),
+        );
+        let (head, tail) = license.split_at(license.len() / 2);
+
+        for pasted in [
+            format!("\"{}\"", license),
+            format!("'{}'", license),
+            format!("  {}  \n", license),
+            format!("\u{201c}{}\u{201d}", license),
+            format!("{}\r\n{}", head, tail),
+            format!(" \"{}\r\n  {}\"\r\n", head, tail),
+        ] {
+            assert_eq!(normalize_license_key(&pasted), license, "{:?}", pasted);
+            assert!(parse_license(&pasted).is_ok(), "{:?}", pasted);
+            assert!(
+                verify_license(&pasted, &public_key, None).is_ok(),
+                "{:?}",
+                pasted
+            );
+        }
+
+        // Well-formed keys, including '-' and '_', pass through untouched
+        assert!(matches!(normalize_license_key(&license), Cow::Borrowed(_)));
+        assert_eq!(normalize_license_key("a-b_c.d-e.f_g"), "a-b_c.d-e.f_g");
+    }
+
+    #[test]
+    fn test_verify_components_matches_verify_license() {
+        let (license, public_key) = sign_test_license(
+            json!({"lid":"x","pid":"y","cid":"z","did":"w","features":["pro"],"iat":0,"exp":null,"mid":"fp_a"}),
+        );
+        let parts: Vec<&str> = license.split('.').collect();
+        let components =
+            |machine_id| verify_components(parts[0], parts[1], parts[2], &public_key, machine_id);
+
+        assert_eq!(
+            components(Some("fp_a")).unwrap(),
+            verify_license(&license, &public_key, Some("fp_a")).unwrap()
+        );
+        assert!(matches!(
+            components(Some("fp_b")),
+            Err(TuishError::InvalidMachineId)
+        ));
+        assert!(matches!(
+            verify_components(parts[0], parts[1], parts[2], TEST_PUBLIC_KEY_HEX, None),
+            Err(TuishError::InvalidSignature)
+        ));
+
+        // Signature doesn't cover a different payload
+        let (other, _) = sign_test_license(
+            json!({"lid":"x","pid":"other","cid":"z","did":"w","features":[],"iat":0,"exp":null,"mid":null}),
+        );
+        let other_payload = other.split('.').nth(1).unwrap();
+        assert!(matches!(
+            verify_components(parts[0], other_payload, parts[2], &public_key, None),
+            Err(TuishError::InvalidSignature)
+        ));
+        assert!(matches!(
+            verify_components(parts[0], "", parts[2], &public_key, None),
+            Err(TuishError::InvalidLicense(_))
+        ));
+    }
+
+    #[test]
+    fn test_verify_license_single_mid_unchanged() {
+        let (bound, public_key) = sign_test_license(
+            json!({"lid":"x","pid":"y","cid":"z","did":"w","features":[],"iat":0,"exp":null,"mid":"fp_a"}),
+        );
+        assert!(verify_license(&bound, &public_key, Some("fp_a")).is_ok());
+        assert!(matches!(
+            verify_license(&bound, &public_key, Some("fp_b")),
+            Err(TuishError::InvalidMachineId)
+        ));
+        assert!(verify_license(&bound, &public_key, None).is_ok());
+        assert!(extract_license_payload(&bound).unwrap().mids.is_empty());
+
+        // Unbound licenses verify on any machine
+        let (unbound, _) = sign_test_license(
+            json!({"lid":"x","pid":"y","cid":"z","did":"w","features":[],"iat":0,"exp":null,"mid":null}),
+        );
+        assert!(verify_license(&unbound, &public_key, Some("fp_anything")).is_ok());
+    }
+
+    #[test]
+    fn test_redact_license_hides_payload() {
+        let (license, _) = sign_test_license(
+            json!({"lid":"lic_secret","pid":"y","cid":"z","did":"w","features":[],"iat":0,"exp":null,"mid":null}),
+        );
+        let parts: Vec<&str> = license.split('.').collect();
+
+        let redacted = redact_license(&license);
+        assert!(!redacted.contains(parts[1]));
+        assert!(!redacted.contains(parts[2]));
+        assert!(redacted.starts_with(&format!("{}.[redacted].", parts[0])));
+        assert!(redacted.ends_with(&parts[2][parts[2].len() - 4..]));
+
+        assert_eq!(redact_license("not-a-license"), "[redacted]");
+        assert_eq!(redact_license("a.b.short"), "[redacted]");
+    }
+
+    #[test]
+    fn test_redact_license_keys_in_text() {
+        let (license, _) = sign_test_license(
+            json!({"lid":"lic_secret","pid":"y","cid":"z","did":"w","features":[],"iat":0,"exp":null,"mid":null}),
+        );
+        let payload = license.split('.').nth(1).unwrap();
+        let body = format!(
+            r#"{{"status":"complete","license":"{}","next":"eyJnot.a-token"}}"#,
+            license
+        );
+
+        let redacted = redact_license_keys(&body);
+        assert!(!redacted.contains(payload));
+        assert!(redacted.contains(&redact_license(&license)));
+        assert!(redacted.starts_with(r#"{"status":"complete","license":""#));
+        assert!(redacted.ends_with(r#"","next":"eyJnot.a-token"}"#));
+        assert_eq!(redact_license_keys("no keys here"), "no keys here");
+
+        // Full stop after the key
+        let sentence = format!("key {}.", license);
+        let redacted = redact_license_keys(&sentence);
+        assert!(!redacted.contains(payload));
+        assert_eq!(redacted, format!("key {}.", redact_license(&license)));
+
+        // Key inside a sentence
+        let sentence = format!("Your key is {}. Keep it safe.", license);
+        assert_eq!(
+            redact_license_keys(&sentence),
+            format!("Your key is {}. Keep it safe.", redact_license(&license))
+        );
+
+        // Extra segments are redacted outright
+        let redacted = redact_license_keys(&format!("{}.extra", license));
+        assert!(!redacted.contains(payload));
+        assert_eq!(redacted, "[redacted]");
+    }
+
+    fn expecting_license(exp: Option<i64>) -> (String, String) {
+        sign_test_license(
+            json!({"lid":"lic_1","pid":"prod_a","cid":"c","did":"d","features":["pro","export"],"iat":0,"exp":exp,"mid":null,"entitlements":[{"feature":"export","exp":1000}]}),
+        )
+    }
+
+    fn now_ms() -> i64 {
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_millis() as i64
+    }
+
+    fn unmet(result: Result<LicensePayload, TuishError>) -> UnmetExpectation {
+        match result {
+            Err(TuishError::ExpectationUnmet(unmet)) => unmet,
+            other => panic!("expected unmet expectation, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_verify_license_expecting_met() {
+        let (license, public_key) = expecting_license(Some(now_ms() + 30 * 86_400_000));
+        let expect = Expectations::new()
+            .product_id("prod_a")
+            .require_feature("pro")
+            .min_remaining(Duration::from_secs(7 * 86_400));
+
+        let payload = verify_license_expecting(&license, &public_key, None, &expect).unwrap();
+        assert_eq!(payload.lid, "lic_1");
+        assert!(
+            verify_license_expecting(&license, &public_key, None, &Expectations::default()).is_ok()
+        );
+    }
+
+    #[test]
+    fn test_verify_license_expecting_product_mismatch() {
+        let (license, public_key) = expecting_license(None);
+        let expect = Expectations::new().product_id("prod_b");
+
+        assert_eq!(
+            unmet(verify_license_expecting(
+                &license,
+                &public_key,
+                None,
+                &expect
+            )),
+            UnmetExpectation::ProductMismatch {
+                expected: "prod_b".into(),
+                actual: "prod_a".into(),
+            }
+        );
+    }
+
+    #[test]
+    fn test_verify_license_expecting_feature_missing() {
+        let (license, public_key) = expecting_license(None);
+
+        let expect = Expectations::new()
+            .require_feature("pro")
+            .require_feature("team");
+        assert_eq!(
+            unmet(verify_license_expecting(
+                &license,
+                &public_key,
+                None,
+                &expect
+            )),
+            UnmetExpectation::FeatureMissing("team".into())
+        );
+
+        // Granted, but its entitlement has lapsed
+        let expect = Expectations::new().require_feature("export");
+        assert_eq!(
+            unmet(verify_license_expecting(
+                &license,
+                &public_key,
+                None,
+                &expect
+            )),
+            UnmetExpectation::FeatureMissing("export".into())
+        );
+    }
+
+    #[test]
+    fn test_verify_license_expecting_insufficient_time() {
+        let (license, public_key) = expecting_license(Some(now_ms() + 86_400_000));
+        let expect = Expectations::new().min_remaining(Duration::from_secs(7 * 86_400));
+
+        match unmet(verify_license_expecting(
+            &license,
+            &public_key,
+            None,
+            &expect,
+        )) {
+            UnmetExpectation::InsufficientTime {
+                remaining,
+                required,
+            } => {
+                assert!(remaining <= Duration::from_secs(86_400));
+                assert_eq!(required, Duration::from_secs(7 * 86_400));
+            }
+            other => panic!("expected insufficient time, got {:?}", other),
+        }
+
+        // Perpetual licenses never run out
+        let (license, public_key) = expecting_license(None);
+        assert!(verify_license_expecting(&license, &public_key, None, &expect).is_ok());
+    }
+
+    #[test]
+    fn test_max_license_key_len() {
+        let (license, public_key) = sign_test_license(
+            json!({"lid":"lic_1","pid":"prod_1","cid":"cus_1","did":"dev_1","features":[],"iat":0,"exp":null,"mid":null}),
+        );
+        let padded = |len: usize| format!("{}{}", license, " ".repeat(len - license.len()));
+        let too_large = |result: Result<_, TuishError>| matches!(result, Err(TuishError::InvalidLicense(msg)) if msg == "license too large");
+
+        // Padding is stripped, so only the raw length decides
+        let at_limit = padded(MAX_LICENSE_KEY_LEN);
+        assert!(parse_license(&at_limit).is_ok());
+        assert!(verify_license(&at_limit, &public_key, None).is_ok());
+
+        let over_limit = padded(MAX_LICENSE_KEY_LEN + 1);
+        assert!(too_large(parse_license(&over_limit).map(|_| ())));
+        assert!(too_large(
+            verify_license(&over_limit, &public_key, None).map(|_| ())
+        ));
+
+        assert!(parse_license_with_max_len(&license, license.len()).is_ok());
+        assert!(too_large(
+            parse_license_with_max_len(&license, license.len() - 1).map(|_| ())
+        ));
+    }
+
+    #[test]
+    fn test_parse_license_borrowed_matches_parse_license() {
+        let (license, public_key) = sign_test_license(
+            json!({"lid":"lic_1","pid":"prod_1","cid":"cus_1","did":"dev_1","features":["pro"],"iat":0,"exp":null,"mid":null}),
+        );
+
+        // Same work many times over, as a server would; compare the results
+        for key in [license.clone(), format!(" \"{}\"\n", license)]
+            .iter()
+            .cycle()
+            .take(100)
+        {
+            let (header, payload, signature) = parse_license(key).unwrap();
+            let parts = parse_license_borrowed(std::hint::black_box(key)).unwrap();
+            parts.verify_signature(&public_key).unwrap();
+            assert_eq!(parts.header().unwrap(), header);
+            assert_eq!(parts.payload().unwrap(), payload);
+            assert_eq!(
+                parts.signature_bytes().unwrap().as_slice(),
+                signature.as_slice()
+            );
+            assert_eq!(
+                format!("{}.{}", parts.message(), parts.signature_b64),
+                license
+            );
+        }
+
+        // Segments borrow from the input
+        let parts = parse_license_borrowed(&license).unwrap();
+        assert!(license
+            .as_bytes()
+            .as_ptr_range()
+            .contains(&parts.signature_b64.as_ptr()));
+        assert!(matches!(
+            parts.verify_signature(TEST_PUBLIC_KEY_HEX),
+            Err(TuishError::InvalidSignature)
+        ));
+
+        // Whatever parse_license rejects is rejected too
+        let (head, tail) = license.split_at(license.len() / 2);
+        let wrong_alg = format!(
+            "{}.{}",
+            to_base64url(br#"{"alg":"rsa","ver":1}"#),
+            license.split_once('.').unwrap().1
+        );
+        for bad in ["", "a.b", "a.b.c.d", "a..c", "!!.b.c", wrong_alg.as_str()] {
+            assert!(parse_license(bad).is_err(), "{:?}", bad);
+            let borrowed = parse_license_borrowed(bad).and_then(|parts| parts.header());
+            assert!(
+                matches!(borrowed, Err(TuishError::InvalidLicense(_))),
+                "{:?}",
+                bad
+            );
+        }
+
+        // Wrapped keys need normalizing first
+        let wrapped = format!("{}\r\n{}", head, tail);
+        assert!(parse_license_borrowed(&wrapped).is_err());
+        assert!(parse_license_borrowed(&normalize_license_key(&wrapped)).is_ok());
+
+        // Short signature
+        let short = format!("{}.{}", parts.message(), to_base64url(&[0u8; 32]));
+        let parts = parse_license_borrowed(&short).unwrap();
+        assert!(matches!(
+            parts.signature_bytes(),
+            Err(TuishError::InvalidSignature)
+        ));
+    }
+
+    #[test]
+    fn test_verify_license_expecting_passes_through_verify_errors() {
+        let (license, _) = expecting_license(None);
+        let expect = Expectations::new().product_id("prod_b");
+
+        let result = verify_license_expecting(&license, TEST_PUBLIC_KEY_HEX, None, &expect);
+        assert!(matches!(result, Err(TuishError::InvalidSignature)));
+    }
 }
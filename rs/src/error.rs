@@ -1,6 +1,8 @@
 //! Error types for the Tuish SDK
 
+use serde::{Deserialize, Serialize};
 use std::fmt;
+use std::time::Duration;
 use thiserror::Error;
 
 /// All errors that can occur in the Tuish SDK
@@ -14,6 +16,10 @@ pub enum TuishError {
     #[error("license has expired")]
     ExpiredLicense,
 
+    /// License is not valid yet (nbf timestamp is in the future)
+    #[error("license is not yet valid")]
+    NotYetValid,
+
     /// License signature verification failed
     #[error("invalid license signature")]
     InvalidSignature,
@@ -47,13 +53,94 @@ pub enum TuishError {
     #[error("invalid public key format: {0}")]
     InvalidPublicKey(String),
 
+    /// Webhook timestamp is outside the allowed tolerance
+    #[error("webhook timestamp outside tolerance (age {age_secs}s)")]
+    StaleWebhook {
+        /// Seconds between the webhook timestamp and now (negative if in the future)
+        age_secs: i64,
+    },
+
     /// Feature not available (e.g., http feature not enabled)
     #[error("feature not available: {0}")]
     FeatureNotAvailable(String),
+
+    /// Network access is disabled by `TuishConfig::offline_only`
+    #[error("network access disabled (offline-only mode)")]
+    Offline,
+
+    /// The terminal purchase flow cannot prompt because stdin is not a terminal
+    ///
+    /// Set `TUISH_LOGIN_OTP` and `TUISH_PURCHASE_OTP` to run it unattended.
+    #[error("terminal purchase requires a TTY (or TUISH_LOGIN_OTP and TUISH_PURCHASE_OTP)")]
+    NotATerminal,
+
+    /// Purchase needs 3D Secure authentication before it can complete.
+    ///
+    /// Open `action_url` for the customer, then call
+    /// `Tuish::confirm_purchase_after_action` with the pending purchase.
+    #[error("purchase requires authentication: {}", .0.action_url)]
+    PurchaseActionRequired(Box<crate::types::PendingPurchase>),
+
+    /// License is valid but does not meet the caller's `Expectations`
+    #[error("license does not meet expectations: {0}")]
+    ExpectationUnmet(UnmetExpectation),
+}
+
+impl TuishError {
+    /// Stable snake_case identifier for this kind of error
+    ///
+    /// Suitable for machine-readable output, e.g. a CLI's `--json` errors.
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::InvalidLicense(_) => "invalid_license",
+            Self::ExpiredLicense => "expired_license",
+            Self::NotYetValid => "not_yet_valid",
+            Self::InvalidSignature => "invalid_signature",
+            Self::InvalidMachineId => "machine_mismatch",
+            Self::NetworkError(_) => "network_error",
+            Self::StorageError(_) => "storage_error",
+            Self::ApiError { .. } => "api_error",
+            Self::ParseError(_) => "parse_error",
+            Self::InvalidPublicKey(_) => "invalid_public_key",
+            Self::StaleWebhook { .. } => "stale_webhook",
+            Self::FeatureNotAvailable(_) => "feature_not_available",
+            Self::Offline => "offline",
+            Self::NotATerminal => "tty_required",
+            Self::PurchaseActionRequired(_) => "purchase_action_required",
+            Self::ExpectationUnmet(_) => "expectation_unmet",
+        }
+    }
+}
+
+/// The first expectation a valid license failed in `verify_license_expecting`
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum UnmetExpectation {
+    /// License was issued for a different product
+    #[error("expected product {expected}, license is for {actual}")]
+    ProductMismatch {
+        /// Product ID the caller expected
+        expected: String,
+        /// Product ID in the license
+        actual: String,
+    },
+
+    /// Required feature is not granted, or its entitlement has expired
+    #[error("feature '{0}' is not active")]
+    FeatureMissing(String),
+
+    /// License expires sooner than the required minimum
+    #[error("license expires in {}s, at least {}s required", .remaining.as_secs(), .required.as_secs())]
+    InsufficientTime {
+        /// Time left before the license expires
+        remaining: Duration,
+        /// Minimum time the caller required
+        required: Duration,
+    },
 }
 
 /// Reason why a license is invalid
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum LicenseInvalidReason {
     /// License string format is invalid
     InvalidFormat,
@@ -61,6 +148,8 @@ pub enum LicenseInvalidReason {
     InvalidSignature,
     /// License has expired
     Expired,
+    /// License start date (`nbf`) is in the future
+    NotYetValid,
     /// License is bound to a different machine
     MachineMismatch,
     /// License was not found (API validation)
@@ -69,6 +158,8 @@ pub enum LicenseInvalidReason {
     Revoked,
     /// Network error during validation
     NetworkError,
+    /// Operation was cancelled by the caller (e.g. checkout polling)
+    Cancelled,
 }
 
 impl fmt::Display for LicenseInvalidReason {
@@ -77,10 +168,70 @@ impl fmt::Display for LicenseInvalidReason {
             Self::InvalidFormat => write!(f, "invalid_format"),
             Self::InvalidSignature => write!(f, "invalid_signature"),
             Self::Expired => write!(f, "expired"),
+            Self::NotYetValid => write!(f, "not_yet_valid"),
             Self::MachineMismatch => write!(f, "machine_mismatch"),
             Self::NotFound => write!(f, "not_found"),
             Self::Revoked => write!(f, "revoked"),
             Self::NetworkError => write!(f, "network_error"),
+            Self::Cancelled => write!(f, "cancelled"),
+        }
+    }
+}
+
+impl LicenseInvalidReason {
+    /// The reason a license verification error corresponds to
+    ///
+    /// Returns `None` for errors that say nothing about the license itself,
+    /// e.g. storage or API failures.
+    pub fn from_error(err: &TuishError) -> Option<Self> {
+        match err {
+            TuishError::InvalidLicense(_) | TuishError::ParseError(_) => Some(Self::InvalidFormat),
+            TuishError::InvalidSignature => Some(Self::InvalidSignature),
+            TuishError::ExpiredLicense => Some(Self::Expired),
+            TuishError::NotYetValid => Some(Self::NotYetValid),
+            TuishError::InvalidMachineId => Some(Self::MachineMismatch),
+            TuishError::NetworkError(_) => Some(Self::NetworkError),
+            _ => None,
+        }
+    }
+
+    /// A short, user-facing hint on how to fix the license
+    ///
+    /// Suitable as the message shown in place of a gated feature.
+    pub fn remediation(&self) -> &'static str {
+        match self {
+            Self::InvalidFormat => "Check that the license key was copied in full",
+            Self::InvalidSignature => "Restore your license from a backup or contact support",
+            Self::Expired => "Renew your subscription",
+            Self::NotYetValid => "Check your system clock, or wait until the license starts",
+            Self::MachineMismatch => "Deactivate on your old machine then rebind here",
+            Self::NotFound => "Purchase or restore a license",
+            Self::Revoked => "Contact support about this license",
+            Self::NetworkError => "Check your connection and try again",
+            Self::Cancelled => "Start the purchase again when you're ready",
+        }
+    }
+}
+
+impl std::str::FromStr for LicenseInvalidReason {
+    type Err = TuishError;
+
+    /// Parse the snake_case form produced by `Display` and serde
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "invalid_format" => Ok(Self::InvalidFormat),
+            "invalid_signature" => Ok(Self::InvalidSignature),
+            "expired" => Ok(Self::Expired),
+            "not_yet_valid" => Ok(Self::NotYetValid),
+            "machine_mismatch" => Ok(Self::MachineMismatch),
+            "not_found" => Ok(Self::NotFound),
+            "revoked" => Ok(Self::Revoked),
+            "network_error" => Ok(Self::NetworkError),
+            "cancelled" => Ok(Self::Cancelled),
+            other => Err(TuishError::ParseError(format!(
+                "unknown license invalid reason: {}",
+                other
+            ))),
         }
     }
 }
@@ -138,6 +289,62 @@ mod tests {
         assert_eq!(error.to_string(), "API error (status 401): unauthorized");
     }
 
+    #[test]
+    fn test_error_code() {
+        assert_eq!(
+            TuishError::NetworkError("down".into()).code(),
+            "network_error"
+        );
+        assert_eq!(
+            TuishError::InvalidLicense("bad".into()).code(),
+            "invalid_license"
+        );
+        assert_eq!(
+            TuishError::ApiError {
+                status: 401,
+                message: "unauthorized".into()
+            }
+            .code(),
+            "api_error"
+        );
+        assert_eq!(
+            TuishError::ExpectationUnmet(UnmetExpectation::FeatureMissing("pro".into())).code(),
+            "expectation_unmet"
+        );
+    }
+
+    #[test]
+    fn test_license_invalid_reason_remediation() {
+        use LicenseInvalidReason::*;
+
+        let hints = [
+            (
+                InvalidFormat,
+                "Check that the license key was copied in full",
+            ),
+            (
+                InvalidSignature,
+                "Restore your license from a backup or contact support",
+            ),
+            (Expired, "Renew your subscription"),
+            (
+                NotYetValid,
+                "Check your system clock, or wait until the license starts",
+            ),
+            (
+                MachineMismatch,
+                "Deactivate on your old machine then rebind here",
+            ),
+            (NotFound, "Purchase or restore a license"),
+            (Revoked, "Contact support about this license"),
+            (NetworkError, "Check your connection and try again"),
+            (Cancelled, "Start the purchase again when you're ready"),
+        ];
+        for (reason, hint) in hints {
+            assert_eq!(reason.remediation(), hint, "{reason}");
+        }
+    }
+
     #[test]
     fn test_license_invalid_reason_display() {
         assert_eq!(
@@ -150,4 +357,60 @@ mod tests {
             "machine_mismatch"
         );
     }
+
+    const ALL_REASONS: [LicenseInvalidReason; 9] = [
+        LicenseInvalidReason::InvalidFormat,
+        LicenseInvalidReason::InvalidSignature,
+        LicenseInvalidReason::Expired,
+        LicenseInvalidReason::NotYetValid,
+        LicenseInvalidReason::MachineMismatch,
+        LicenseInvalidReason::NotFound,
+        LicenseInvalidReason::Revoked,
+        LicenseInvalidReason::NetworkError,
+        LicenseInvalidReason::Cancelled,
+    ];
+
+    #[test]
+    fn test_invalid_reason_serde_matches_display() {
+        for reason in ALL_REASONS {
+            let json = serde_json::to_value(reason).unwrap();
+            assert_eq!(json, reason.to_string());
+            let parsed: LicenseInvalidReason = serde_json::from_value(json).unwrap();
+            assert_eq!(parsed, reason);
+        }
+    }
+
+    #[test]
+    fn test_invalid_reason_from_str_round_trips() {
+        for reason in ALL_REASONS {
+            assert_eq!(
+                reason.to_string().parse::<LicenseInvalidReason>().unwrap(),
+                reason
+            );
+        }
+        assert!(matches!(
+            "Expired".parse::<LicenseInvalidReason>(),
+            Err(TuishError::ParseError(_))
+        ));
+    }
+
+    #[test]
+    fn test_invalid_reason_from_error() {
+        assert_eq!(
+            LicenseInvalidReason::from_error(&TuishError::InvalidLicense("bad".into())),
+            Some(LicenseInvalidReason::InvalidFormat)
+        );
+        assert_eq!(
+            LicenseInvalidReason::from_error(&TuishError::ExpiredLicense),
+            Some(LicenseInvalidReason::Expired)
+        );
+        assert_eq!(
+            LicenseInvalidReason::from_error(&TuishError::InvalidMachineId),
+            Some(LicenseInvalidReason::MachineMismatch)
+        );
+        assert_eq!(
+            LicenseInvalidReason::from_error(&TuishError::StorageError("disk".into())),
+            None
+        );
+    }
 }
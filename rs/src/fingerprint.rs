@@ -6,10 +6,39 @@
 //!
 //! The fingerprint format matches the TypeScript SDK:
 //! `SHA256(hostname + ":" + username + ":" + platform + ":" + arch)`
+//!
+//! # Salting
+//!
+//! Unsalted, every app using the SDK sees the same fingerprint for a
+//! machine, so fingerprints can be correlated across vendors. Setting
+//! `TuishConfig::fingerprint_salt` hashes `salt + ":" + components`
+//! instead, which is stable for one app but unlinkable between apps.
+//!
+//! The trade-off: a salted fingerprint differs from the unsalted one, so
+//! licenses already bound to a machine (`mid`) stop matching once a salt is
+//! introduced or changed. Pick a salt before issuing machine-bound licenses
+//! and keep it fixed; any other SDK validating the same licenses must use
+//! the same salt.
+//!
+//! # Override
+//!
+//! CI runners and golden VM images need a fixed fingerprint so cached
+//! licenses survive cloning. Set `TUISH_MACHINE_FINGERPRINT` to 64 hex
+//! characters, or `TuishConfig::machine_fingerprint_override` (which takes
+//! precedence), and that value is used as-is, unsalted.
+//!
+//! This turns off real machine binding: a license bound to the pinned
+//! fingerprint validates on every machine that sets it. Only pin
+//! fingerprints on machines you control.
 
 use sha2::{Digest, Sha256};
+use std::ffi::OsString;
 use std::sync::OnceLock;
-use tracing::debug;
+
+use crate::logging::{debug, warn};
+
+/// Environment variable that pins the machine fingerprint
+pub const MACHINE_FINGERPRINT_ENV: &str = "TUISH_MACHINE_FINGERPRINT";
 
 /// Generate a machine fingerprint as a SHA256 hex string.
 ///
@@ -33,11 +62,40 @@ use tracing::debug;
 /// println!("Machine ID: {}", fingerprint);
 /// // Output: "a1b2c3d4e5f6..." (64 hex characters)
 /// ```
+///
+/// Returns `TUISH_MACHINE_FINGERPRINT` instead, if set; see the
+/// [module docs](self#override).
 pub fn get_machine_fingerprint() -> String {
+    if let Some(fingerprint) = env_override() {
+        return fingerprint;
+    }
     let components = collect_fingerprint_components();
     hash_fingerprint(&components)
 }
 
+/// Generate a machine fingerprint namespaced by an app-specific salt.
+///
+/// Computes `SHA256(salt + ":" + hostname:username:platform:arch)`. The
+/// result is stable for a given salt but cannot be linked to the unsalted
+/// fingerprint or to fingerprints using another salt. See the
+/// [module docs](self#salting) for the compatibility trade-off.
+///
+/// # Example
+///
+/// ```rust
+/// use tuish::fingerprint::get_machine_fingerprint_salted;
+///
+/// let fingerprint = get_machine_fingerprint_salted("com.example.my-app");
+/// assert_eq!(fingerprint.len(), 64);
+/// ```
+pub fn get_machine_fingerprint_salted(salt: &str) -> String {
+    if let Some(fingerprint) = env_override() {
+        return fingerprint;
+    }
+    let components = collect_fingerprint_components();
+    hash_fingerprint(&salted_components(salt, &components))
+}
+
 /// Synchronous version of `get_machine_fingerprint`.
 ///
 /// This is identical to `get_machine_fingerprint` since all operations
@@ -59,6 +117,33 @@ pub fn get_machine_fingerprint_cached() -> &'static str {
     FINGERPRINT.get_or_init(get_machine_fingerprint)
 }
 
+/// Whether `value` looks like a fingerprint: 64 hex characters.
+pub fn is_valid_fingerprint(value: &str) -> bool {
+    value.len() == 64 && value.chars().all(|c| c.is_ascii_hexdigit())
+}
+
+/// The fingerprint pinned by `TUISH_MACHINE_FINGERPRINT`, if any.
+fn env_override() -> Option<String> {
+    parse_override(std::env::var_os(MACHINE_FINGERPRINT_ENV))
+}
+
+/// Validate an override, ignoring (with a warning) anything malformed.
+fn parse_override(value: Option<OsString>) -> Option<String> {
+    let value = value.filter(|value| !value.is_empty())?;
+    match value.to_str().map(str::trim) {
+        Some(fingerprint) if is_valid_fingerprint(fingerprint) => {
+            Some(fingerprint.to_ascii_lowercase())
+        }
+        _ => {
+            warn!(
+                var = MACHINE_FINGERPRINT_ENV,
+                "Ignoring fingerprint override: not 64 hex characters"
+            );
+            None
+        }
+    }
+}
+
 /// Collect the components used for fingerprinting.
 ///
 /// Returns a string in the format: `hostname:username:platform:arch`
@@ -74,6 +159,11 @@ fn collect_fingerprint_components() -> String {
     components
 }
 
+/// Prefix the components with a salt: `salt:hostname:username:platform:arch`.
+fn salted_components(salt: &str, components: &str) -> String {
+    format!("{}:{}", salt, components)
+}
+
 /// Hash the fingerprint components using SHA256.
 fn hash_fingerprint(components: &str) -> String {
     let mut hasher = Sha256::new();
@@ -186,6 +276,40 @@ mod tests {
         assert_ne!(hash, different);
     }
 
+    #[test]
+    fn test_parse_override() {
+        let pinned = "AB".repeat(32);
+        assert_eq!(
+            parse_override(Some(pinned.clone().into())),
+            Some("ab".repeat(32))
+        );
+        assert_eq!(
+            parse_override(Some(format!(" {}\n", pinned).into())),
+            Some("ab".repeat(32))
+        );
+
+        assert_eq!(parse_override(None), None);
+        assert_eq!(parse_override(Some("".into())), None);
+        assert_eq!(parse_override(Some("abc".into())), None);
+        assert_eq!(parse_override(Some("zz".repeat(32).into())), None);
+    }
+
+    #[test]
+    fn test_salted_fingerprint() {
+        let salted = get_machine_fingerprint_salted("app-one");
+        assert_eq!(salted, get_machine_fingerprint_salted("app-one"));
+        assert_ne!(salted, get_machine_fingerprint_salted("app-two"));
+        assert_ne!(salted, get_machine_fingerprint());
+        assert_eq!(salted.len(), 64);
+
+        // SHA256(salt + ":" + components)
+        let components = "testhost:testuser:linux:x64";
+        assert_eq!(
+            hash_fingerprint(&salted_components("app-one", components)),
+            hash_fingerprint("app-one:testhost:testuser:linux:x64")
+        );
+    }
+
     #[test]
     fn test_collect_fingerprint_components() {
         let components = collect_fingerprint_components();
@@ -8,12 +8,29 @@
 //! - `http` - Enable HTTP client for online license validation (enabled by default)
 //! - `storage` - Enable filesystem storage for license caching (enabled by default)
 //! - `browser` - Enable browser opening for checkout flows (enabled by default)
+//! - `blocking` - Add `TuishClientBlocking` and `Tuish::*_blocking` methods for
+//!   applications without an async runtime (implies `http`)
+//! - `chrono` - Add `DateTime<Utc>` accessors for timestamp fields
+//! - `tracing` - Emit logs and spans via `tracing` (enabled by default); when
+//!   disabled, logging compiles to nothing and `tracing` is not a dependency
+//! - `metrics` - Record license-check counters and refresh latency in
+//!   `LicenseManager` via the `metrics` crate (`tuish_license_checks_total`,
+//!   `tuish_refresh_duration_seconds`, `tuish_cache_hits_total`)
+//! - `signing` - Add the `signing` module to generate key pairs and sign
+//!   licenses for tests and fixtures
+//!
+//! With all features disabled the crate is a dependency-light verification
+//! core: `verify_license`, `parse_license`, `extract_license_payload`,
+//! `verify_webhook`, and the fingerprint functions, with no async runtime. `Tuish` and `LicenseManager`
+//! require the `storage` feature.
 //!
 //! # Quick Start
 //!
 //! ```rust,no_run
+//! # #[cfg(all(feature = "http", feature = "storage", feature = "browser"))]
 //! use tuish::Tuish;
 //!
+//! # #[cfg(all(feature = "http", feature = "storage", feature = "browser"))]
 //! # async fn example() -> Result<(), tuish::TuishError> {
 //! // Create a Tuish instance
 //! let mut tuish = Tuish::builder()
@@ -70,6 +87,7 @@
 //! let result = client.validate_license(LicenseValidateRequest {
 //!     license_key: "license-key-here".to_string(),
 //!     machine_fingerprint: "machine-hash".to_string(),
+//!     installation_id: None,
 //! }).await?;
 //!
 //! if result.valid {
@@ -79,53 +97,140 @@
 //! # }
 //! ```
 
+// Values that only feed log messages go unused when logging is compiled out
+#![cfg_attr(not(feature = "tracing"), allow(unused_variables))]
+
+// Internal logging shim (no-ops without the `tracing` feature)
+mod logging;
+
+// Internal metrics shim (no-ops without the `metrics` feature)
+#[cfg(feature = "storage")]
+mod metrics;
+
 // Core modules (always available)
+pub mod bundle;
 pub mod crypto;
 pub mod error;
 pub mod fingerprint;
+pub mod secret;
 pub mod types;
 
 // Feature-gated modules
 #[cfg(feature = "storage")]
 pub mod storage;
 
+#[cfg(feature = "storage")]
 pub mod license;
 
+#[cfg(feature = "storage")]
+pub mod trial;
+
+#[cfg(feature = "storage")]
+pub mod audit;
+
 #[cfg(feature = "http")]
 pub mod client;
 
+#[cfg(feature = "blocking")]
+pub mod blocking;
+
 #[cfg(feature = "browser")]
 pub mod browser;
 
+// Unit tests sign their fixtures with this module
+#[cfg(any(test, feature = "signing"))]
+pub mod signing;
+
+#[cfg(test)]
+mod test_support;
+
 // Re-exports for convenient access
+pub use bundle::{LicenseBundle, BUNDLE_EXTENSION};
 pub use crypto::{
     extract_license_payload, get_license_time_remaining, is_license_expired,
-    is_valid_license_format, parse_license, verify_license,
+    is_valid_license_format, normalize_license_key, parse_license, parse_license_borrowed,
+    parse_license_with_max_len, redact_license, redact_license_keys, verify_components,
+    verify_license, verify_license_expecting, verify_license_with_skew, verify_revocation_list,
+    verify_webhook, LicenseParts, DEFAULT_CLOCK_SKEW, MAX_LICENSE_KEY_LEN,
 };
-pub use error::{LicenseInvalidReason, TuishError};
-pub use fingerprint::{get_machine_fingerprint, get_machine_fingerprint_cached, get_machine_fingerprint_sync};
+pub use error::{LicenseInvalidReason, TuishError, UnmetExpectation};
+pub use fingerprint::{
+    get_machine_fingerprint, get_machine_fingerprint_cached, get_machine_fingerprint_salted,
+    get_machine_fingerprint_sync, is_valid_fingerprint, MACHINE_FINGERPRINT_ENV,
+};
+#[cfg(feature = "storage")]
 pub use license::LicenseManager;
+pub use secret::SecretString;
 pub use types::{
-    CachedLicenseData, CheckoutInitRequest, CheckoutInitResponse, CheckoutStatus,
-    CheckoutStatusResponse, LicenseCheckResult, LicenseDetails, LicenseHeader, LicenseInfo,
-    LicensePayload, LicenseStatus, LicenseValidateRequest, LicenseValidateResponse,
-    LoginInitRequest, LoginInitResponse, LoginVerifyRequest, LoginVerifyResponse,
-    PurchaseConfirmRequest, PurchaseConfirmResponse, PurchaseInitRequest, PurchaseInitResponse,
-    SavedCard, SignedLicense, TuishConfig,
+    AnalyticsSummary, CachedLicenseData, Capabilities, CheckoutInitRequest, CheckoutInitResponse,
+    CheckoutStatus, CheckoutStatusResponse, Developer, Entitlement, Expectations, LicenseChange,
+    LicenseCheckResult, LicenseDeactivateRequest, LicenseDeactivateResponse, LicenseDetails,
+    LicenseHeader, LicenseInfo, LicensePayload, LicenseRebindRequest, LicenseRebindResponse,
+    LicenseStatus, LicenseStatusSnapshot, LicenseValidateRequest, LicenseValidateResponse,
+    LicenseWarning, LoginInitRequest, LoginInitResponse, LoginVerifyRequest, LoginVerifyResponse,
+    MachineMismatch, PendingPurchase, ProductAnalytics, PurchaseConfirmRequest,
+    PurchaseConfirmResponse, PurchaseInitRequest, PurchaseInitResponse, ResultSource,
+    RevocationList, RevocationListResponse, SavedCard, SignedLicense, TrialStartRequest,
+    TrialStartResponse, TuishConfig, DEFAULT_EXPIRY_WARNING, TRIAL_FEATURE,
 };
 
 #[cfg(feature = "storage")]
-pub use storage::LicenseStorage;
+pub use storage::{LicenseStorage, STORAGE_DIR_ENV};
+
+#[cfg(feature = "storage")]
+pub use trial::TrialTracker;
+
+#[cfg(feature = "storage")]
+pub use audit::{AuditAction, AuditEvent, AuditSink, JsonlFileSink};
+
+#[cfg(feature = "http")]
+pub use client::{
+    new_idempotency_key, new_request_id, DeprecationNotice, OtpResponse, TuishClient,
+    API_DEPRECATED_HEADER, API_VERSION_HEADER, DEFAULT_API_URL, DEFAULT_API_VERSION,
+    IDEMPOTENCY_KEY_HEADER, REQUEST_ID_HEADER,
+};
+
+#[cfg(feature = "blocking")]
+pub use blocking::TuishClientBlocking;
 
 #[cfg(feature = "http")]
-pub use client::{OtpResponse, TuishClient, DEFAULT_API_URL};
+pub use tokio_util::sync::CancellationToken;
+
+/// Embed a public key file in the binary at compile time
+///
+/// The path is relative to the calling crate's `Cargo.toml`. The file may
+/// hold an SPKI base64, PEM, or 64-character hex key; anything else fails
+/// the build, so a malformed key can't be shipped. Expands to a
+/// `&'static str` for `TuishBuilder::public_key` or `TuishConfig::new`.
+///
+/// ```rust,ignore
+/// let tuish = tuish::Tuish::builder()
+///     .product_id("prod_xxx")
+///     .public_key(tuish::include_public_key!("keys/prod.pub"))
+///     .build()?;
+/// ```
+#[macro_export]
+macro_rules! include_public_key {
+    ($path:expr $(,)?) => {{
+        const PUBLIC_KEY: &str = $crate::crypto::checked_public_key(include_str!(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/",
+            $path
+        )));
+        PUBLIC_KEY
+    }};
+}
 
 // ============================================================================
 // Main Tuish SDK Entry Point
 // ============================================================================
 
 use std::time::Duration;
-use tracing::{debug, info, warn};
+
+#[cfg(feature = "storage")]
+use crate::logging::info;
+#[cfg(all(feature = "http", feature = "storage"))]
+use crate::logging::{debug, info_span, warn, Instrument};
 
 /// Checkout session information
 ///
@@ -137,6 +242,345 @@ pub struct CheckoutSession {
     pub session_id: String,
     /// URL to open in the user's browser for checkout
     pub checkout_url: String,
+    /// Correlation ID sent as `X-Request-Id` on every request in this flow
+    pub flow_id: String,
+}
+
+/// Progress of checkout polling, reported once per poll
+///
+/// Passed to the callback of `Tuish::wait_for_checkout_with_progress`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CheckoutPollProgress {
+    /// Poll attempt number, starting at 1
+    pub attempt: u32,
+    /// Time elapsed since polling started
+    pub elapsed: std::time::Duration,
+    /// Status returned by this poll
+    pub status: CheckoutStatus,
+}
+
+/// Optional settings for a browser checkout session
+///
+/// `success_url` and `cancel_url` replace the hosted pages the browser is
+/// sent to when checkout finishes, e.g. with a page served by a local
+/// listener so the app learns of completion sooner than by polling.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CheckoutOptions {
+    /// Customer email to pre-fill the checkout form
+    pub email: Option<String>,
+    /// Where to redirect after a successful payment
+    pub success_url: Option<String>,
+    /// Where to redirect if the customer cancels
+    pub cancel_url: Option<String>,
+}
+
+impl CheckoutOptions {
+    /// Create options with no email and the default redirect pages
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Pre-fill the checkout form with `email`
+    pub fn with_email(mut self, email: impl Into<String>) -> Self {
+        self.email = Some(email.into());
+        self
+    }
+
+    /// Redirect to `url` after a successful payment
+    pub fn with_success_url(mut self, url: impl Into<String>) -> Self {
+        self.success_url = Some(url.into());
+        self
+    }
+
+    /// Redirect to `url` if the customer cancels
+    pub fn with_cancel_url(mut self, url: impl Into<String>) -> Self {
+        self.cancel_url = Some(url.into());
+        self
+    }
+
+    #[cfg(all(feature = "http", feature = "storage"))]
+    fn from_email(email: Option<&str>) -> Self {
+        Self {
+            email: email.map(String::from),
+            ..Self::default()
+        }
+    }
+
+    #[cfg(all(feature = "http", feature = "storage"))]
+    fn into_request(self, product_id: &str) -> CheckoutInitRequest {
+        CheckoutInitRequest {
+            product_id: product_id.to_string(),
+            email: self.email,
+            success_url: self.success_url,
+            cancel_url: self.cancel_url,
+        }
+    }
+}
+
+/// Login OTP used by `Tuish::purchase_in_terminal` when it cannot prompt
+#[cfg(feature = "http")]
+pub const LOGIN_OTP_ENV: &str = "TUISH_LOGIN_OTP";
+
+/// Purchase OTP used by `Tuish::purchase_in_terminal` when it cannot prompt
+#[cfg(feature = "http")]
+pub const PURCHASE_OTP_ENV: &str = "TUISH_PURCHASE_OTP";
+
+/// How long `Tuish::wait_for_redirect` waits for the browser before polling
+#[cfg(all(feature = "http", feature = "browser", feature = "storage"))]
+const REDIRECT_TIMEOUT: Duration = Duration::from_secs(300);
+
+/// Polling schedule for checkout status
+///
+/// The default polls every 2 seconds for up to 10 minutes. A
+/// `backoff_factor` above 1.0 grows the interval after each poll up to
+/// `max_interval`, and `jitter` randomizes each interval by up to that
+/// fraction so clients started together don't poll in lockstep.
+///
+/// Each async status request is also cut off after `request_timeout`, so
+/// one stalled poll can't use up the whole `timeout`; it counts as still
+/// pending and the next poll follows on schedule.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CheckoutPollConfig {
+    /// Interval before the second poll (and the lower bound before jitter)
+    pub poll_interval: Duration,
+    /// Upper bound on the interval; values below `poll_interval` are ignored
+    pub max_interval: Duration,
+    /// Multiplier applied to the interval after each poll (1.0 = fixed)
+    pub backoff_factor: f64,
+    /// Random spread as a fraction of the interval, from 0.0 to 1.0
+    pub jitter: f64,
+    /// Maximum time to wait before timing out
+    pub timeout: Duration,
+    /// Maximum time to wait for a single status request
+    pub request_timeout: Duration,
+}
+
+impl Default for CheckoutPollConfig {
+    fn default() -> Self {
+        Self {
+            poll_interval: Duration::from_secs(2),
+            max_interval: Duration::from_secs(2),
+            backoff_factor: 1.0,
+            jitter: 0.0,
+            timeout: Duration::from_secs(600),
+            request_timeout: Duration::from_secs(10),
+        }
+    }
+}
+
+impl CheckoutPollConfig {
+    /// Create the default fixed-interval schedule
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the base polling interval
+    pub fn with_poll_interval(mut self, interval: Duration) -> Self {
+        self.poll_interval = interval;
+        self
+    }
+
+    /// Grow the interval by `factor` after each poll, up to `max_interval`
+    pub fn with_backoff(mut self, factor: f64, max_interval: Duration) -> Self {
+        self.backoff_factor = factor;
+        self.max_interval = max_interval;
+        self
+    }
+
+    /// Randomize each interval by up to this fraction (clamped to 0.0..=1.0)
+    pub fn with_jitter(mut self, jitter: f64) -> Self {
+        self.jitter = jitter;
+        self
+    }
+
+    /// Set the overall timeout
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Set the timeout for each status request
+    pub fn with_request_timeout(mut self, timeout: Duration) -> Self {
+        self.request_timeout = timeout;
+        self
+    }
+
+    /// Delay to wait after poll number `attempt` (1-based).
+    ///
+    /// Always within `poll_interval * (1 - jitter)` and
+    /// `max(poll_interval, max_interval)`.
+    pub fn interval_for_attempt(&self, attempt: u32) -> Duration {
+        let jitter = if self.jitter.is_finite() {
+            self.jitter.clamp(0.0, 1.0)
+        } else {
+            0.0
+        };
+        let base = self.poll_interval.as_secs_f64();
+        let max = self.max_interval.as_secs_f64().max(base);
+        let min = base * (1.0 - jitter);
+
+        let factor = if self.backoff_factor.is_finite() {
+            self.backoff_factor.max(0.0)
+        } else {
+            1.0
+        };
+        let exponent = attempt.saturating_sub(1).min(i32::MAX as u32) as i32;
+        let mut interval = (base * factor.powi(exponent)).min(max);
+
+        if jitter > 0.0 {
+            interval *= 1.0 + jitter * (2.0 * random_unit() - 1.0);
+        }
+
+        Duration::from_secs_f64(interval.clamp(min, max))
+    }
+}
+
+/// Uniform random value in `[0, 1)` without pulling in an RNG crate
+fn random_unit() -> f64 {
+    use std::collections::hash_map::RandomState;
+    use std::hash::BuildHasher;
+
+    let bits = RandomState::new().hash_one(std::time::SystemTime::now());
+    (bits >> 11) as f64 / (1u64 << 53) as f64
+}
+
+/// Decide where the terminal purchase flow gets its OTPs
+///
+/// `None` means prompt through the callbacks; otherwise the (login,
+/// purchase) codes taken from the environment.
+#[cfg(all(feature = "http", feature = "storage"))]
+fn scripted_otps(
+    interactive: bool,
+    login_otp: Option<String>,
+    purchase_otp: Option<String>,
+) -> Result<Option<(String, String)>, TuishError> {
+    if interactive {
+        return Ok(None);
+    }
+    match (login_otp, purchase_otp) {
+        (Some(login), Some(purchase)) if !login.is_empty() && !purchase.is_empty() => {
+            Ok(Some((login, purchase)))
+        }
+        _ => Err(TuishError::NotATerminal),
+    }
+}
+
+/// Result of a terminal purchase preview
+///
+/// Returned by `Tuish::preview_purchase_in_terminal`, which walks the
+/// terminal purchase flow but stops before the card is charged.
+#[derive(Debug, Clone)]
+pub struct PurchasePreview {
+    /// Purchase details (saved cards, amount, currency) from the API
+    pub purchase: PurchaseInitResponse,
+    /// ID of the card the user selected
+    pub card_id: String,
+}
+
+/// Boxed async prompt used by `TerminalPrompts`
+#[cfg(all(feature = "http", feature = "browser", feature = "storage"))]
+type Prompt<'a, A, T> =
+    Box<dyn FnOnce(A) -> std::pin::Pin<Box<dyn std::future::Future<Output = T> + 'a>> + 'a>;
+
+/// Boxed async card picker used by `TerminalPrompts`
+#[cfg(all(feature = "http", feature = "browser", feature = "storage"))]
+type CardPrompt<'a> = Box<
+    dyn FnOnce(
+            Vec<SavedCard>,
+            i64,
+            String,
+        ) -> std::pin::Pin<Box<dyn std::future::Future<Output = Option<String>> + 'a>>
+        + 'a,
+>;
+
+/// The email and callbacks for a terminal purchase, as used by
+/// `Tuish::purchase_in_terminal`
+#[cfg(all(feature = "http", feature = "browser", feature = "storage"))]
+pub struct TerminalPrompts<'a> {
+    email: String,
+    get_login_otp: Prompt<'a, String, String>,
+    select_card: CardPrompt<'a>,
+    get_purchase_otp: Prompt<'a, String, String>,
+}
+
+#[cfg(all(feature = "http", feature = "browser", feature = "storage"))]
+impl<'a> TerminalPrompts<'a> {
+    /// Collect the arguments of `Tuish::purchase_in_terminal`
+    pub fn new<F1, F2, F3, Fut1, Fut2, Fut3>(
+        email: impl Into<String>,
+        get_login_otp: F1,
+        select_card: F2,
+        get_purchase_otp: F3,
+    ) -> Self
+    where
+        F1: FnOnce(String) -> Fut1 + 'a,
+        Fut1: std::future::Future<Output = String> + 'a,
+        F2: FnOnce(Vec<SavedCard>, i64, String) -> Fut2 + 'a,
+        Fut2: std::future::Future<Output = Option<String>> + 'a,
+        F3: FnOnce(String) -> Fut3 + 'a,
+        Fut3: std::future::Future<Output = String> + 'a,
+    {
+        Self {
+            email: email.into(),
+            get_login_otp: Box::new(move |phone| Box::pin(get_login_otp(phone))),
+            select_card: Box::new(move |cards, amount, currency| {
+                Box::pin(select_card(cards, amount, currency))
+            }),
+            get_purchase_otp: Box::new(move |phone| Box::pin(get_purchase_otp(phone))),
+        }
+    }
+}
+
+#[cfg(all(feature = "http", feature = "browser", feature = "storage"))]
+impl std::fmt::Debug for TerminalPrompts<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TerminalPrompts")
+            .field("email", &self.email)
+            .finish_non_exhaustive()
+    }
+}
+
+/// How `Tuish::purchase` sells a license
+#[cfg(all(feature = "http", feature = "browser", feature = "storage"))]
+#[derive(Debug)]
+pub enum PurchaseStrategy<'a> {
+    /// Browser checkout (`open_checkout` + `wait_for_checkout_with_config`)
+    Browser {
+        /// Email to pre-fill the checkout form
+        email: Option<String>,
+        /// Polling schedule and overall timeout for the checkout
+        poll: CheckoutPollConfig,
+    },
+    /// Terminal purchase with saved cards (`purchase_in_terminal`)
+    Terminal(TerminalPrompts<'a>),
+    /// Browser checkout, falling back to the terminal flow if the browser
+    /// cannot be opened or checkout times out (e.g. the tab was closed)
+    BrowserThenTerminal {
+        /// Polling schedule and overall timeout for the browser attempt
+        poll: CheckoutPollConfig,
+        /// Email (also pre-filled in checkout) and callbacks for the fallback
+        prompts: TerminalPrompts<'a>,
+    },
+}
+
+/// Which flow completed a `Tuish::purchase`
+#[cfg(all(feature = "http", feature = "browser", feature = "storage"))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PurchaseMethod {
+    /// Browser checkout
+    Browser,
+    /// Terminal purchase with a saved card
+    Terminal,
+}
+
+/// Result of `Tuish::purchase`
+#[cfg(all(feature = "http", feature = "browser", feature = "storage"))]
+#[derive(Debug, Clone)]
+pub struct PurchaseOutcome {
+    /// The flow that produced `result`
+    pub method: PurchaseMethod,
+    /// The license check result from that flow
+    pub result: LicenseCheckResult,
 }
 
 /// Main SDK entry point for Tuish license management
@@ -165,14 +609,22 @@ pub struct CheckoutSession {
 /// # Ok(())
 /// # }
 /// ```
+#[cfg(feature = "storage")]
 #[derive(Debug)]
 pub struct Tuish {
     config: TuishConfig,
     license_manager: LicenseManager,
     #[cfg(feature = "http")]
     client: TuishClient,
+    /// Flow IDs of checkout sessions created by this instance, by session ID
+    #[cfg(feature = "http")]
+    checkout_flows: std::sync::Mutex<std::collections::HashMap<String, String>>,
+    /// Looks up `LOGIN_OTP_ENV`/`PURCHASE_OTP_ENV` for non-interactive purchases
+    #[cfg(feature = "http")]
+    otp_env: fn(&str) -> Option<String>,
 }
 
+#[cfg(feature = "storage")]
 impl Tuish {
     /// Create a new Tuish instance with the given configuration
     ///
@@ -198,6 +650,8 @@ impl Tuish {
                 Duration::from_secs(30),
                 config.debug,
             )?
+            .with_api_prefix(config.api_prefix.as_deref().unwrap_or(""))
+            .with_offline(config.offline_only)
         };
 
         #[cfg(feature = "http")]
@@ -213,6 +667,10 @@ impl Tuish {
             license_manager,
             #[cfg(feature = "http")]
             client,
+            #[cfg(feature = "http")]
+            checkout_flows: Default::default(),
+            #[cfg(feature = "http")]
+            otp_env: |name| std::env::var(name).ok(),
         })
     }
 
@@ -236,6 +694,17 @@ impl Tuish {
         TuishBuilder::new()
     }
 
+    /// Report which optional features this build was compiled with
+    ///
+    /// ```rust
+    /// if tuish::Tuish::capabilities().http {
+    ///     println!("[b] Buy a license");
+    /// }
+    /// ```
+    pub const fn capabilities() -> Capabilities {
+        Capabilities::current()
+    }
+
     // =========================================================================
     // License Checking
     // =========================================================================
@@ -280,119 +749,440 @@ impl Tuish {
         self.license_manager.check_license_sync()
     }
 
-    /// Force online license validation
+    /// Snapshot of the license state for a status panel
     ///
-    /// This bypasses the cache and validates the license directly with
-    /// the Tuish API. Requires the `http` feature.
+    /// Combines `check_license`, the cache refresh state and the machine
+    /// fingerprint in one call, without network requests.
     ///
-    /// # Arguments
+    /// # Example
     ///
-    /// * `license_key` - Optional license key to validate. If not provided,
-    ///   the cached license key will be used.
+    /// ```rust,no_run
+    /// use tuish::Tuish;
     ///
-    /// # Errors
+    /// # fn example() -> Result<(), tuish::TuishError> {
+    /// let mut tuish = Tuish::builder()
+    ///     .product_id("prod_xxx")
+    ///     .public_key("key...")
+    ///     .build()?;
     ///
-    /// Returns an error if no license key is available or if the network
-    /// request fails.
-    #[cfg(feature = "http")]
-    pub async fn validate_online(
-        &self,
-        license_key: Option<&str>,
-    ) -> Result<LicenseCheckResult, TuishError> {
-        let key = match license_key {
-            Some(k) => k.to_string(),
-            None => self
-                .license_manager
-                .get_cached_license_key()
-                .ok_or_else(|| TuishError::InvalidLicense("no license key available".to_string()))?,
-        };
-
-        self.license_manager.validate_online(&key).await
-    }
+    /// let status = tuish.status();
+    /// if let Some(left) = status.time_until_expiry {
+    ///     println!("Expires in {} days", left.as_secs() / 86400);
+    /// }
+    /// if status.needs_refresh {
+    ///     println!("Refresh pending");
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn status(&mut self) -> LicenseStatusSnapshot {
+        let result = self.check_license();
+        let time_until_expiry = result
+            .license
+            .as_ref()
+            .and_then(|license| license.expires_at)
+            .map(|expires_at| {
+                let now = std::time::SystemTime::now()
+                    .duration_since(std::time::UNIX_EPOCH)
+                    .map(|d| d.as_millis() as i64)
+                    .unwrap_or(0);
+                Duration::from_millis(expires_at.saturating_sub(now).max(0) as u64)
+            });
 
-    /// Save a license key manually
-    ///
-    /// Use this when you receive a license key through a custom flow
-    /// (e.g., from a license file or email).
-    ///
-    /// # Arguments
-    ///
-    /// * `license_key` - The license key string to save
-    ///
-    /// # Returns
-    ///
-    /// The result of verifying the saved license.
-    pub fn save_license(&mut self, license_key: &str) -> Result<LicenseCheckResult, TuishError> {
-        self.license_manager.save_license_sync(license_key)
+        LicenseStatusSnapshot {
+            source: result.source,
+            needs_refresh: self.license_manager.needs_refresh_sync(),
+            time_until_expiry,
+            fingerprint: self.machine_fingerprint(),
+            result,
+        }
     }
 
-    /// Clear the stored license
+    /// Time left on the license if it expires within `within`
     ///
-    /// Removes the cached license from disk. After calling this,
-    /// `check_license()` will return invalid until a new license is saved.
-    pub fn clear_license(&mut self) -> Result<(), TuishError> {
-        self.license_manager.clear_license_sync()
+    /// `None` for perpetual licenses, licenses expiring later, or when no
+    /// valid license is cached. See `LicenseManager::renewal_due`.
+    pub fn renewal_due(&self, within: Duration) -> Option<Duration> {
+        self.license_manager.renewal_due(within)
     }
 
-    /// Get the cached license key
+    /// Whether any license is cached for this product
     ///
-    /// Returns the raw license key string if one is cached.
-    pub fn get_cached_license_key(&self) -> Option<String> {
-        self.license_manager.get_cached_license_key()
+    /// A hint, not a validity check: it skips signature verification so a
+    /// splash screen can pick a likely first screen instantly. Call
+    /// `check_license` before granting access. See
+    /// `LicenseManager::has_cached_license`.
+    pub fn has_license_cached(&self) -> bool {
+        self.license_manager.has_cached_license()
     }
 
-    // =========================================================================
-    // Browser Purchase Flow
-    // =========================================================================
-
-    /// Start a browser checkout session
-    ///
-    /// Creates a checkout session on the server and returns the session
-    /// information. The caller is responsible for opening the checkout URL.
-    ///
-    /// # Arguments
+    /// Stable ID of this installation, for counting installs
     ///
-    /// * `email` - Optional customer email to pre-fill the checkout form
-    ///
-    /// # Returns
-    ///
-    /// A `CheckoutSession` containing the session ID and checkout URL.
-    #[cfg(feature = "http")]
-    pub async fn purchase_in_browser(
-        &self,
-        email: Option<&str>,
-    ) -> Result<CheckoutSession, TuishError> {
-        debug!(email = ?email, "Creating checkout session");
-
-        let request = CheckoutInitRequest {
-            product_id: self.config.product_id.clone(),
-            email: email.map(String::from),
-            success_url: None,
-            cancel_url: None,
-        };
-
-        let response = self.client.init_checkout(request).await?;
-
-        info!(session_id = %response.session_id, "Checkout session created");
-
-        Ok(CheckoutSession {
-            session_id: response.session_id,
-            checkout_url: response.checkout_url,
-        })
+    /// Survives machine fingerprint changes; see
+    /// `LicenseManager::installation_id`.
+    pub fn installation_id(&self) -> String {
+        self.license_manager.installation_id()
     }
 
-    /// Open checkout in the default browser
-    ///
-    /// Creates a checkout session and automatically opens it in the user's
-    /// default web browser. Requires both `http` and `browser` features.
-    ///
-    /// # Arguments
+    /// Check the license, refreshing a stale cache online
     ///
-    /// * `email` - Optional customer email to pre-fill the checkout form
+    /// Like `check_license`, but when the cached license is due for refresh
+    /// it is re-validated with the Tuish API. If the API cannot be reached,
+    /// the offline result is kept and marked `stale: true` instead of
+    /// failing, so users can keep working while offline.
     ///
-    /// # Returns
+    /// # Errors
     ///
-    /// A `CheckoutSession` containing the session ID for polling.
+    /// Returns an error if the refresh fails for a reason other than the
+    /// network being unavailable (e.g. an API error).
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use tuish::Tuish;
+    ///
+    /// # async fn example() -> Result<(), tuish::TuishError> {
+    /// let mut tuish = Tuish::builder()
+    ///     .product_id("prod_xxx")
+    ///     .public_key("key...")
+    ///     .build()?;
+    ///
+    /// let result = tuish.check_license_with_network().await?;
+    /// if result.stale {
+    ///     println!("Offline: using cached license");
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "http")]
+    pub async fn check_license_with_network(&mut self) -> Result<LicenseCheckResult, TuishError> {
+        self.license_manager.check_license_with_network().await
+    }
+
+    /// Validate the cached license online now, whether or not it is stale
+    ///
+    /// A valid response refreshes the cache. When the API reports the
+    /// license revoked or expired, the cache is deleted so later offline
+    /// checks fail too. Network errors are returned rather than falling
+    /// back to the cache; use `check_license_with_network` for that.
+    ///
+    /// # Errors
+    ///
+    /// Returns `TuishError::InvalidLicense` if no license is cached, or the
+    /// network/API error if validation fails.
+    #[cfg(feature = "http")]
+    pub async fn refresh_now(&mut self) -> Result<LicenseCheckResult, TuishError> {
+        let license_key = self
+            .license_manager
+            .get_cached_license_key()
+            .ok_or_else(|| {
+                TuishError::InvalidLicense("no cached license to refresh".to_string())
+            })?;
+
+        let result = self.license_manager.validate_online(&license_key).await?;
+        if matches!(
+            result.reason,
+            Some(LicenseInvalidReason::Revoked | LicenseInvalidReason::Expired)
+        ) {
+            info!(reason = ?result.reason, "Cached license no longer valid, clearing cache");
+            self.license_manager.clear_license()?;
+        }
+        Ok(result)
+    }
+
+    /// Check the license the way most apps want on startup
+    ///
+    /// A fresh cached license is answered offline. A stale one is
+    /// validated online with the cached key, falling back to the offline
+    /// result (marked `stale: true`) if the API cannot be reached. If the
+    /// API reports the license revoked or expired, the cache is cleared as
+    /// in `refresh_now`. Without a cached key there is nothing to validate,
+    /// and the `NotFound` result is returned so the app can offer a
+    /// purchase or restore.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if online validation fails for a reason other than
+    /// the network being unavailable (e.g. an API error).
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use tuish::Tuish;
+    ///
+    /// # async fn example() -> Result<(), tuish::TuishError> {
+    /// let mut tuish = Tuish::builder()
+    ///     .product_id("prod_xxx")
+    ///     .public_key("key...")
+    ///     .build()?;
+    ///
+    /// if !tuish.ensure_licensed().await?.valid {
+    ///     tuish.purchase_in_browser(None).await?;
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "http")]
+    pub async fn ensure_licensed(&mut self) -> Result<LicenseCheckResult, TuishError> {
+        let result = self.license_manager.check_license_with_network().await?;
+        if result.source == ResultSource::Online
+            && matches!(
+                result.reason,
+                Some(LicenseInvalidReason::Revoked | LicenseInvalidReason::Expired)
+            )
+        {
+            info!(reason = ?result.reason, "Cached license no longer valid, clearing cache");
+            self.license_manager.clear_license()?;
+        }
+        Ok(result)
+    }
+
+    /// Register a callback for license state transitions
+    ///
+    /// Runs whenever a check or refresh changes the license's validity or
+    /// reason, e.g. to lock features as soon as a refresh finds the license
+    /// revoked. See `LicenseManager::on_change`.
+    pub fn on_change(&mut self, callback: Box<dyn Fn(&LicenseCheckResult) + Send + Sync>) {
+        self.license_manager.on_change(callback);
+    }
+
+    /// Report every license check, refresh and save to `sink`
+    ///
+    /// Replaces the sink from `TuishBuilder::audit_log`, if any. See the
+    /// [`audit`](crate::audit) module.
+    pub fn set_audit_sink(&mut self, sink: Box<dyn AuditSink>) {
+        self.license_manager.set_audit_sink(sink);
+    }
+
+    /// Check the cached licenses of several products concurrently
+    ///
+    /// For apps that bundle more than one licensed product signed with the
+    /// same public key. The machine fingerprint is computed once and the
+    /// cache reads run in parallel. Results are keyed by product ID.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use tuish::Tuish;
+    ///
+    /// # async fn example() -> Result<(), tuish::TuishError> {
+    /// let mut tuish = Tuish::builder()
+    ///     .product_id("prod_core")
+    ///     .public_key("key...")
+    ///     .build()?;
+    ///
+    /// let results = tuish.check_all(&["prod_core", "prod_addon"]).await;
+    /// if results["prod_addon"].valid {
+    ///     println!("Add-on unlocked");
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn check_all(
+        &mut self,
+        product_ids: &[&str],
+    ) -> std::collections::HashMap<String, LicenseCheckResult> {
+        self.license_manager.check_all(product_ids).await
+    }
+
+    /// Force online license validation
+    ///
+    /// This bypasses the cache and validates the license directly with
+    /// the Tuish API. Requires the `http` feature.
+    ///
+    /// # Arguments
+    ///
+    /// * `license_key` - Optional license key to validate. If not provided,
+    ///   the cached license key will be used.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if no license key is available or if the network
+    /// request fails.
+    #[cfg(feature = "http")]
+    pub async fn validate_online(
+        &self,
+        license_key: Option<&str>,
+    ) -> Result<LicenseCheckResult, TuishError> {
+        let key = match license_key {
+            Some(k) => k.to_string(),
+            None => self
+                .license_manager
+                .get_cached_license_key()
+                .ok_or_else(|| TuishError::InvalidLicense("no license key available".to_string()))?,
+        };
+
+        self.license_manager.validate_online(&key).await
+    }
+
+    /// Save a license key manually
+    ///
+    /// Use this when you receive a license key through a custom flow
+    /// (e.g., from a license file or email).
+    ///
+    /// # Arguments
+    ///
+    /// * `license_key` - The license key string to save
+    ///
+    /// # Returns
+    ///
+    /// The result of verifying the saved license.
+    pub fn save_license(&mut self, license_key: &str) -> Result<LicenseCheckResult, TuishError> {
+        self.license_manager.save_license_sync(license_key)
+    }
+
+    /// Load a license from a file
+    ///
+    /// Reads a bare license key or an armored bundle (e.g. a `.tuishlic`
+    /// file), verifies it offline against the embedded public key, and
+    /// caches it. Works without the `http` feature, for air-gapped installs.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - Path to the license file
+    ///
+    /// # Returns
+    ///
+    /// The result of verifying the loaded license.
+    pub fn load_license_file(
+        &mut self,
+        path: &std::path::Path,
+    ) -> Result<LicenseCheckResult, TuishError> {
+        self.license_manager.load_license_file(path)
+    }
+
+    /// Clear the stored license
+    ///
+    /// Removes the cached license from disk. After calling this,
+    /// `check_license()` will return invalid until a new license is saved.
+    pub fn clear_license(&mut self) -> Result<(), TuishError> {
+        self.license_manager.clear_license_sync()
+    }
+
+    /// Decode a license's claims WITHOUT verifying it
+    ///
+    /// For debugging only: the signature is not checked and no public key is
+    /// needed, so the result says nothing about whether the license is
+    /// genuine. Use `check_license` or `verify_license` to verify.
+    ///
+    /// # Returns
+    ///
+    /// The unverified license details, or `None` if the key is malformed.
+    pub fn inspect(license_key: &str) -> Option<LicenseDetails> {
+        license::extract_unverified_details(license_key)
+    }
+
+    /// Get the cached license key
+    ///
+    /// Returns the raw license key string if one is cached.
+    pub fn get_cached_license_key(&self) -> Option<String> {
+        self.license_manager.get_cached_license_key()
+    }
+
+    /// Export the cached license key for backup
+    ///
+    /// Returns the raw key so users can keep a copy across reinstalls and
+    /// restore it with `import_license`. The key is a credential: anyone
+    /// holding it can use the license (within its machine binding), so
+    /// treat backups like a password and never log them.
+    pub fn export_license(&self) -> Option<String> {
+        self.get_cached_license_key()
+    }
+
+    /// Restore a license exported with `export_license`
+    ///
+    /// Same as `save_license`: the key is verified and cached.
+    pub fn import_license(&mut self, license_key: &str) -> Result<LicenseCheckResult, TuishError> {
+        self.save_license(license_key)
+    }
+
+    // =========================================================================
+    // Browser Purchase Flow
+    // =========================================================================
+
+    /// Start a browser checkout session
+    ///
+    /// Creates a checkout session on the server and returns the session
+    /// information. The caller is responsible for opening the checkout URL.
+    ///
+    /// # Arguments
+    ///
+    /// * `email` - Optional customer email to pre-fill the checkout form
+    ///
+    /// # Returns
+    ///
+    /// A `CheckoutSession` containing the session ID and checkout URL.
+    #[cfg(feature = "http")]
+    pub async fn purchase_in_browser(
+        &self,
+        email: Option<&str>,
+    ) -> Result<CheckoutSession, TuishError> {
+        self.purchase_in_browser_with_options(CheckoutOptions::from_email(email))
+            .await
+    }
+
+    /// Start a browser checkout session with custom options
+    ///
+    /// Like `purchase_in_browser`, but also sets the success and cancel
+    /// redirect URLs.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use tuish::{CheckoutOptions, Tuish};
+    ///
+    /// # async fn example(tuish: &Tuish) -> Result<(), tuish::TuishError> {
+    /// let options = CheckoutOptions::new()
+    ///     .with_success_url("http://127.0.0.1:8765/done")
+    ///     .with_cancel_url("http://127.0.0.1:8765/cancelled");
+    /// let session = tuish.purchase_in_browser_with_options(options).await?;
+    /// println!("Open {}", session.checkout_url);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "http")]
+    pub async fn purchase_in_browser_with_options(
+        &self,
+        options: CheckoutOptions,
+    ) -> Result<CheckoutSession, TuishError> {
+        let flow_id = client::new_request_id();
+        let span = info_span!("checkout", flow_id = %flow_id);
+        debug!(parent: &span, email = ?options.email, "Creating checkout session");
+
+        let request = options.into_request(&self.config.product_id);
+
+        let mut client = self.client.clone();
+        client.set_request_id(Some(flow_id.clone()));
+        let response = client
+            .init_checkout(request)
+            .instrument(span.clone())
+            .await?;
+
+        info!(parent: &span, session_id = %response.session_id, "Checkout session created");
+
+        if let Ok(mut flows) = self.checkout_flows.lock() {
+            flows.insert(response.session_id.clone(), flow_id.clone());
+        }
+
+        Ok(CheckoutSession {
+            session_id: response.session_id,
+            checkout_url: response.checkout_url,
+            flow_id,
+        })
+    }
+
+    /// Open checkout in the default browser
+    ///
+    /// Creates a checkout session and automatically opens it in the user's
+    /// default web browser. Requires both `http` and `browser` features.
+    ///
+    /// # Arguments
+    ///
+    /// * `email` - Optional customer email to pre-fill the checkout form
+    ///
+    /// # Returns
+    ///
+    /// A `CheckoutSession` containing the session ID for polling.
     ///
     /// # Example
     ///
@@ -418,20 +1208,92 @@ impl Tuish {
     /// ```
     #[cfg(all(feature = "http", feature = "browser"))]
     pub async fn open_checkout(&self, email: Option<&str>) -> Result<CheckoutSession, TuishError> {
-        let session = self.purchase_in_browser(email).await?;
+        self.open_checkout_with_options(CheckoutOptions::from_email(email))
+            .await
+    }
+
+    /// Open checkout in the default browser with custom options
+    ///
+    /// Like `open_checkout`, but also sets the success and cancel redirect
+    /// URLs; see `CheckoutOptions`.
+    #[cfg(all(feature = "http", feature = "browser"))]
+    pub async fn open_checkout_with_options(
+        &self,
+        options: CheckoutOptions,
+    ) -> Result<CheckoutSession, TuishError> {
+        let session = self.purchase_in_browser_with_options(options).await?;
 
         // Open the checkout URL in the browser
+        let _span = info_span!("checkout", flow_id = %session.flow_id).entered();
         browser::open_url_detached(&session.checkout_url)?;
 
         Ok(session)
     }
 
+    /// Run a browser checkout that completes on the loopback redirect
+    ///
+    /// Starts a one-shot HTTP server on `127.0.0.1:<port>` (`0` picks a free
+    /// port), opens checkout with `http://127.0.0.1:<port>/callback` as the
+    /// success URL and `/cancel` as the cancel URL, and fetches the license
+    /// the instant the browser is redirected back instead of waiting for the
+    /// next poll. If no redirect arrives within 5 minutes (e.g. the user
+    /// finished checkout on another device), falls back to
+    /// `wait_for_checkout` polling.
+    ///
+    /// # Returns
+    ///
+    /// A `LicenseCheckResult` with the new license if successful, or
+    /// `LicenseInvalidReason::Cancelled` if the browser reached the cancel
+    /// URL.
+    #[cfg(all(feature = "http", feature = "browser"))]
+    pub async fn wait_for_redirect(&mut self, port: u16) -> Result<LicenseCheckResult, TuishError> {
+        let listener = browser::RedirectListener::bind(port).await?;
+        let options = CheckoutOptions::new()
+            .with_success_url(listener.success_url())
+            .with_cancel_url(listener.cancel_url());
+        let session = self.open_checkout_with_options(options).await?;
+
+        match tokio::time::timeout(REDIRECT_TIMEOUT, listener.accept()).await {
+            Ok(Ok(browser::CheckoutRedirect::Cancelled)) => {
+                info!(session_id = %session.session_id, "Checkout cancelled in browser");
+                if let Ok(mut flows) = self.checkout_flows.lock() {
+                    flows.remove(&session.session_id);
+                }
+                Ok(LicenseCheckResult {
+                    valid: false,
+                    license: None,
+                    reason: Some(LicenseInvalidReason::Cancelled),
+                    source: ResultSource::NotFound,
+                    stale: false,
+                    mismatch_detail: None,
+                    warning: None,
+                })
+            }
+            // Redirected to the success URL: the first poll picks up the license
+            Ok(Ok(browser::CheckoutRedirect::Success)) => {
+                self.wait_for_checkout(&session.session_id).await
+            }
+            Ok(Err(e)) => {
+                warn!(error = %e, "Redirect listener failed, falling back to polling");
+                self.wait_for_checkout(&session.session_id).await
+            }
+            Err(_) => {
+                debug!("No checkout redirect received, falling back to polling");
+                self.wait_for_checkout(&session.session_id).await
+            }
+        }
+    }
+
     /// Wait for checkout to complete
     ///
     /// Polls the checkout status until the user completes payment or
     /// the session expires. Default timeout is 10 minutes with 2-second
     /// polling interval.
     ///
+    /// If the completed session carries an identity token (returning
+    /// customers), it is set on the client, so `init_purchase` and other
+    /// identity-token endpoints work without logging in again.
+    ///
     /// # Arguments
     ///
     /// * `session_id` - The session ID from `purchase_in_browser` or `open_checkout`
@@ -444,12 +1306,16 @@ impl Tuish {
         &mut self,
         session_id: &str,
     ) -> Result<LicenseCheckResult, TuishError> {
-        self.wait_for_checkout_with_options(session_id, Duration::from_secs(2), Duration::from_secs(600))
+        self.wait_for_checkout_with_config(session_id, &CheckoutPollConfig::default())
             .await
     }
 
     /// Wait for checkout to complete with custom options
     ///
+    /// Each status request is bounded by the default
+    /// `CheckoutPollConfig::request_timeout`; use
+    /// `wait_for_checkout_with_config` to change it.
+    ///
     /// # Arguments
     ///
     /// * `session_id` - The session ID to poll
@@ -462,65 +1328,569 @@ impl Tuish {
         poll_interval: Duration,
         timeout: Duration,
     ) -> Result<LicenseCheckResult, TuishError> {
-        debug!(
-            session_id = %session_id,
-            poll_interval = ?poll_interval,
-            timeout = ?timeout,
-            "Waiting for checkout to complete"
-        );
-
-        let start = std::time::Instant::now();
+        self.wait_for_checkout_cancellable(
+            session_id,
+            poll_interval,
+            timeout,
+            &CancellationToken::new(),
+        )
+        .await
+    }
+
+    /// Wait for checkout to complete using a polling schedule
+    ///
+    /// Use this to enable backoff and jitter, e.g.
+    /// `CheckoutPollConfig::new().with_backoff(1.5, Duration::from_secs(15)).with_jitter(0.2)`.
+    ///
+    /// # Arguments
+    ///
+    /// * `session_id` - The session ID to poll
+    /// * `config` - Polling interval, backoff, jitter and timeout
+    #[cfg(feature = "http")]
+    pub async fn wait_for_checkout_with_config(
+        &mut self,
+        session_id: &str,
+        config: &CheckoutPollConfig,
+    ) -> Result<LicenseCheckResult, TuishError> {
+        self.poll_checkout(session_id, config, &CancellationToken::new(), &mut |_| {})
+            .await
+    }
+
+    /// Wait for checkout to complete, stopping early if `cancel` fires
+    ///
+    /// The token is checked before each poll and interrupts in-flight
+    /// requests and sleeps, so cancelling (e.g. the user pressing Esc)
+    /// returns promptly with `LicenseInvalidReason::Cancelled`.
+    ///
+    /// # Arguments
+    ///
+    /// * `session_id` - The session ID to poll
+    /// * `poll_interval` - How often to check the status
+    /// * `timeout` - Maximum time to wait before timing out
+    /// * `cancel` - Token that aborts polling when cancelled
+    #[cfg(feature = "http")]
+    pub async fn wait_for_checkout_cancellable(
+        &mut self,
+        session_id: &str,
+        poll_interval: Duration,
+        timeout: Duration,
+        cancel: &CancellationToken,
+    ) -> Result<LicenseCheckResult, TuishError> {
+        let config = CheckoutPollConfig::new()
+            .with_poll_interval(poll_interval)
+            .with_timeout(timeout);
+        self.poll_checkout(session_id, &config, cancel, &mut |_| {})
+            .await
+    }
+
+    /// Wait for checkout to complete, reporting each poll to `on_poll`
+    ///
+    /// The callback receives the attempt number, elapsed time and status
+    /// after every poll, so a TUI can render e.g. "attempt 5, 4m remaining".
+    ///
+    /// # Arguments
+    ///
+    /// * `session_id` - The session ID to poll
+    /// * `poll_interval` - How often to check the status
+    /// * `timeout` - Maximum time to wait before timing out
+    /// * `on_poll` - Called with a `CheckoutPollProgress` after each poll
+    #[cfg(feature = "http")]
+    pub async fn wait_for_checkout_with_progress(
+        &mut self,
+        session_id: &str,
+        poll_interval: Duration,
+        timeout: Duration,
+        mut on_poll: impl FnMut(CheckoutPollProgress),
+    ) -> Result<LicenseCheckResult, TuishError> {
+        let config = CheckoutPollConfig::new()
+            .with_poll_interval(poll_interval)
+            .with_timeout(timeout);
+        self.poll_checkout(session_id, &config, &CancellationToken::new(), &mut on_poll)
+            .await
+    }
+
+    /// Checkout polling shared by the `wait_for_checkout*` methods
+    ///
+    /// Polls under the flow ID assigned by `purchase_in_browser` for this
+    /// session, or a fresh one for sessions created elsewhere.
+    #[cfg(feature = "http")]
+    async fn poll_checkout(
+        &mut self,
+        session_id: &str,
+        config: &CheckoutPollConfig,
+        cancel: &CancellationToken,
+        on_poll: &mut dyn FnMut(CheckoutPollProgress),
+    ) -> Result<LicenseCheckResult, TuishError> {
+        let flow_id = self
+            .checkout_flows
+            .lock()
+            .ok()
+            .and_then(|flows| flows.get(session_id).cloned())
+            .unwrap_or_else(client::new_request_id);
+        let span = info_span!("checkout", flow_id = %flow_id);
+
+        let mut client = self.client.clone();
+        client.set_request_id(Some(flow_id));
+        let result = self
+            .poll_checkout_status(&client, session_id, config, cancel, on_poll)
+            .instrument(span)
+            .await;
+
+        // Keep the flow ID if the caller may resume polling this session
+        let cancelled = matches!(
+            &result,
+            Ok(LicenseCheckResult {
+                reason: Some(LicenseInvalidReason::Cancelled),
+                ..
+            })
+        );
+        if !cancelled {
+            if let Ok(mut flows) = self.checkout_flows.lock() {
+                flows.remove(session_id);
+            }
+        }
+        result
+    }
+
+    /// Checkout polling loop, sending requests through `client`
+    #[cfg(feature = "http")]
+    async fn poll_checkout_status(
+        &mut self,
+        client: &TuishClient,
+        session_id: &str,
+        config: &CheckoutPollConfig,
+        cancel: &CancellationToken,
+        on_poll: &mut dyn FnMut(CheckoutPollProgress),
+    ) -> Result<LicenseCheckResult, TuishError> {
+        debug!(
+            session_id = %session_id,
+            poll_interval = ?config.poll_interval,
+            max_interval = ?config.max_interval,
+            timeout = ?config.timeout,
+            request_timeout = ?config.request_timeout,
+            "Waiting for checkout to complete"
+        );
+
+        let start = std::time::Instant::now();
+        let mut attempt = 0;
+        let cancelled = || {
+            debug!(session_id = %session_id, "Checkout polling cancelled");
+            Ok(LicenseCheckResult {
+                valid: false,
+                license: None,
+                reason: Some(LicenseInvalidReason::Cancelled),
+                source: ResultSource::NotFound,
+                stale: false,
+                mismatch_detail: None,
+                warning: None,
+            })
+        };
 
         loop {
+            if cancel.is_cancelled() {
+                return cancelled();
+            }
+
             // Check timeout
-            if start.elapsed() > timeout {
+            if start.elapsed() > config.timeout {
                 warn!(session_id = %session_id, "Checkout timed out");
                 return Ok(LicenseCheckResult {
                     valid: false,
                     license: None,
                     reason: Some(LicenseInvalidReason::NetworkError),
-                    offline_verified: false,
+                    source: ResultSource::NotFound,
+                    stale: false,
+                    mismatch_detail: None,
+                    warning: None,
                 });
             }
 
-            // Poll status
-            let status = self.client.get_checkout_status(session_id).await?;
-
-            match status.status {
-                CheckoutStatus::Complete => {
-                    if let Some(license_key) = status.license {
-                        info!(session_id = %session_id, "Checkout completed");
-
-                        // Save and verify the license (sync operation, no await needed)
-                        return self.license_manager.save_license(&license_key);
-                    } else {
-                        warn!(session_id = %session_id, "Checkout complete but no license key");
-                        return Ok(LicenseCheckResult {
-                            valid: false,
-                            license: None,
-                            reason: Some(LicenseInvalidReason::NotFound),
-                            offline_verified: false,
-                        });
-                    }
+            // Poll status, never past the overall deadline
+            let request_timeout = config
+                .request_timeout
+                .min(config.timeout.saturating_sub(start.elapsed()));
+            let status = tokio::select! {
+                _ = cancel.cancelled() => return cancelled(),
+                status = tokio::time::timeout(request_timeout, client.get_checkout_status(session_id)) => status,
+            };
+
+            attempt += 1;
+            if let Ok(status) = status {
+                let status = status?;
+                on_poll(CheckoutPollProgress {
+                    attempt,
+                    elapsed: start.elapsed(),
+                    status: status.status,
+                });
+
+                if let Some(result) = self.checkout_outcome(session_id, status) {
+                    return result;
+                }
+            } else {
+                warn!(session_id = %session_id, attempt, timeout = ?request_timeout, "Checkout status request timed out");
+            }
+
+            // Wait before next poll
+            tokio::select! {
+                _ = cancel.cancelled() => return cancelled(),
+                _ = tokio::time::sleep(config.interval_for_attempt(attempt)) => {}
+            }
+        }
+    }
+
+    /// Handle one checkout status poll
+    ///
+    /// Returns the final result once the session is complete or expired,
+    /// or `None` while it is still pending.
+    #[cfg(feature = "http")]
+    fn checkout_outcome(
+        &mut self,
+        session_id: &str,
+        status: CheckoutStatusResponse,
+    ) -> Option<Result<LicenseCheckResult, TuishError>> {
+        match status.status {
+            CheckoutStatus::Complete => {
+                // Returning customers come back signed in, so the purchase
+                // and manage flows work without a separate login
+                if let Some(token) = status.identity_token {
+                    self.client.set_identity_token(Some(token));
                 }
-                CheckoutStatus::Expired => {
-                    debug!(session_id = %session_id, "Checkout session expired");
-                    return Ok(LicenseCheckResult {
+
+                if let Some(license_key) = status.license {
+                    info!(session_id = %session_id, "Checkout completed");
+
+                    // Save and verify the license (sync operation, no await needed)
+                    Some(self.license_manager.save_license(&license_key))
+                } else {
+                    warn!(session_id = %session_id, "Checkout complete but no license key");
+                    Some(Ok(LicenseCheckResult {
                         valid: false,
                         license: None,
-                        reason: Some(LicenseInvalidReason::Expired),
-                        offline_verified: false,
-                    });
-                }
-                CheckoutStatus::Pending => {
-                    // Still waiting
-                    debug!(session_id = %session_id, "Checkout still pending");
+                        reason: Some(LicenseInvalidReason::NotFound),
+                        source: ResultSource::NotFound,
+                        stale: false,
+                        mismatch_detail: None,
+                        warning: None,
+                    }))
                 }
             }
+            CheckoutStatus::Expired => {
+                debug!(session_id = %session_id, "Checkout session expired");
+                Some(Ok(LicenseCheckResult {
+                    valid: false,
+                    license: None,
+                    reason: Some(LicenseInvalidReason::Expired),
+                    source: ResultSource::Online,
+                    stale: false,
+                    mismatch_detail: None,
+                    warning: None,
+                }))
+            }
+            CheckoutStatus::Pending => {
+                // Still waiting
+                debug!(session_id = %session_id, "Checkout still pending");
+                None
+            }
+        }
+    }
 
-            // Wait before next poll
-            tokio::time::sleep(poll_interval).await;
+    // =========================================================================
+    // Blocking API (requires `blocking` feature)
+    // =========================================================================
+
+    /// Build a blocking client from this instance's configuration
+    ///
+    /// Created per call because `reqwest::blocking` clients must not be
+    /// dropped inside an async runtime, which `Tuish` may be owned by.
+    #[cfg(feature = "blocking")]
+    fn blocking_client(
+        &self,
+        request_id: Option<String>,
+    ) -> Result<TuishClientBlocking, TuishError> {
+        let mut client = TuishClientBlocking::with_config(
+            &self.config.api_base_url,
+            self.config.api_key.as_deref().unwrap_or(""),
+            Duration::from_secs(30),
+            self.config.debug,
+        )?
+        .with_api_prefix(self.config.api_prefix.as_deref().unwrap_or(""))
+        .with_offline(self.config.offline_only);
+        client.set_request_id(request_id);
+        Ok(client)
+    }
+
+    /// Force online license validation without an async runtime
+    ///
+    /// Blocking version of `validate_online`. Must not be called from
+    /// inside an async runtime.
+    ///
+    /// # Arguments
+    ///
+    /// * `license_key` - Optional license key to validate. If not provided,
+    ///   the cached license key will be used.
+    #[cfg(feature = "blocking")]
+    pub fn validate_online_blocking(
+        &self,
+        license_key: Option<&str>,
+    ) -> Result<LicenseCheckResult, TuishError> {
+        let key = match license_key {
+            Some(k) => k.to_string(),
+            None => self
+                .license_manager
+                .get_cached_license_key()
+                .ok_or_else(|| {
+                    TuishError::InvalidLicense("no license key available".to_string())
+                })?,
+        };
+
+        let client = self.blocking_client(None)?;
+        self.license_manager.validate_online_blocking(&client, &key)
+    }
+
+    /// Open checkout in the default browser without an async runtime
+    ///
+    /// Blocking version of `open_checkout`. Must not be called from inside
+    /// an async runtime.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use tuish::Tuish;
+    ///
+    /// # fn example() -> Result<(), tuish::TuishError> {
+    /// let mut tuish = Tuish::builder()
+    ///     .product_id("prod_xxx")
+    ///     .public_key("key...")
+    ///     .build()?;
+    ///
+    /// let session = tuish.open_checkout_blocking(None)?;
+    /// let result = tuish.wait_for_checkout_blocking(&session.session_id)?;
+    /// if result.valid {
+    ///     println!("Purchase complete!");
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(all(feature = "blocking", feature = "browser"))]
+    pub fn open_checkout_blocking(
+        &self,
+        email: Option<&str>,
+    ) -> Result<CheckoutSession, TuishError> {
+        self.open_checkout_blocking_with_options(CheckoutOptions::from_email(email))
+    }
+
+    /// Open checkout in the default browser with custom options, without an async runtime
+    ///
+    /// Blocking version of `open_checkout_with_options`. Must not be called
+    /// from inside an async runtime.
+    #[cfg(all(feature = "blocking", feature = "browser"))]
+    pub fn open_checkout_blocking_with_options(
+        &self,
+        options: CheckoutOptions,
+    ) -> Result<CheckoutSession, TuishError> {
+        let flow_id = client::new_request_id();
+        let _span = info_span!("checkout", flow_id = %flow_id).entered();
+        debug!(email = ?options.email, "Creating checkout session");
+
+        let request = options.into_request(&self.config.product_id);
+
+        let response = self
+            .blocking_client(Some(flow_id.clone()))?
+            .init_checkout(request)?;
+
+        info!(session_id = %response.session_id, "Checkout session created");
+
+        if let Ok(mut flows) = self.checkout_flows.lock() {
+            flows.insert(response.session_id.clone(), flow_id.clone());
+        }
+
+        browser::open_url_detached(&response.checkout_url)?;
+
+        Ok(CheckoutSession {
+            session_id: response.session_id,
+            checkout_url: response.checkout_url,
+            flow_id,
+        })
+    }
+
+    /// Wait for checkout to complete without an async runtime
+    ///
+    /// Blocking version of `wait_for_checkout`, using the default polling
+    /// schedule. Must not be called from inside an async runtime.
+    #[cfg(feature = "blocking")]
+    pub fn wait_for_checkout_blocking(
+        &mut self,
+        session_id: &str,
+    ) -> Result<LicenseCheckResult, TuishError> {
+        self.wait_for_checkout_blocking_with_config(session_id, &CheckoutPollConfig::default())
+    }
+
+    /// Wait for checkout to complete using a polling schedule, without an async runtime
+    ///
+    /// Blocking version of `wait_for_checkout_with_config`.
+    #[cfg(feature = "blocking")]
+    pub fn wait_for_checkout_blocking_with_config(
+        &mut self,
+        session_id: &str,
+        config: &CheckoutPollConfig,
+    ) -> Result<LicenseCheckResult, TuishError> {
+        let flow_id = self
+            .checkout_flows
+            .lock()
+            .ok()
+            .and_then(|flows| flows.get(session_id).cloned())
+            .unwrap_or_else(client::new_request_id);
+        let _span = info_span!("checkout", flow_id = %flow_id).entered();
+        let client = self.blocking_client(Some(flow_id))?;
+
+        debug!(session_id = %session_id, timeout = ?config.timeout, "Waiting for checkout to complete");
+
+        let start = std::time::Instant::now();
+        let mut attempt = 0;
+        let result = loop {
+            if start.elapsed() > config.timeout {
+                warn!(session_id = %session_id, "Checkout timed out");
+                break Ok(LicenseCheckResult {
+                    valid: false,
+                    license: None,
+                    reason: Some(LicenseInvalidReason::NetworkError),
+                    source: ResultSource::NotFound,
+                    stale: false,
+                    mismatch_detail: None,
+                    warning: None,
+                });
+            }
+
+            let status = client.get_checkout_status(session_id)?;
+            attempt += 1;
+
+            if let Some(result) = self.checkout_outcome(session_id, status) {
+                break result;
+            }
+
+            std::thread::sleep(config.interval_for_attempt(attempt));
+        };
+
+        if let Ok(mut flows) = self.checkout_flows.lock() {
+            flows.remove(session_id);
+        }
+        result
+    }
+
+    // =========================================================================
+    // Machine Binding
+    // =========================================================================
+
+    /// Release this machine's seat and clear the cached license
+    ///
+    /// Use this before moving a seat-limited license to another machine.
+    /// The local cache is only cleared once the API confirms the seat was
+    /// released.
+    ///
+    /// # Errors
+    ///
+    /// Returns `TuishError::InvalidLicense` if no license is cached, or an
+    /// API error if the server refuses the deactivation.
+    #[cfg(feature = "http")]
+    pub async fn deactivate(&mut self) -> Result<(), TuishError> {
+        let license_key = self
+            .license_manager
+            .get_cached_license_key()
+            .ok_or_else(|| {
+                TuishError::InvalidLicense("no cached license to deactivate".to_string())
+            })?;
+
+        let response = self
+            .client
+            .deactivate_license(&license_key, &self.config.machine_fingerprint())
+            .await?;
+
+        if !response.deactivated {
+            return Err(TuishError::ApiError {
+                status: 409,
+                message: "License could not be deactivated".to_string(),
+            });
+        }
+
+        info!("License deactivated on this machine");
+        self.license_manager.clear_license()
+    }
+
+    /// Bind an existing license to this machine
+    ///
+    /// For users who reinstalled or moved machines: the API returns a
+    /// freshly signed license bound to the current fingerprint, which is
+    /// then verified and cached.
+    ///
+    /// # Arguments
+    ///
+    /// * `license_key` - The license key previously bound to another machine
+    ///
+    /// # Returns
+    ///
+    /// The verification result for the rebound license.
+    #[cfg(feature = "http")]
+    pub async fn rebind(&mut self, license_key: &str) -> Result<LicenseCheckResult, TuishError> {
+        let response = self
+            .client
+            .rebind_license(license_key, &self.config.machine_fingerprint())
+            .await?;
+
+        info!("License rebound to this machine");
+        self.license_manager.save_license(&response.license)
+    }
+
+    /// Download and apply the product's signed revocation list
+    ///
+    /// The list is verified with the product public key and cached, so
+    /// revoked licenses are rejected by later offline checks too.
+    ///
+    /// # Returns
+    ///
+    /// The revocation list now in effect.
+    #[cfg(feature = "http")]
+    pub async fn refresh_revocation_list(&mut self) -> Result<RevocationList, TuishError> {
+        let response = self
+            .client
+            .get_revocation_list(&self.config.product_id)
+            .await?;
+
+        self.license_manager
+            .set_revocation_list(&response.list)
+            .cloned()
+    }
+
+    // =========================================================================
+    // Trials
+    // =========================================================================
+
+    /// Start a time-limited trial on this machine
+    ///
+    /// Requests a signed trial license from the API and saves it like a
+    /// purchased license. Trial licenses carry the `trial` feature flag, so
+    /// `LicenseDetails::is_trial` can be used to badge them in the UI.
+    ///
+    /// # Returns
+    ///
+    /// The verification result for the saved trial license.
+    #[cfg(feature = "http")]
+    pub async fn start_trial(&mut self) -> Result<LicenseCheckResult, TuishError> {
+        let machine_fingerprint = self.config.machine_fingerprint();
+        let response = self
+            .client
+            .start_trial(&self.config.product_id, &machine_fingerprint)
+            .await?;
+
+        info!(expires_at = response.expires_at, "Trial started");
+        let result = self.license_manager.save_license(&response.license)?;
+
+        // Remember the trial locally so clearing the cache doesn't reset it
+        if result.valid {
+            if let Err(e) = self.license_manager.record_trial_start() {
+                warn!(error = %e, "Failed to record trial start");
+            }
         }
+        Ok(result)
     }
 
     // =========================================================================
@@ -551,6 +1921,22 @@ impl Tuish {
     /// # Returns
     ///
     /// The license check result after successful purchase.
+    ///
+    /// # Errors
+    ///
+    /// Returns `TuishError::PurchaseActionRequired` if the card needs 3D
+    /// Secure authentication; open its `action_url`, then resume with
+    /// `confirm_purchase_after_action`.
+    ///
+    /// Returns `TuishError::NotATerminal` before contacting the API if stdin
+    /// is not a terminal and the OTPs are not in the environment.
+    ///
+    /// # Without a terminal
+    ///
+    /// In pipes and CI the OTP callbacks are skipped and the codes are read
+    /// from `TUISH_LOGIN_OTP` and `TUISH_PURCHASE_OTP` instead, for
+    /// automated tests against a test account. `select_card` is still
+    /// called. Use `TuishBuilder::interactive` to override the detection.
     #[cfg(feature = "http")]
     pub async fn purchase_in_terminal<F1, F2, F3, Fut1, Fut2, Fut3>(
         &mut self,
@@ -567,17 +1953,275 @@ impl Tuish {
         F3: FnOnce(String) -> Fut3,
         Fut3: std::future::Future<Output = String>,
     {
-        info!(email = %email, "Starting terminal purchase flow");
+        let flow_id = client::new_request_id();
+        let span = info_span!("terminal_purchase", flow_id = %flow_id);
+        info!(parent: &span, email = %email, "Starting terminal purchase flow");
 
-        // Step 1: Request login OTP
-        let login_init = self.client.request_login_otp(email).await?;
-        debug!(phone_masked = %login_init.phone_masked, "Login OTP requested");
+        self.client.set_request_id(Some(flow_id));
+        let result = async {
+            let (_, confirm_request) = self
+                .prepare_terminal_purchase(email, get_login_otp, select_card, get_purchase_otp)
+                .await?;
 
-        // Step 2: Get login OTP from user
-        let login_otp = get_login_otp(login_init.phone_masked.clone()).await;
+            // Step 8: Confirm purchase
+            self.finish_purchase(confirm_request).await
+        }
+        .instrument(span)
+        .await;
+        self.client.set_request_id(None);
+
+        result
+    }
+
+    /// Resume a terminal purchase after 3D Secure authentication
+    ///
+    /// Call this once the customer has completed the action at
+    /// `pending.action_url` (from `TuishError::PurchaseActionRequired`).
+    /// Re-sends the confirmation and saves the license on success.
+    ///
+    /// The re-sent confirmation gets a fresh idempotency key: reusing the
+    /// first one would make the API replay its `requires_action` response.
+    #[cfg(feature = "http")]
+    pub async fn confirm_purchase_after_action(
+        &mut self,
+        pending: &PendingPurchase,
+    ) -> Result<LicenseCheckResult, TuishError> {
+        info!("Resuming purchase after authentication");
+        let confirm_request = PurchaseConfirmRequest {
+            idempotency_key: Some(client::new_idempotency_key()),
+            ..pending.confirm_request.clone()
+        };
+        self.finish_purchase(confirm_request).await
+    }
+
+    /// Confirm a purchase and save the resulting license
+    #[cfg(feature = "http")]
+    async fn finish_purchase(
+        &mut self,
+        confirm_request: PurchaseConfirmRequest,
+    ) -> Result<LicenseCheckResult, TuishError> {
+        let confirm_result = self
+            .client
+            .confirm_purchase(confirm_request.clone())
+            .await?;
+
+        if confirm_result.requires_action == Some(true) {
+            let action_url = confirm_result
+                .action_url
+                .ok_or_else(|| TuishError::ApiError {
+                    status: 500,
+                    message: "Purchase requires action but no action URL returned".to_string(),
+                })?;
+            info!("Purchase requires 3D Secure authentication");
+            return Err(TuishError::PurchaseActionRequired(Box::new(
+                PendingPurchase {
+                    action_url,
+                    confirm_request,
+                },
+            )));
+        }
+
+        if !confirm_result.success {
+            return Err(TuishError::ApiError {
+                status: 400,
+                message: confirm_result
+                    .error
+                    .unwrap_or_else(|| "Purchase failed".to_string()),
+            });
+        }
+
+        // Save and verify license
+        if let Some(license_key) = confirm_result.license {
+            info!("Purchase complete, saving license");
+            self.license_manager.save_license(&license_key)
+        } else {
+            Err(TuishError::ApiError {
+                status: 500,
+                message: "Purchase succeeded but no license returned".to_string(),
+            })
+        }
+    }
+
+    /// Dry run of the terminal purchase flow
+    ///
+    /// Runs steps 1-7 of `purchase_in_terminal` (login, card selection,
+    /// purchase OTP) with the same callbacks, but never confirms the
+    /// purchase, so no card is charged and no license is saved. Use this to
+    /// validate an integration against a test account.
+    ///
+    /// # Returns
+    ///
+    /// The purchase details and the selected card ID.
+    #[cfg(feature = "http")]
+    pub async fn preview_purchase_in_terminal<F1, F2, F3, Fut1, Fut2, Fut3>(
+        &mut self,
+        email: &str,
+        get_login_otp: F1,
+        select_card: F2,
+        get_purchase_otp: F3,
+    ) -> Result<PurchasePreview, TuishError>
+    where
+        F1: FnOnce(String) -> Fut1,
+        Fut1: std::future::Future<Output = String>,
+        F2: FnOnce(Vec<SavedCard>, i64, String) -> Fut2,
+        Fut2: std::future::Future<Output = Option<String>>,
+        F3: FnOnce(String) -> Fut3,
+        Fut3: std::future::Future<Output = String>,
+    {
+        info!(email = %email, "Starting terminal purchase preview");
+
+        let (preview, _) = self
+            .prepare_terminal_purchase(email, get_login_otp, select_card, get_purchase_otp)
+            .await?;
+
+        info!(card_id = %preview.card_id, "Purchase preview complete, not charging");
+        Ok(preview)
+    }
+
+    /// Sell a license using `strategy`
+    ///
+    /// One entry point for apps that offer more than one purchase flow.
+    /// With `PurchaseStrategy::BrowserThenTerminal`, a browser checkout
+    /// that cannot be opened or times out (the user closed the tab) is
+    /// followed by `purchase_in_terminal`; an expired or cancelled checkout
+    /// is returned as-is.
+    ///
+    /// # Errors
+    ///
+    /// Returns the error of the flow that ran last; see `open_checkout`,
+    /// `wait_for_checkout` and `purchase_in_terminal`.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use tuish::{CheckoutPollConfig, PurchaseStrategy, TerminalPrompts, Tuish};
+    /// use std::time::Duration;
+    ///
+    /// # async fn example() -> Result<(), tuish::TuishError> {
+    /// let mut tuish = Tuish::builder()
+    ///     .product_id("prod_xxx")
+    ///     .public_key("key...")
+    ///     .build()?;
+    ///
+    /// let outcome = tuish
+    ///     .purchase(PurchaseStrategy::BrowserThenTerminal {
+    ///         poll: CheckoutPollConfig::new().with_timeout(Duration::from_secs(300)),
+    ///         prompts: TerminalPrompts::new(
+    ///             "user@example.com",
+    ///             |phone| async move { prompt_otp(&phone) },
+    ///             |cards, _, _| async move { cards.first().map(|c| c.id.clone()) },
+    ///             |phone| async move { prompt_otp(&phone) },
+    ///         ),
+    ///     })
+    ///     .await?;
+    /// println!("Purchased via {:?}: {}", outcome.method, outcome.result.valid);
+    /// # Ok(())
+    /// # }
+    /// # fn prompt_otp(_phone: &str) -> String { String::new() }
+    /// ```
+    #[cfg(all(feature = "http", feature = "browser"))]
+    pub async fn purchase(
+        &mut self,
+        strategy: PurchaseStrategy<'_>,
+    ) -> Result<PurchaseOutcome, TuishError> {
+        let prompts = match strategy {
+            PurchaseStrategy::Browser { email, poll } => {
+                let session = self.open_checkout(email.as_deref()).await?;
+                let result = self
+                    .wait_for_checkout_with_config(&session.session_id, &poll)
+                    .await?;
+                return Ok(PurchaseOutcome {
+                    method: PurchaseMethod::Browser,
+                    result,
+                });
+            }
+            PurchaseStrategy::Terminal(prompts) => prompts,
+            PurchaseStrategy::BrowserThenTerminal { poll, prompts } => {
+                let session = self.purchase_in_browser(Some(&prompts.email)).await?;
+                let opened = {
+                    let _span = info_span!("checkout", flow_id = %session.flow_id).entered();
+                    browser::open_url_detached(&session.checkout_url)
+                };
+
+                match opened {
+                    Ok(()) => {
+                        let result = self
+                            .wait_for_checkout_with_config(&session.session_id, &poll)
+                            .await?;
+                        // Polling reports its timeout as a network failure
+                        if result.reason != Some(LicenseInvalidReason::NetworkError) {
+                            return Ok(PurchaseOutcome {
+                                method: PurchaseMethod::Browser,
+                                result,
+                            });
+                        }
+                        info!(session_id = %session.session_id, "Browser checkout timed out, falling back to terminal purchase");
+                    }
+                    Err(e) => {
+                        warn!(error = %e, "Could not open browser, falling back to terminal purchase");
+                        if let Ok(mut flows) = self.checkout_flows.lock() {
+                            flows.remove(&session.session_id);
+                        }
+                    }
+                }
+                prompts
+            }
+        };
+
+        let result = self
+            .purchase_in_terminal(
+                &prompts.email,
+                prompts.get_login_otp,
+                prompts.select_card,
+                prompts.get_purchase_otp,
+            )
+            .await?;
+        Ok(PurchaseOutcome {
+            method: PurchaseMethod::Terminal,
+            result,
+        })
+    }
+
+    /// Steps 1-7 of the terminal purchase flow, up to (not including) the
+    /// charge. Returns the preview and the request that would confirm it.
+    #[cfg(feature = "http")]
+    async fn prepare_terminal_purchase<F1, F2, F3, Fut1, Fut2, Fut3>(
+        &mut self,
+        email: &str,
+        get_login_otp: F1,
+        select_card: F2,
+        get_purchase_otp: F3,
+    ) -> Result<(PurchasePreview, PurchaseConfirmRequest), TuishError>
+    where
+        F1: FnOnce(String) -> Fut1,
+        Fut1: std::future::Future<Output = String>,
+        F2: FnOnce(Vec<SavedCard>, i64, String) -> Fut2,
+        Fut2: std::future::Future<Output = Option<String>>,
+        F3: FnOnce(String) -> Fut3,
+        Fut3: std::future::Future<Output = String>,
+    {
+        let interactive = self
+            .config
+            .interactive
+            .unwrap_or_else(|| std::io::IsTerminal::is_terminal(&std::io::stdin()));
+        let scripted = scripted_otps(
+            interactive,
+            (self.otp_env)(LOGIN_OTP_ENV),
+            (self.otp_env)(PURCHASE_OTP_ENV),
+        )?;
+
+        // Step 1: Request login OTP
+        let login_init = self.client.request_login_otp(email).await?;
+        debug!(phone_masked = %login_init.phone_masked, "Login OTP requested");
+
+        // Step 2: Get login OTP from user
+        let login_otp = match &scripted {
+            Some((login_otp, _)) => login_otp.clone(),
+            None => get_login_otp(login_init.phone_masked.clone()).await,
+        };
 
         // Step 3: Verify login
-        let machine_fingerprint = get_machine_fingerprint();
+        let machine_fingerprint = self.config.machine_fingerprint();
         let login_result = self
             .client
             .verify_login(LoginVerifyRequest {
@@ -610,7 +2254,7 @@ impl Tuish {
 
         // Step 5: User selects card
         let card_id = select_card(
-            purchase_init.cards,
+            purchase_init.cards.clone(),
             purchase_init.amount,
             purchase_init.currency.clone(),
         )
@@ -625,36 +2269,26 @@ impl Tuish {
         debug!("Purchase OTP requested");
 
         // Step 7: Get purchase OTP from user
-        let purchase_otp = get_purchase_otp(purchase_init.phone_masked).await;
-
-        // Step 8: Confirm purchase
-        let confirm_result = self
-            .client
-            .confirm_purchase(PurchaseConfirmRequest {
-                product_id: self.config.product_id.clone(),
-                card_id,
-                otp_id: purchase_otp_response.otp_id,
-                otp: purchase_otp,
-            })
-            .await?;
+        let purchase_otp = match scripted {
+            Some((_, purchase_otp)) => purchase_otp,
+            None => get_purchase_otp(purchase_init.phone_masked.clone()).await,
+        };
 
-        if !confirm_result.success {
-            return Err(TuishError::ApiError {
-                status: 400,
-                message: confirm_result.error.unwrap_or_else(|| "Purchase failed".to_string()),
-            });
-        }
+        let confirm_request = PurchaseConfirmRequest {
+            product_id: self.config.product_id.clone(),
+            card_id: card_id.clone(),
+            otp_id: purchase_otp_response.otp_id,
+            otp: purchase_otp,
+            idempotency_key: Some(client::new_idempotency_key()),
+        };
 
-        // Save and verify license
-        if let Some(license_key) = confirm_result.license {
-            info!("Purchase complete, saving license");
-            self.license_manager.save_license(&license_key)
-        } else {
-            Err(TuishError::ApiError {
-                status: 500,
-                message: "Purchase succeeded but no license returned".to_string(),
-            })
-        }
+        Ok((
+            PurchasePreview {
+                purchase: purchase_init,
+                card_id,
+            },
+            confirm_request,
+        ))
     }
 
     // =========================================================================
@@ -672,8 +2306,11 @@ impl Tuish {
     }
 
     /// Get the current machine fingerprint
+    ///
+    /// Salted with the configured `fingerprint_salt`, if any, or the
+    /// pinned override; see [`fingerprint`](crate::fingerprint#override).
     pub fn machine_fingerprint(&self) -> String {
-        get_machine_fingerprint()
+        self.config.machine_fingerprint()
     }
 
     /// Get the underlying HTTP client (requires `http` feature)
@@ -710,16 +2347,30 @@ impl Tuish {
 /// # Ok(())
 /// # }
 /// ```
+#[cfg(feature = "storage")]
 #[derive(Debug, Default)]
 pub struct TuishBuilder {
     product_id: Option<String>,
+    product_name: Option<String>,
+    accepted_product_ids: Vec<String>,
     public_key: Option<String>,
     api_key: Option<String>,
     api_url: Option<String>,
+    api_prefix: Option<String>,
     storage_dir: Option<String>,
+    license_history: bool,
+    trial_dir: Option<String>,
+    audit_log: Option<String>,
+    clock_skew: Option<Duration>,
+    expiry_warning: Option<Duration>,
+    fingerprint_salt: Option<String>,
+    machine_fingerprint_override: Option<String>,
+    offline_only: bool,
+    interactive: Option<bool>,
     debug: bool,
 }
 
+#[cfg(feature = "storage")]
 impl TuishBuilder {
     /// Create a new builder with default settings
     pub fn new() -> Self {
@@ -734,16 +2385,59 @@ impl TuishBuilder {
         self
     }
 
+    /// Set the product's display name (optional)
+    ///
+    /// Offline license checks report it as `LicenseDetails::product_name`,
+    /// since signed licenses carry only the product ID.
+    pub fn product_name(mut self, name: impl Into<String>) -> Self {
+        self.product_name = Some(name.into());
+        self
+    }
+
+    /// Also accept licenses for another product ID (optional, repeatable)
+    ///
+    /// For interchangeable SKUs, e.g. a legacy product that should unlock
+    /// the same app.
+    pub fn accept_product_id(mut self, id: impl Into<String>) -> Self {
+        self.accepted_product_ids.push(id.into());
+        self
+    }
+
     /// Set the Ed25519 public key (required)
     ///
     /// This key is used for offline license verification.
-    /// Accepts SPKI base64 format (e.g., "MCowBQYDK2VwAyEA...")
-    /// or raw 64-character hex format.
+    /// Accepts SPKI base64 format (e.g., "MCowBQYDK2VwAyEA..."), a PEM
+    /// `PUBLIC KEY` block, or raw 64-character hex format.
     pub fn public_key(mut self, key: impl Into<String>) -> Self {
         self.public_key = Some(key.into());
         self
     }
 
+    /// Read the Ed25519 public key from a file at runtime
+    ///
+    /// Accepts the same formats as `public_key`. To bake the key into the
+    /// binary instead, use `include_public_key!`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `TuishError::StorageError` if the file cannot be read, or
+    /// `TuishError::InvalidPublicKey` if it does not hold a valid key.
+    pub fn public_key_from_file(
+        self,
+        path: impl AsRef<std::path::Path>,
+    ) -> Result<Self, TuishError> {
+        let path = path.as_ref();
+        let key = std::fs::read_to_string(path).map_err(|e| {
+            TuishError::StorageError(format!(
+                "failed to read public key {}: {}",
+                path.display(),
+                e
+            ))
+        })?;
+        license::parse_public_key(&key)?;
+        Ok(self.public_key(key))
+    }
+
     /// Set the API key for authenticated requests (optional)
     ///
     /// Required for online license validation and checkout flows.
@@ -760,14 +2454,109 @@ impl TuishBuilder {
         self
     }
 
+    /// Set a path prefix for a self-hosted API (optional)
+    ///
+    /// Requests go to `{api_url}{prefix}/v1/...`, e.g. `"/tuish/api"` for
+    /// an API mounted under that path.
+    pub fn api_prefix(mut self, prefix: impl Into<String>) -> Self {
+        self.api_prefix = Some(prefix.into());
+        self
+    }
+
     /// Set a custom storage directory (optional)
     ///
-    /// By default, licenses are stored in `~/.tuish/licenses/`.
+    /// Takes precedence over the `TUISH_STORAGE_DIR` environment variable.
+    /// By default, licenses are stored in `TUISH_STORAGE_DIR` if set, else
+    /// `~/.tuish/licenses/`, else under the system temp dir; see
+    /// [`storage`](crate::storage#storage-directory).
     pub fn storage_dir(mut self, dir: impl Into<String>) -> Self {
         self.storage_dir = Some(dir.into());
         self
     }
 
+    /// Keep the previous license when a renewal replaces it (optional)
+    ///
+    /// Off by default. Read it back with `LicenseStorage::load_previous`;
+    /// see [`storage`](crate::storage#history).
+    pub fn license_history(mut self, enabled: bool) -> Self {
+        self.license_history = enabled;
+        self
+    }
+
+    /// Set a custom directory for trial markers (optional)
+    ///
+    /// By default, markers are kept in the platform local data directory,
+    /// separate from the license cache.
+    pub fn trial_dir(mut self, dir: impl Into<String>) -> Self {
+        self.trial_dir = Some(dir.into());
+        self
+    }
+
+    /// Append a JSON line for every license check, refresh and save to `path` (optional)
+    ///
+    /// Off by default. Use `Tuish::set_audit_sink` for a custom destination;
+    /// see [`audit`](crate::audit).
+    pub fn audit_log(mut self, path: impl Into<String>) -> Self {
+        self.audit_log = Some(path.into());
+        self
+    }
+
+    /// Set the clock skew tolerated for a license's `nbf` claim (optional)
+    ///
+    /// Defaults to `DEFAULT_CLOCK_SKEW`.
+    pub fn clock_skew(mut self, skew: Duration) -> Self {
+        self.clock_skew = Some(skew);
+        self
+    }
+
+    /// How close to expiry a license gets `LicenseWarning::ExpiringSoon` (optional)
+    ///
+    /// Defaults to `DEFAULT_EXPIRY_WARNING` (7 days).
+    pub fn expiry_warning(mut self, within: Duration) -> Self {
+        self.expiry_warning = Some(within);
+        self
+    }
+
+    /// Salt the machine fingerprint with an app-specific value (optional)
+    ///
+    /// Makes the fingerprint unlinkable to other apps on the same machine.
+    /// Changing it breaks existing machine-bound licenses; see
+    /// [`fingerprint`](crate::fingerprint#salting).
+    pub fn fingerprint_salt(mut self, salt: impl Into<String>) -> Self {
+        self.fingerprint_salt = Some(salt.into());
+        self
+    }
+
+    /// Pin the machine fingerprint to 64 hex characters (optional)
+    ///
+    /// For CI and cloned VM images. Disables real machine binding; see
+    /// [`fingerprint`](crate::fingerprint#override).
+    pub fn machine_fingerprint_override(mut self, fingerprint: impl Into<String>) -> Self {
+        self.machine_fingerprint_override = Some(fingerprint.into());
+        self
+    }
+
+    /// Never contact the network (optional)
+    ///
+    /// Online validation, checkout and other API calls fail with
+    /// `TuishError::Offline` without sending a request, even when the
+    /// `http` feature is compiled in. Offline verification of cached
+    /// licenses keeps working.
+    pub fn offline_only(mut self, offline_only: bool) -> Self {
+        self.offline_only = offline_only;
+        self
+    }
+
+    /// Whether the terminal purchase flow may prompt (optional)
+    ///
+    /// By default it prompts only when stdin is a terminal, and otherwise
+    /// reads the OTPs from `TUISH_LOGIN_OTP` and `TUISH_PURCHASE_OTP`. Set
+    /// `true` if your callbacks collect input some other way, e.g. a GUI.
+    pub fn interactive(mut self, interactive: bool) -> Self {
+        self.interactive = Some(interactive);
+        self
+    }
+
     /// Enable debug logging (optional)
     pub fn debug(mut self, debug: bool) -> Self {
         self.debug = debug;
@@ -778,7 +2567,10 @@ impl TuishBuilder {
     ///
     /// # Errors
     ///
-    /// Returns an error if required fields (product_id, public_key) are not set.
+    /// Returns an error if required fields (product_id, public_key) are not set,
+    /// `TuishError::InvalidPublicKey` if the public key is not SPKI base64,
+    /// PEM, or 64-character hex, or `TuishError::ParseError` if the machine
+    /// fingerprint override is not 64 hex characters.
     pub fn build(self) -> Result<Tuish, TuishError> {
         let product_id = self.product_id.ok_or_else(|| {
             TuishError::InvalidLicense("product_id is required".to_string())
@@ -788,14 +2580,36 @@ impl TuishBuilder {
             TuishError::InvalidPublicKey("public_key is required".to_string())
         })?;
 
+        // Fail fast on a malformed key, before any client or storage setup
+        license::parse_public_key(&public_key)?;
+        if let Some(fingerprint) = &self.machine_fingerprint_override {
+            if !fingerprint::is_valid_fingerprint(fingerprint) {
+                return Err(TuishError::ParseError(
+                    "machine fingerprint override must be 64 hex characters".to_string(),
+                ));
+            }
+        }
+
         let config = TuishConfig {
             product_id,
+            product_name: self.product_name,
+            accepted_product_ids: self.accepted_product_ids,
             public_key,
             api_base_url: self
                 .api_url
                 .unwrap_or_else(|| "https://api.tuish.dev".to_string()),
+            api_prefix: self.api_prefix,
             api_key: self.api_key,
             storage_dir: self.storage_dir,
+            license_history: self.license_history,
+            trial_dir: self.trial_dir,
+            audit_log: self.audit_log,
+            clock_skew: self.clock_skew.unwrap_or(DEFAULT_CLOCK_SKEW),
+            expiry_warning: self.expiry_warning.unwrap_or(DEFAULT_EXPIRY_WARNING),
+            fingerprint_salt: self.fingerprint_salt,
+            machine_fingerprint_override: self.machine_fingerprint_override,
+            offline_only: self.offline_only,
+            interactive: self.interactive,
             debug: self.debug,
         };
 
@@ -803,9 +2617,12 @@ impl TuishBuilder {
     }
 }
 
-#[cfg(test)]
+#[cfg(all(test, feature = "storage"))]
 mod tests {
     use super::*;
+    #[cfg(feature = "http")]
+    use crate::test_support::sign_test_claims;
+    use crate::test_support::{sign_test_license, test_public_key};
 
     #[test]
     fn test_builder_missing_product_id() {
@@ -826,13 +2643,1565 @@ mod tests {
     }
 
     #[test]
-    fn test_checkout_session_fields() {
-        let session = CheckoutSession {
-            session_id: "sess_123".to_string(),
-            checkout_url: "https://checkout.tuish.dev/sess_123".to_string(),
-        };
+    fn test_save_license_normalizes_pasted_key() {
+        let license = test_license(serde_json::json!({}));
+        let (mut tuish, _temp_dir) = test_tuish();
 
-        assert_eq!(session.session_id, "sess_123");
-        assert!(session.checkout_url.contains("sess_123"));
+        let (head, tail) = license.split_at(40);
+        let result = tuish
+            .save_license(&format!("  \"{}\r\n{}\"\r\n", head, tail))
+            .unwrap();
+        assert!(result.valid);
+        assert_eq!(
+            tuish.get_cached_license_key().as_deref(),
+            Some(license.as_str())
+        );
+    }
+
+    #[test]
+    fn test_export_import_round_trip() {
+        let license = test_license(serde_json::json!({ "features": ["pro"] }));
+        let build = |temp_dir: &tempfile::TempDir| test_builder(temp_dir).build().unwrap();
+
+        let old_dir = tempfile::TempDir::new().unwrap();
+        let mut old = build(&old_dir);
+        assert!(old.export_license().is_none());
+        old.save_license(&license).unwrap();
+        let backup = old.export_license().unwrap();
+        assert_eq!(backup, license);
+
+        // A fresh store, as after a reinstall
+        let new_dir = tempfile::TempDir::new().unwrap();
+        let mut new = build(&new_dir);
+        assert!(new.import_license(&backup).unwrap().valid);
+        assert!(new.check_license().valid);
+        assert_eq!(new.export_license(), Some(backup));
+    }
+
+    #[test]
+    fn test_status_reflects_cached_license() {
+        let expires_at = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_millis() as i64
+            + 3_600_000;
+        let license = test_license(serde_json::json!({ "exp": expires_at }));
+        let (mut tuish, _temp_dir) = test_tuish();
+
+        let empty = tuish.status();
+        assert!(!empty.result.valid);
+        assert_eq!(empty.source, ResultSource::NotFound);
+        assert!(empty.time_until_expiry.is_none());
+        assert!(!empty.needs_refresh);
+
+        tuish.save_license(&license).unwrap();
+        let status = tuish.status();
+        assert!(status.result.valid);
+        assert_eq!(status.source, status.result.source);
+        assert!(!status.needs_refresh);
+        assert_eq!(status.fingerprint, tuish.machine_fingerprint());
+        let left = status.time_until_expiry.unwrap();
+        assert!(left > Duration::from_secs(3500) && left <= Duration::from_secs(3600));
+    }
+
+    #[test]
+    fn test_capabilities_match_features() {
+        let capabilities = Tuish::capabilities();
+
+        assert_eq!(capabilities.http, cfg!(feature = "http"));
+        assert_eq!(capabilities.browser, cfg!(feature = "browser"));
+        assert!(capabilities.storage);
+
+        #[cfg(feature = "browser")]
+        assert_eq!(capabilities.browser, browser::is_available());
+    }
+
+    #[test]
+    fn test_builder_rejects_malformed_public_key() {
+        let result = Tuish::builder()
+            .product_id("prod_test")
+            .public_key("not-a-public-key")
+            .storage_dir(std::env::temp_dir().to_string_lossy().to_string())
+            .build();
+
+        match result {
+            Err(TuishError::InvalidPublicKey(msg)) => {
+                assert!(msg.contains("SPKI base64"));
+                assert!(msg.contains("64-character hex"));
+            }
+            Err(e) => panic!("expected InvalidPublicKey, got {:?}", e),
+            Ok(_) => panic!("expected build() to fail"),
+        }
+    }
+
+    #[test]
+    fn test_builder_accepts_hex_public_key() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let result = Tuish::builder()
+            .product_id("prod_test")
+            .public_key(TEST_PUBLIC_KEY_HEX)
+            .storage_dir(temp_dir.path().to_string_lossy().to_string())
+            .build();
+
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn test_include_public_key_embeds_fixture() {
+        const KEY: &str = include_public_key!("tests/fixtures/test_key.pub");
+        assert!(KEY.starts_with("-----BEGIN PUBLIC KEY-----"));
+
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let tuish = Tuish::builder()
+            .product_id("prod_test")
+            .public_key(KEY)
+            .storage_dir(temp_dir.path().to_string_lossy().to_string())
+            .build()
+            .unwrap();
+        assert_eq!(
+            license::parse_public_key(&tuish.config().public_key).unwrap(),
+            TEST_PUBLIC_KEY_HEX
+        );
+    }
+
+    #[test]
+    fn test_builder_public_key_from_file() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let fixture =
+            std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/test_key.pub");
+        let builder = Tuish::builder().public_key_from_file(&fixture).unwrap();
+        let tuish = builder
+            .product_id("prod_test")
+            .storage_dir(temp_dir.path().to_string_lossy().to_string())
+            .build()
+            .unwrap();
+        assert_eq!(
+            license::parse_public_key(&tuish.config().public_key).unwrap(),
+            TEST_PUBLIC_KEY_HEX
+        );
+
+        let malformed = temp_dir.path().join("bad.pub");
+        std::fs::write(&malformed, "not-a-public-key\n").unwrap();
+        assert!(matches!(
+            Tuish::builder().public_key_from_file(&malformed),
+            Err(TuishError::InvalidPublicKey(_))
+        ));
+        assert!(matches!(
+            Tuish::builder().public_key_from_file(temp_dir.path().join("missing.pub")),
+            Err(TuishError::StorageError(_))
+        ));
+    }
+
+    #[test]
+    fn test_fingerprint_salt_binds_licenses_per_app() {
+        let salted_mid = get_machine_fingerprint_salted("app-one");
+        let license = test_license(serde_json::json!({ "mid": salted_mid }));
+
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let build = |salt: Option<&str>| {
+            let mut builder = test_builder(&temp_dir);
+            if let Some(salt) = salt {
+                builder = builder.fingerprint_salt(salt);
+            }
+            builder.build().unwrap()
+        };
+
+        let salted = build(Some("app-one"));
+        assert_eq!(salted.machine_fingerprint(), salted_mid);
+        assert!(salted.license_manager().verify_license_key(&license).valid);
+
+        let unsalted = build(None);
+        assert_eq!(unsalted.machine_fingerprint(), get_machine_fingerprint());
+        let result = unsalted.license_manager().verify_license_key(&license);
+        assert_eq!(result.reason, Some(LicenseInvalidReason::MachineMismatch));
+    }
+
+    #[test]
+    fn test_machine_fingerprint_override() {
+        let pinned = "ab".repeat(32);
+        let license = test_license(serde_json::json!({ "mid": pinned }));
+
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let builder = || test_builder(&temp_dir).fingerprint_salt("app-one");
+
+        let real = builder().build().unwrap();
+        let result = real.license_manager().verify_license_key(&license);
+        assert_eq!(result.reason, Some(LicenseInvalidReason::MachineMismatch));
+
+        // The override wins over the salt
+        let pinned_tuish = builder()
+            .machine_fingerprint_override(pinned.to_uppercase())
+            .build()
+            .unwrap();
+        assert_eq!(pinned_tuish.machine_fingerprint(), pinned);
+        assert!(
+            pinned_tuish
+                .license_manager()
+                .verify_license_key(&license)
+                .valid
+        );
+
+        assert!(matches!(
+            builder()
+                .machine_fingerprint_override("not-a-fingerprint")
+                .build(),
+            Err(TuishError::ParseError(_))
+        ));
+    }
+
+    /// Mount mocks for the terminal purchase steps before confirmation
+    #[cfg(feature = "http")]
+    async fn mount_purchase_mocks(server: &wiremock::MockServer) {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, ResponseTemplate};
+
+        let mocks = [
+            (
+                "/v1/auth/login/init",
+                serde_json::json!({ "otpId": "otp_login", "phoneMasked": "***1234", "expiresIn": 300 }),
+            ),
+            (
+                "/v1/auth/login/verify",
+                serde_json::json!({ "identityToken": "idt_test", "licenses": [] }),
+            ),
+            (
+                "/v1/purchase/init",
+                serde_json::json!({
+                    "cards": [{
+                        "id": "card_1", "brand": "visa", "last4": "4242",
+                        "expiryMonth": 12, "expiryYear": 2030
+                    }],
+                    "amount": 1999,
+                    "currency": "usd",
+                    "phoneMasked": "***1234",
+                    "productName": "Test Product"
+                }),
+            ),
+            (
+                "/v1/purchase/otp",
+                serde_json::json!({ "otpId": "otp_purchase", "expiresIn": 300 }),
+            ),
+        ];
+        for (route, body) in mocks {
+            Mock::given(method("POST"))
+                .and(path(route))
+                .respond_with(ResponseTemplate::new(200).set_body_json(body))
+                .expect(1)
+                .mount(server)
+                .await;
+        }
+    }
+
+    const TEST_PUBLIC_KEY_HEX: &str =
+        "cf71e737c27c3be902373e21d47a0a2cb406a4c67d3eeef11fb73b37828d40de";
+
+    /// A license for `prod_test` signed with the test key
+    ///
+    /// `claims` override the defaults: `lic_1`, no features, no expiry and
+    /// no machine binding.
+    fn test_license(claims: serde_json::Value) -> String {
+        let mut payload = serde_json::json!({
+            "lid": "lic_1", "pid": "prod_test", "cid": "cus_1", "did": "dev_1",
+            "features": [], "iat": 0, "exp": null, "mid": null
+        });
+        if let (Some(payload), serde_json::Value::Object(claims)) =
+            (payload.as_object_mut(), claims)
+        {
+            payload.extend(claims);
+        }
+        sign_test_license(payload).0
+    }
+
+    /// Builder for `prod_test` trusting the test key, storing in `temp_dir`
+    fn test_builder(temp_dir: &tempfile::TempDir) -> TuishBuilder {
+        Tuish::builder()
+            .product_id("prod_test")
+            .public_key(test_public_key())
+            .storage_dir(temp_dir.path().to_string_lossy().to_string())
+    }
+
+    /// An offline `Tuish` from `test_builder`, with its own storage dir
+    fn test_tuish() -> (Tuish, tempfile::TempDir) {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        (test_builder(&temp_dir).build().unwrap(), temp_dir)
+    }
+
+    /// A `Tuish` from `test_builder` talking to `server`
+    #[cfg(feature = "http")]
+    fn online_tuish(server: &wiremock::MockServer, temp_dir: &tempfile::TempDir) -> Tuish {
+        test_builder(temp_dir)
+            .api_url(server.uri())
+            .interactive(true)
+            .build()
+            .unwrap()
+    }
+
+    #[cfg(feature = "http")]
+    #[tokio::test]
+    async fn test_preview_purchase_never_confirms() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        mount_purchase_mocks(&server).await;
+        Mock::given(method("POST"))
+            .and(path("/v1/purchase/confirm"))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_json(serde_json::json!({ "success": true })),
+            )
+            .expect(0)
+            .mount(&server)
+            .await;
+
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let mut tuish = online_tuish(&server, &temp_dir);
+
+        let preview = tuish
+            .preview_purchase_in_terminal(
+                "user@example.com",
+                |_| async { "123456".to_string() },
+                |cards, _, _| async move { cards.first().map(|card| card.id.clone()) },
+                |_| async { "654321".to_string() },
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(preview.card_id, "card_1");
+        assert_eq!(preview.purchase.amount, 1999);
+        assert_eq!(preview.purchase.cards.len(), 1);
+        assert!(tuish.get_cached_license_key().is_none());
+
+        // Fails if /v1/purchase/confirm was hit
+        server.verify().await;
+    }
+
+    #[cfg(feature = "http")]
+    #[test]
+    fn test_scripted_otps() {
+        let otp = |s: &str| Some(s.to_string());
+
+        assert_eq!(scripted_otps(true, None, None).unwrap(), None);
+        assert_eq!(
+            scripted_otps(false, otp("111111"), otp("222222")).unwrap(),
+            Some(("111111".to_string(), "222222".to_string()))
+        );
+        assert!(matches!(
+            scripted_otps(false, None, None),
+            Err(TuishError::NotATerminal)
+        ));
+        assert!(matches!(
+            scripted_otps(false, otp("111111"), otp("")),
+            Err(TuishError::NotATerminal)
+        ));
+    }
+
+    #[cfg(feature = "http")]
+    #[tokio::test]
+    async fn test_preview_purchase_without_tty_uses_env_otps() {
+        use wiremock::MockServer;
+
+        let server = MockServer::start().await;
+        mount_purchase_mocks(&server).await;
+
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let mut tuish = test_builder(&temp_dir)
+            .api_url(server.uri())
+            .interactive(false)
+            .build()
+            .unwrap();
+        tuish.otp_env = |name| match name {
+            LOGIN_OTP_ENV => Some("314159".to_string()),
+            PURCHASE_OTP_ENV => Some("271828".to_string()),
+            _ => None,
+        };
+
+        let preview = tuish
+            .preview_purchase_in_terminal(
+                "user@example.com",
+                |_| async { unreachable!("login OTP must come from the environment") },
+                |cards, _, _| async move { cards.first().map(|card| card.id.clone()) },
+                |_| async { unreachable!("purchase OTP must come from the environment") },
+            )
+            .await;
+
+        assert_eq!(preview.unwrap().card_id, "card_1");
+        let requests = server.received_requests().await.unwrap();
+        let verify = requests
+            .iter()
+            .find(|request| request.url.path() == "/v1/auth/login/verify")
+            .unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&verify.body).unwrap();
+        assert_eq!(body["otp"], "314159");
+    }
+
+    #[cfg(feature = "http")]
+    #[tokio::test]
+    async fn test_purchase_requires_action_surfaces_url() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        mount_purchase_mocks(&server).await;
+        Mock::given(method("POST"))
+            .and(path("/v1/purchase/confirm"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "success": false,
+                "requiresAction": true,
+                "actionUrl": "https://3ds.example.com/auth/abc"
+            })))
+            .up_to_n_times(1)
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let mut tuish = online_tuish(&server, &temp_dir);
+
+        let err = tuish
+            .purchase_in_terminal(
+                "user@example.com",
+                |_| async { "123456".to_string() },
+                |cards, _, _| async move { cards.first().map(|card| card.id.clone()) },
+                |_| async { "654321".to_string() },
+            )
+            .await
+            .unwrap_err();
+
+        let pending = match err {
+            TuishError::PurchaseActionRequired(pending) => pending,
+            other => panic!("expected PurchaseActionRequired, got {:?}", other),
+        };
+        assert_eq!(pending.action_url, "https://3ds.example.com/auth/abc");
+        assert_eq!(pending.confirm_request.card_id, "card_1");
+        assert_eq!(pending.confirm_request.otp_id, "otp_purchase");
+
+        // After authentication the continuation re-confirms the purchase
+        Mock::given(method("POST"))
+            .and(path("/v1/purchase/confirm"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "success": false,
+                "error": "card declined"
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        match tuish.confirm_purchase_after_action(&pending).await {
+            Err(TuishError::ApiError { message, .. }) => assert_eq!(message, "card declined"),
+            other => panic!("expected ApiError, got {:?}", other),
+        }
+
+        // The retry is a new operation, so the API must not replay the first response
+        let keys: Vec<_> = server
+            .received_requests()
+            .await
+            .unwrap()
+            .into_iter()
+            .filter(|request| request.url.path() == "/v1/purchase/confirm")
+            .map(|request| request.headers.get(IDEMPOTENCY_KEY_HEADER).cloned().unwrap())
+            .collect();
+        assert_eq!(keys.len(), 2);
+        assert_ne!(keys[0], keys[1]);
+    }
+
+    #[cfg(all(feature = "http", feature = "browser"))]
+    #[tokio::test]
+    async fn test_purchase_falls_back_to_terminal_after_checkout_timeout() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let license = test_license(serde_json::json!({}));
+
+        let server = MockServer::start().await;
+        mount_purchase_mocks(&server).await;
+        Mock::given(method("POST"))
+            .and(path("/v1/checkout/init"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "sessionId": "sess_abandoned",
+                // A missing file rather than a URL, so no browser tab opens
+                "checkoutUrl": "/nonexistent/tuish-test-checkout"
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/v1/checkout/status/sess_abandoned"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(serde_json::json!({ "status": "pending" })),
+            )
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/v1/purchase/confirm"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "success": true,
+                "license": license
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let mut tuish = online_tuish(&server, &temp_dir);
+
+        let outcome = tuish
+            .purchase(PurchaseStrategy::BrowserThenTerminal {
+                poll: CheckoutPollConfig::new()
+                    .with_poll_interval(Duration::from_millis(10))
+                    .with_timeout(Duration::from_millis(100)),
+                prompts: TerminalPrompts::new(
+                    "user@example.com",
+                    |_| async { "123456".to_string() },
+                    |cards, _, _| async move { cards.first().map(|card| card.id.clone()) },
+                    |_| async { "654321".to_string() },
+                ),
+            })
+            .await
+            .unwrap();
+
+        assert_eq!(outcome.method, PurchaseMethod::Terminal);
+        assert!(outcome.result.valid);
+        assert_eq!(
+            tuish.get_cached_license_key().as_deref(),
+            Some(license.as_str())
+        );
+        server.verify().await;
+    }
+
+    /// `X-Request-Id` values of the requests the mock server received
+    #[cfg(feature = "http")]
+    async fn received_request_ids(server: &wiremock::MockServer) -> Vec<Option<String>> {
+        server
+            .received_requests()
+            .await
+            .unwrap()
+            .iter()
+            .map(|request| {
+                request
+                    .headers
+                    .get(REQUEST_ID_HEADER)
+                    .and_then(|value| value.to_str().ok())
+                    .map(String::from)
+            })
+            .collect()
+    }
+
+    #[cfg(feature = "http")]
+    #[tokio::test]
+    async fn test_checkout_options_forward_redirect_urls() {
+        use wiremock::matchers::{body_json, method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/v1/checkout/init"))
+            .and(body_json(serde_json::json!({
+                "productId": "prod_test",
+                "email": "user@example.com",
+                "successUrl": "http://127.0.0.1:8765/done",
+                "cancelUrl": "http://127.0.0.1:8765/cancelled"
+            })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "sessionId": "sess_urls",
+                "checkoutUrl": "https://checkout.tuish.dev/sess_urls"
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let tuish = online_tuish(&server, &temp_dir);
+
+        let options = CheckoutOptions::new()
+            .with_email("user@example.com")
+            .with_success_url("http://127.0.0.1:8765/done")
+            .with_cancel_url("http://127.0.0.1:8765/cancelled");
+        let session = tuish
+            .purchase_in_browser_with_options(options)
+            .await
+            .unwrap();
+        assert_eq!(session.session_id, "sess_urls");
+    }
+
+    #[cfg(feature = "http")]
+    #[tokio::test]
+    async fn test_checkout_flow_shares_request_id() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/v1/checkout/init"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "sessionId": "sess_flow",
+                "checkoutUrl": "https://checkout.tuish.dev/sess_flow"
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/v1/checkout/status/sess_flow"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(serde_json::json!({ "status": "pending" })),
+            )
+            .up_to_n_times(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/v1/checkout/status/sess_flow"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(serde_json::json!({ "status": "expired" })),
+            )
+            .mount(&server)
+            .await;
+
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let mut tuish = online_tuish(&server, &temp_dir);
+
+        let session = tuish.purchase_in_browser(None).await.unwrap();
+        assert!(session.flow_id.starts_with("req_"));
+        tuish
+            .wait_for_checkout_with_options(
+                "sess_flow",
+                Duration::from_millis(10),
+                Duration::from_secs(10),
+            )
+            .await
+            .unwrap();
+
+        let ids = received_request_ids(&server).await;
+        assert_eq!(ids.len(), 3);
+        assert!(ids
+            .iter()
+            .all(|id| id.as_deref() == Some(session.flow_id.as_str())));
+
+        // A second flow gets a different ID
+        let second = tuish.purchase_in_browser(None).await.unwrap();
+        assert_ne!(second.flow_id, session.flow_id);
+    }
+
+    #[cfg(feature = "http")]
+    #[tokio::test]
+    async fn test_checkout_completion_captures_identity_token() {
+        use wiremock::matchers::{header, method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let license = test_license(serde_json::json!({}));
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/v1/checkout/status/sess_idt"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "status": "complete",
+                "license": license,
+                "identityToken": "idt_checkout"
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/v1/purchase/init"))
+            .and(header("Authorization", "Bearer idt_checkout"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "cards": [], "amount": 1999, "currency": "usd",
+                "phoneMasked": "***1234", "productName": "Test"
+            })))
+            .mount(&server)
+            .await;
+
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let mut tuish = online_tuish(&server, &temp_dir);
+        assert!(tuish.client().identity_token().is_none());
+
+        let result = tuish
+            .wait_for_checkout_with_options(
+                "sess_idt",
+                Duration::from_millis(10),
+                Duration::from_secs(10),
+            )
+            .await
+            .unwrap();
+        assert!(result.valid);
+        assert_eq!(tuish.client().identity_token(), Some("idt_checkout"));
+        tuish.client().init_purchase("prod_test").await.unwrap();
+    }
+
+    #[cfg(feature = "http")]
+    #[tokio::test]
+    async fn test_terminal_purchase_shares_request_id() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        mount_purchase_mocks(&server).await;
+        Mock::given(method("POST"))
+            .and(path("/v1/purchase/confirm"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "success": false,
+                "error": "card declined"
+            })))
+            .mount(&server)
+            .await;
+
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let mut tuish = online_tuish(&server, &temp_dir);
+
+        let _ = tuish
+            .purchase_in_terminal(
+                "user@example.com",
+                |_| async { "123456".to_string() },
+                |cards, _, _| async move { cards.first().map(|card| card.id.clone()) },
+                |_| async { "654321".to_string() },
+            )
+            .await;
+
+        let ids = received_request_ids(&server).await;
+        assert_eq!(ids.len(), 5);
+        let flow_id = ids[0].clone().expect("request ID header should be set");
+        assert!(ids.iter().all(|id| id.as_deref() == Some(flow_id.as_str())));
+
+        // The ID is cleared once the flow ends
+        assert!(tuish.client().request_id().is_none());
+    }
+
+    #[cfg(feature = "http")]
+    #[tokio::test]
+    async fn test_wait_for_checkout_cancel_returns_promptly() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/v1/checkout/status/sess_123"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(serde_json::json!({ "status": "pending" })),
+            )
+            .mount(&server)
+            .await;
+
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let mut tuish = online_tuish(&server, &temp_dir);
+
+        let cancel = CancellationToken::new();
+        let trigger = cancel.clone();
+        tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_millis(100)).await;
+            trigger.cancel();
+        });
+
+        let start = std::time::Instant::now();
+        let result = tuish
+            .wait_for_checkout_cancellable(
+                "sess_123",
+                Duration::from_secs(30),
+                Duration::from_secs(600),
+                &cancel,
+            )
+            .await
+            .unwrap();
+
+        assert!(start.elapsed() < Duration::from_secs(5));
+        assert!(!result.valid);
+        assert_eq!(result.reason, Some(LicenseInvalidReason::Cancelled));
+    }
+
+    #[cfg(feature = "http")]
+    #[tokio::test]
+    async fn test_wait_for_checkout_reports_progress() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/v1/checkout/status/sess_123"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(serde_json::json!({ "status": "pending" })),
+            )
+            .up_to_n_times(2)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/v1/checkout/status/sess_123"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(serde_json::json!({ "status": "complete" })),
+            )
+            .mount(&server)
+            .await;
+
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let mut tuish = online_tuish(&server, &temp_dir);
+
+        let mut progress = Vec::new();
+        let result = tuish
+            .wait_for_checkout_with_progress(
+                "sess_123",
+                Duration::from_millis(10),
+                Duration::from_secs(10),
+                |p| progress.push(p),
+            )
+            .await
+            .unwrap();
+
+        // Complete without a license key
+        assert_eq!(result.reason, Some(LicenseInvalidReason::NotFound));
+        assert_eq!(progress.len(), 3);
+        assert_eq!(
+            progress.iter().map(|p| p.attempt).collect::<Vec<_>>(),
+            vec![1, 2, 3]
+        );
+        assert_eq!(
+            progress.iter().map(|p| p.status).collect::<Vec<_>>(),
+            vec![
+                CheckoutStatus::Pending,
+                CheckoutStatus::Pending,
+                CheckoutStatus::Complete
+            ]
+        );
+        assert!(progress.windows(2).all(|w| w[0].elapsed <= w[1].elapsed));
+    }
+
+    #[cfg(feature = "http")]
+    #[tokio::test]
+    async fn test_wait_for_checkout_skips_stalled_poll() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/v1/checkout/status/sess_slow"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(serde_json::json!({ "status": "pending" }))
+                    .set_delay(Duration::from_secs(5)),
+            )
+            .up_to_n_times(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/v1/checkout/status/sess_slow"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(serde_json::json!({ "status": "complete" })),
+            )
+            .mount(&server)
+            .await;
+
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let mut tuish = online_tuish(&server, &temp_dir);
+
+        let config = CheckoutPollConfig::new()
+            .with_poll_interval(Duration::from_millis(10))
+            .with_request_timeout(Duration::from_millis(200))
+            .with_timeout(Duration::from_secs(2));
+        let start = std::time::Instant::now();
+        let result = tuish
+            .wait_for_checkout_with_config("sess_slow", &config)
+            .await
+            .unwrap();
+
+        // Complete without a license key, rather than a timeout
+        assert_eq!(result.reason, Some(LicenseInvalidReason::NotFound));
+        assert!(start.elapsed() < Duration::from_secs(2));
+        assert_eq!(server.received_requests().await.unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_poll_config_default_is_fixed() {
+        let config = CheckoutPollConfig::default();
+        for attempt in 1..=50 {
+            assert_eq!(config.interval_for_attempt(attempt), Duration::from_secs(2));
+        }
+        assert_eq!(config.timeout, Duration::from_secs(600));
+    }
+
+    #[test]
+    fn test_poll_config_backoff_is_capped() {
+        let config = CheckoutPollConfig::new()
+            .with_poll_interval(Duration::from_secs(1))
+            .with_backoff(2.0, Duration::from_secs(10));
+
+        let intervals: Vec<_> = (1..=8).map(|a| config.interval_for_attempt(a)).collect();
+        assert_eq!(intervals[0], Duration::from_secs(1));
+        assert_eq!(intervals[1], Duration::from_secs(2));
+        assert_eq!(intervals[3], Duration::from_secs(8));
+        assert!(intervals[4..].iter().all(|i| *i == Duration::from_secs(10)));
+        assert_eq!(
+            config.interval_for_attempt(u32::MAX),
+            Duration::from_secs(10)
+        );
+    }
+
+    #[test]
+    fn test_poll_config_jitter_stays_in_bounds() {
+        let config = CheckoutPollConfig::new()
+            .with_poll_interval(Duration::from_secs(2))
+            .with_backoff(1.5, Duration::from_secs(20))
+            .with_jitter(0.5);
+
+        let min = Duration::from_secs(1);
+        let max = Duration::from_secs(20);
+        let mut distinct = std::collections::HashSet::new();
+        for attempt in 1..=200 {
+            let interval = config.interval_for_attempt(attempt % 12 + 1);
+            assert!(
+                interval >= min && interval <= max,
+                "{:?} out of bounds",
+                interval
+            );
+            distinct.insert(interval);
+        }
+        assert!(distinct.len() > 1, "jitter should vary intervals");
+
+        // Out-of-range settings are clamped rather than producing bad durations
+        let wild = CheckoutPollConfig::new()
+            .with_backoff(f64::NAN, Duration::ZERO)
+            .with_jitter(7.0);
+        for attempt in 1..=20 {
+            assert!(wild.interval_for_attempt(attempt) <= Duration::from_secs(2));
+        }
+    }
+
+    #[cfg(feature = "http")]
+    #[tokio::test]
+    async fn test_start_trial_saves_trial_license() {
+        use wiremock::matchers::{body_partial_json, header, method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_millis() as i64;
+        let expires_at = now + 14 * 24 * 60 * 60 * 1000;
+        let fingerprint = get_machine_fingerprint();
+        let license = test_license(serde_json::json!({
+            "lid": "lic_trial", "features": [TRIAL_FEATURE], "iat": now, "exp": expires_at,
+            "mid": fingerprint
+        }));
+
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/v1/trials/start"))
+            .and(header("X-API-Key", "test-api-key"))
+            .and(body_partial_json(serde_json::json!({
+                "productId": "prod_test",
+                "machineFingerprint": fingerprint
+            })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "license": license, "expiresAt": expires_at
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let mut tuish = test_builder(&temp_dir)
+            .api_key("test-api-key")
+            .api_url(server.uri())
+            .trial_dir(temp_dir.path().join("trial").to_string_lossy().to_string())
+            .build()
+            .unwrap();
+
+        let result = tuish.start_trial().await.unwrap();
+        assert!(result.valid, "trial should verify: {:?}", result.reason);
+        assert!(result.license.as_ref().unwrap().is_trial());
+        assert_eq!(result.license.unwrap().expires_at, Some(expires_at));
+        assert_eq!(
+            tuish.get_cached_license_key().as_deref(),
+            Some(license.as_str())
+        );
+        assert!(tuish.license_manager().trial_elapsed("prod_test").is_some());
+    }
+
+    #[cfg(feature = "http")]
+    #[tokio::test]
+    async fn test_deactivate_hits_endpoint_and_clears_cache() {
+        use wiremock::matchers::{body_partial_json, header, method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_millis() as i64;
+        let license = test_license(serde_json::json!({ "iat": now }));
+
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/v1/licenses/deactivate"))
+            .and(header("X-API-Key", "test-api-key"))
+            .and(body_partial_json(serde_json::json!({
+                "licenseKey": license,
+                "machineFingerprint": get_machine_fingerprint()
+            })))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(serde_json::json!({ "deactivated": true })),
+            )
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let mut tuish = test_builder(&temp_dir)
+            .api_key("test-api-key")
+            .api_url(server.uri())
+            .build()
+            .unwrap();
+
+        assert!(tuish.save_license(&license).unwrap().valid);
+        assert!(tuish.get_cached_license_key().is_some());
+
+        tuish.deactivate().await.unwrap();
+        assert!(tuish.get_cached_license_key().is_none());
+        server.verify().await;
+
+        // Nothing left to deactivate
+        assert!(matches!(
+            tuish.deactivate().await,
+            Err(TuishError::InvalidLicense(_))
+        ));
+    }
+
+    #[cfg(feature = "http")]
+    #[tokio::test]
+    async fn test_rebind_binds_license_to_current_machine() {
+        use wiremock::matchers::{body_partial_json, method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_millis() as i64;
+        let fingerprint = get_machine_fingerprint();
+        let old_license = test_license(serde_json::json!({ "iat": now, "mid": "old-machine" }));
+        let new_license = test_license(serde_json::json!({ "iat": now, "mid": fingerprint }));
+
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/v1/licenses/rebind"))
+            .and(body_partial_json(serde_json::json!({
+                "licenseKey": old_license,
+                "machineFingerprint": fingerprint
+            })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "license": new_license
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let mut tuish = online_tuish(&server, &temp_dir);
+
+        // The old license is bound elsewhere and fails locally
+        assert!(!tuish.save_license(&old_license).unwrap().valid);
+
+        let result = tuish.rebind(&old_license).await.unwrap();
+        assert!(
+            result.valid,
+            "rebound license should verify: {:?}",
+            result.reason
+        );
+
+        let cached = tuish.get_cached_license_key().unwrap();
+        let payload = extract_license_payload(&cached).unwrap();
+        assert_eq!(payload.mid.as_deref(), Some(fingerprint.as_str()));
+    }
+
+    #[cfg(feature = "http")]
+    #[tokio::test]
+    async fn test_refresh_revocation_list_revokes_cached_license() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let license = test_license(serde_json::json!({ "iat": 1000 }));
+        let (list, _) = sign_test_claims(&serde_json::json!({
+            "productId": "prod_test", "revokedIds": ["lic_1"], "issuedAt": 2000
+        }));
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/v1/products/prod_test/revocations"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "list": list
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let mut tuish = online_tuish(&server, &temp_dir);
+        assert!(tuish.save_license(&license).unwrap().valid);
+
+        let applied = tuish.refresh_revocation_list().await.unwrap();
+        assert!(applied.is_revoked("lic_1"));
+
+        let result = tuish.check_license();
+        assert!(!result.valid);
+        assert_eq!(result.reason, Some(LicenseInvalidReason::Revoked));
+    }
+
+    #[test]
+    fn test_inspect_ignores_signature() {
+        let header = crypto::to_base64url(br#"{"alg":"ed25519","ver":1}"#);
+        let payload = crypto::to_base64url(
+            br#"{"lid":"lic_1","pid":"prod_x","cid":"cus_1","did":"dev_1","features":["pro"],"iat":1000,"exp":2000,"mid":null}"#,
+        );
+        let license = format!(
+            "{}.{}.{}",
+            header,
+            payload,
+            crypto::to_base64url(&[0u8; 64])
+        );
+
+        let details = Tuish::inspect(&license).unwrap();
+        assert_eq!(details.id, "lic_1");
+        assert_eq!(details.product_id, "prod_x");
+        assert_eq!(details.features, vec!["pro"]);
+        assert_eq!(details.status, LicenseStatus::Expired);
+
+        assert!(Tuish::inspect("not-a-license").is_none());
+    }
+
+    #[test]
+    fn test_checkout_session_fields() {
+        let session = CheckoutSession {
+            session_id: "sess_123".to_string(),
+            checkout_url: "https://checkout.tuish.dev/sess_123".to_string(),
+            flow_id: "req_0123456789abcdef".to_string(),
+        };
+
+        assert_eq!(session.session_id, "sess_123");
+        assert!(session.checkout_url.contains("sess_123"));
+    }
+
+    #[cfg(feature = "blocking")]
+    #[test]
+    fn test_wait_for_checkout_blocking() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_millis() as i64;
+        let license = test_license(serde_json::json!({ "iat": now }));
+
+        // The blocking client runs on this thread; the runtime only drives the mock server
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        let server = runtime.block_on(MockServer::start());
+        runtime.block_on(async {
+            Mock::given(method("GET"))
+                .and(path("/v1/checkout/status/sess_blocking"))
+                .respond_with(
+                    ResponseTemplate::new(200)
+                        .set_body_json(serde_json::json!({ "status": "pending" })),
+                )
+                .up_to_n_times(1)
+                .mount(&server)
+                .await;
+            Mock::given(method("GET"))
+                .and(path("/v1/checkout/status/sess_blocking"))
+                .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                    "status": "complete",
+                    "license": license
+                })))
+                .mount(&server)
+                .await;
+        });
+
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let mut tuish = online_tuish(&server, &temp_dir);
+
+        let config = CheckoutPollConfig::new().with_poll_interval(Duration::from_millis(10));
+        let result = tuish
+            .wait_for_checkout_blocking_with_config("sess_blocking", &config)
+            .unwrap();
+        assert!(result.valid);
+        assert_eq!(
+            tuish.get_cached_license_key().as_deref(),
+            Some(license.as_str())
+        );
+
+        // Both polls belong to one flow
+        let ids = runtime.block_on(received_request_ids(&server));
+        assert_eq!(ids.len(), 2);
+        assert!(ids[0].as_deref().is_some_and(|id| id.starts_with("req_")));
+        assert_eq!(ids[0], ids[1]);
+    }
+
+    #[cfg(feature = "blocking")]
+    #[test]
+    fn test_validate_online_blocking() {
+        use wiremock::matchers::{header, method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        let server = runtime.block_on(MockServer::start());
+        runtime.block_on(
+            Mock::given(method("POST"))
+                .and(path("/v1/licenses/validate"))
+                .and(header("X-API-Key", "test-api-key"))
+                .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                    "valid": false,
+                    "reason": "revoked"
+                })))
+                .expect(1)
+                .mount(&server),
+        );
+
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let tuish = test_builder(&temp_dir)
+            .api_key("test-api-key")
+            .api_url(server.uri())
+            .build()
+            .unwrap();
+
+        // No cached key and none given
+        assert!(matches!(
+            tuish.validate_online_blocking(None),
+            Err(TuishError::InvalidLicense(_))
+        ));
+
+        let result = tuish.validate_online_blocking(Some("lic.key.sig")).unwrap();
+        assert!(!result.valid);
+        assert_eq!(result.reason, Some(LicenseInvalidReason::Revoked));
+        assert!(tuish.get_cached_license_key().is_none());
+    }
+
+    #[cfg(feature = "http")]
+    fn stale_cache(temp_dir: &tempfile::TempDir, license_key: &str) {
+        let data = CachedLicenseData {
+            license_key: license_key.into(),
+            cached_at: 0,
+            refresh_at: 0,
+            product_id: "prod_test".to_string(),
+            machine_fingerprint: get_machine_fingerprint(),
+        };
+        LicenseStorage::with_base_dir(temp_dir.path().to_path_buf())
+            .save_license_sync("prod_test", &data)
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_check_all_products() {
+        let valid = test_license(serde_json::json!({ "pid": "prod_core" }));
+        let expired = test_license(serde_json::json!({
+            "lid": "lic_2", "pid": "prod_addon", "exp": 1000
+        }));
+
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let storage = LicenseStorage::with_base_dir(temp_dir.path().to_path_buf());
+        for (product_id, license_key) in [("prod_core", &valid), ("prod_addon", &expired)] {
+            storage
+                .save_license_key_sync(product_id, license_key, &get_machine_fingerprint())
+                .unwrap();
+        }
+
+        let mut tuish = test_builder(&temp_dir)
+            .product_id("prod_core")
+            .build()
+            .unwrap();
+
+        let results = tuish
+            .check_all(&["prod_core", "prod_addon", "prod_missing"])
+            .await;
+        assert_eq!(results.len(), 3);
+
+        let core = &results["prod_core"];
+        assert!(core.valid);
+        assert_eq!(core.source, ResultSource::Cache);
+
+        let addon = &results["prod_addon"];
+        assert!(!addon.valid);
+        assert_eq!(addon.reason, Some(LicenseInvalidReason::Expired));
+        assert_eq!(addon.license.as_ref().unwrap().product_id, "prod_addon");
+
+        assert_eq!(
+            results["prod_missing"].reason,
+            Some(LicenseInvalidReason::NotFound)
+        );
+
+        // Invalid licenses for other products stay cached
+        assert!(storage.exists("prod_addon"));
+    }
+
+    #[cfg(feature = "http")]
+    #[tokio::test]
+    async fn test_check_license_with_network_offline_grace() {
+        let license = test_license(serde_json::json!({}));
+
+        // Nothing listens on port 1, so the refresh fails with a network error
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let mut tuish = test_builder(&temp_dir)
+            .api_url("http://127.0.0.1:1")
+            .build()
+            .unwrap();
+        stale_cache(&temp_dir, &license);
+
+        let result = tuish.check_license_with_network().await.unwrap();
+        assert!(result.valid);
+        assert!(result.stale);
+        assert_eq!(result.warning, Some(LicenseWarning::UsingStaleCache));
+        assert_eq!(result.source, ResultSource::Cache);
+        assert_eq!(result.license.unwrap().id, "lic_1");
+
+        // The stale cache is kept for the next attempt
+        assert_eq!(
+            tuish.get_cached_license_key().as_deref(),
+            Some(license.as_str())
+        );
+    }
+
+    #[cfg(feature = "http")]
+    #[tokio::test]
+    async fn test_offline_only_makes_no_requests() {
+        let license = test_license(serde_json::json!({}));
+
+        // No mocks mounted: any request would be recorded (and fail)
+        let server = wiremock::MockServer::start().await;
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let mut tuish = test_builder(&temp_dir)
+            .api_url(server.uri())
+            .offline_only(true)
+            .build()
+            .unwrap();
+        stale_cache(&temp_dir, &license);
+
+        let result = tuish.check_license_with_network().await.unwrap();
+        assert!(result.valid);
+        assert!(result.stale);
+        assert!(matches!(
+            tuish.refresh_now().await,
+            Err(TuishError::Offline)
+        ));
+        assert!(matches!(
+            tuish.validate_online(None).await,
+            Err(TuishError::Offline)
+        ));
+        assert!(matches!(
+            tuish.purchase_in_browser(None).await,
+            Err(TuishError::Offline)
+        ));
+        assert!(matches!(
+            tuish.client().ping().await,
+            Err(TuishError::Offline)
+        ));
+
+        assert!(server.received_requests().await.unwrap().is_empty());
+    }
+
+    #[cfg(feature = "http")]
+    #[tokio::test]
+    async fn test_check_license_with_network_refreshes_stale_cache() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let license = test_license(serde_json::json!({}));
+
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/v1/licenses/validate"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "valid": false,
+                "reason": "revoked"
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let mut tuish = online_tuish(&server, &temp_dir);
+
+        // A fresh cache is answered offline without contacting the API
+        tuish.save_license(&license).unwrap();
+        let fresh = tuish.check_license_with_network().await.unwrap();
+        assert!(fresh.valid);
+        assert!(!fresh.stale);
+
+        // A stale cache takes the server's answer
+        stale_cache(&temp_dir, &license);
+        let result = tuish.check_license_with_network().await.unwrap();
+        assert!(!result.valid);
+        assert!(!result.stale);
+        assert_eq!(result.reason, Some(LicenseInvalidReason::Revoked));
+        assert_eq!(result.source, ResultSource::Online);
+    }
+
+    #[cfg(feature = "http")]
+    async fn mount_validate(server: &wiremock::MockServer, body: serde_json::Value) {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, ResponseTemplate};
+
+        Mock::given(method("POST"))
+            .and(path("/v1/licenses/validate"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(body))
+            .expect(1)
+            .mount(server)
+            .await;
+    }
+
+    #[cfg(feature = "http")]
+    #[tokio::test]
+    async fn test_refresh_now_updates_fresh_cache() {
+        let license = test_license(serde_json::json!({}));
+
+        let server = wiremock::MockServer::start().await;
+        mount_validate(
+            &server,
+            serde_json::json!({
+                "valid": true,
+                "license": {
+                    "id": "lic_1", "productId": "prod_test", "productName": "Test Product",
+                    "features": ["pro"], "status": "active", "issuedAt": 0, "expiresAt": null
+                }
+            }),
+        )
+        .await;
+
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let mut tuish = online_tuish(&server, &temp_dir);
+        // A fresh cache is still validated online
+        tuish.save_license(&license).unwrap();
+        assert!(!tuish.license_manager().needs_refresh_sync());
+
+        let result = tuish.refresh_now().await.unwrap();
+        assert!(result.valid);
+        assert_eq!(result.source, ResultSource::Online);
+        assert_eq!(
+            result.license.unwrap().product_name.as_deref(),
+            Some("Test Product")
+        );
+        assert_eq!(
+            tuish.get_cached_license_key().as_deref(),
+            Some(license.as_str())
+        );
+
+        // The request identifies the installation
+        let requests = server.received_requests().await.unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&requests[0].body).unwrap();
+        assert_eq!(body["installationId"], tuish.installation_id());
+    }
+
+    #[cfg(feature = "http")]
+    #[tokio::test]
+    async fn test_refresh_now_revoked_clears_cache() {
+        let license = test_license(serde_json::json!({}));
+
+        let server = wiremock::MockServer::start().await;
+        mount_validate(
+            &server,
+            serde_json::json!({ "valid": false, "reason": "revoked" }),
+        )
+        .await;
+
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let mut tuish = online_tuish(&server, &temp_dir);
+        tuish.save_license(&license).unwrap();
+
+        let result = tuish.refresh_now().await.unwrap();
+        assert!(!result.valid);
+        assert_eq!(result.reason, Some(LicenseInvalidReason::Revoked));
+
+        assert!(tuish.get_cached_license_key().is_none());
+        assert_eq!(
+            tuish.check_license().reason,
+            Some(LicenseInvalidReason::NotFound)
+        );
+    }
+
+    #[cfg(feature = "http")]
+    #[tokio::test]
+    async fn test_ensure_licensed_cached_valid() {
+        let license = test_license(serde_json::json!({}));
+
+        let server = wiremock::MockServer::start().await;
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let mut tuish = online_tuish(&server, &temp_dir);
+        tuish.save_license(&license).unwrap();
+
+        let result = tuish.ensure_licensed().await.unwrap();
+        assert!(result.valid);
+        assert!(!result.stale);
+        assert_eq!(result.source, ResultSource::Cache);
+        assert!(server.received_requests().await.unwrap().is_empty());
+    }
+
+    #[cfg(feature = "http")]
+    #[tokio::test]
+    async fn test_ensure_licensed_refreshes_stale_cache() {
+        let license = test_license(serde_json::json!({}));
+
+        let server = wiremock::MockServer::start().await;
+        mount_validate(
+            &server,
+            serde_json::json!({
+                "valid": true,
+                "license": {
+                    "id": "lic_1", "productId": "prod_test", "productName": "Test Product",
+                    "features": [], "status": "active", "issuedAt": 0, "expiresAt": null
+                }
+            }),
+        )
+        .await;
+
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let mut tuish = online_tuish(&server, &temp_dir);
+        stale_cache(&temp_dir, &license);
+
+        let result = tuish.ensure_licensed().await.unwrap();
+        assert!(result.valid);
+        assert!(!result.stale);
+        assert_eq!(result.source, ResultSource::Online);
+        assert!(!tuish.license_manager().needs_refresh_sync());
+    }
+
+    #[cfg(feature = "http")]
+    #[tokio::test]
+    async fn test_ensure_licensed_revoked_clears_cache() {
+        let license = test_license(serde_json::json!({}));
+
+        let server = wiremock::MockServer::start().await;
+        mount_validate(
+            &server,
+            serde_json::json!({ "valid": false, "reason": "revoked" }),
+        )
+        .await;
+
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let mut tuish = online_tuish(&server, &temp_dir);
+        stale_cache(&temp_dir, &license);
+
+        let result = tuish.ensure_licensed().await.unwrap();
+        assert_eq!(result.reason, Some(LicenseInvalidReason::Revoked));
+        assert!(tuish.get_cached_license_key().is_none());
+    }
+
+    #[cfg(feature = "http")]
+    #[tokio::test]
+    async fn test_ensure_licensed_not_found() {
+        let server = wiremock::MockServer::start().await;
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let mut tuish = online_tuish(&server, &temp_dir);
+
+        let result = tuish.ensure_licensed().await.unwrap();
+        assert!(!result.valid);
+        assert_eq!(result.reason, Some(LicenseInvalidReason::NotFound));
+        assert_eq!(result.source, ResultSource::NotFound);
+        assert!(server.received_requests().await.unwrap().is_empty());
+    }
+
+    #[cfg(feature = "http")]
+    #[tokio::test]
+    async fn test_refresh_now_without_cached_license() {
+        let server = wiremock::MockServer::start().await;
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let mut tuish = online_tuish(&server, &temp_dir);
+
+        let err = tuish.refresh_now().await.unwrap_err();
+        assert!(matches!(err, TuishError::InvalidLicense(_)));
+        assert!(server.received_requests().await.unwrap().is_empty());
     }
 }
@@ -14,23 +14,41 @@
 //! 6. If cache stale, mark needs_refresh: true
 
 use base64::Engine;
-use tracing::{debug, info, warn};
-
-use crate::crypto::{extract_license_payload, verify_license};
+use std::collections::HashMap;
+use std::fmt;
+use std::path::Path;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use crate::audit::{AuditAction, AuditEvent, AuditSink, JsonlFileSink};
+use crate::bundle::LicenseBundle;
+use crate::crypto::{
+    extract_license_payload, normalize_license_key, strip_pem, verify_license_with_skew,
+    verify_revocation_list,
+};
 use crate::error::{LicenseInvalidReason, TuishError};
-use crate::fingerprint::get_machine_fingerprint;
+use crate::logging::{debug, info, warn};
+use crate::metrics;
 use crate::storage::LicenseStorage;
+use crate::trial::TrialTracker;
 use crate::types::{
     CachedLicenseData, LicenseCheckResult, LicenseDetails, LicensePayload, LicenseStatus,
-    TuishConfig,
+    MachineMismatch, ResultSource, RevocationList, TuishConfig,
 };
 
+#[cfg(feature = "blocking")]
+use crate::blocking::TuishClientBlocking;
 #[cfg(feature = "http")]
 use crate::client::TuishClient;
 
 /// Ed25519 SPKI header in base64 (for detecting SPKI format)
 const ED25519_SPKI_HEADER: &str = "MCowBQYDK2VwAyEA";
 
+/// How long a successful check is reused before the cache is read and
+/// verified again. Apps calling `check_license` every frame pay for one
+/// signature check per interval instead of one per frame.
+const VERIFY_MEMO_TTL: Duration = Duration::from_secs(1);
+
 /// License manager handles verification and caching.
 ///
 /// Provides offline-first license verification with automatic caching
@@ -62,11 +80,62 @@ pub struct LicenseManager {
     public_key_hex: String,
     /// License storage for caching
     storage: LicenseStorage,
+    /// Trial start markers (None if no location could be determined)
+    trial: Option<TrialTracker>,
+    /// Verified revocation list for this product, if one has been loaded
+    revocation_list: Option<RevocationList>,
     /// HTTP client for online validation
     #[cfg(feature = "http")]
     client: Option<TuishClient>,
     /// Cached machine fingerprint
     machine_fingerprint: Option<String>,
+    /// Callback for license state transitions, if registered
+    on_change: Option<ChangeHook>,
+    /// Receiver of audit events, if auditing is on
+    audit_sink: Option<Box<dyn AuditSink>>,
+    /// Installation ID, read from storage on first use
+    installation_id: std::sync::OnceLock<String>,
+    /// Last valid cached check, reused within `VERIFY_MEMO_TTL`
+    verify_memo: Mutex<Option<VerifyMemo>>,
+}
+
+/// The result of verifying the cached license on one machine
+///
+/// The cached key only changes through this manager's save, clear and
+/// online validation, which all drop the memo, so the key itself isn't
+/// compared on lookup.
+#[derive(Debug)]
+struct VerifyMemo {
+    machine_fingerprint: String,
+    result: LicenseCheckResult,
+    verified_at: Instant,
+}
+
+/// Callback registered with `LicenseManager::on_change`
+struct ChangeHook {
+    callback: Box<dyn Fn(&LicenseCheckResult) + Send + Sync>,
+    /// Validity and reason of the last observed result
+    last: Mutex<Option<(bool, Option<LicenseInvalidReason>)>>,
+}
+
+impl ChangeHook {
+    fn observe(&self, result: &LicenseCheckResult) {
+        let state = (result.valid, result.reason);
+        let previous = self
+            .last
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .replace(state);
+        if previous.is_some_and(|previous| previous != state) {
+            (self.callback)(result);
+        }
+    }
+}
+
+impl fmt::Debug for ChangeHook {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ChangeHook").finish_non_exhaustive()
+    }
 }
 
 impl LicenseManager {
@@ -85,10 +154,34 @@ impl LicenseManager {
             LicenseStorage::with_base_dir(dir.into()).with_debug(config.debug)
         } else {
             LicenseStorage::new()?.with_debug(config.debug)
-        };
+        }
+        .with_history(config.license_history);
 
         let public_key_hex = parse_public_key(&config.public_key)?;
 
+        let trial = match config.trial_dir {
+            Some(ref dir) => Some(TrialTracker::with_dir(dir.into())),
+            None => TrialTracker::new().ok(),
+        };
+
+        let revocation_list = storage
+            .load_revocation_list_sync(&config.product_id)
+            .and_then(
+                |signed| match verify_revocation_list(&signed, &public_key_hex) {
+                    Ok(list) if list.product_id == config.product_id => Some(list),
+                    Ok(_) => None,
+                    Err(e) => {
+                        warn!(error = %e, "Ignoring invalid cached revocation list");
+                        None
+                    }
+                },
+            );
+
+        let audit_sink = config
+            .audit_log
+            .as_ref()
+            .map(|path| Box::new(JsonlFileSink::new(path)) as Box<dyn AuditSink>);
+
         if config.debug {
             debug!(
                 product_id = %config.product_id,
@@ -101,9 +194,15 @@ impl LicenseManager {
             config,
             public_key_hex,
             storage,
+            trial,
+            revocation_list,
             #[cfg(feature = "http")]
             client: None,
             machine_fingerprint: None,
+            on_change: None,
+            audit_sink,
+            installation_id: std::sync::OnceLock::new(),
+            verify_memo: Mutex::new(None),
         })
     }
 
@@ -115,14 +214,109 @@ impl LicenseManager {
         Ok(manager)
     }
 
+    /// Register a callback for license state transitions.
+    ///
+    /// The callback runs whenever a check or online validation produces a
+    /// result whose validity or reason differs from the previous one, e.g.
+    /// when a refresh finds the license revoked. The first result after
+    /// registering only records the starting state. Replaces any earlier
+    /// callback.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use tuish::{LicenseManager, TuishConfig};
+    ///
+    /// # fn example() -> Result<(), tuish::TuishError> {
+    /// let mut manager = LicenseManager::new(TuishConfig::new("prod_123", "key..."))?;
+    /// manager.on_change(Box::new(|result| {
+    ///     if !result.valid {
+    ///         eprintln!("License no longer valid: {:?}", result.reason);
+    ///     }
+    /// }));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn on_change(&mut self, callback: Box<dyn Fn(&LicenseCheckResult) + Send + Sync>) {
+        self.on_change = Some(ChangeHook {
+            callback,
+            last: Mutex::new(None),
+        });
+    }
+
+    /// Record a result for this product and run the change callback if needed
+    fn observe(&self, result: &LicenseCheckResult) {
+        if let Some(hook) = &self.on_change {
+            hook.observe(result);
+        }
+    }
+
+    /// Report every license check, refresh and save to `sink`.
+    ///
+    /// Replaces any earlier sink, including the one created from
+    /// `TuishConfig::audit_log`. See the [`audit`](crate::audit) module.
+    pub fn set_audit_sink(&mut self, sink: Box<dyn AuditSink>) {
+        self.audit_sink = Some(sink);
+    }
+
+    /// Pass a result to the audit sink, if one is set
+    fn audit(&self, action: AuditAction, result: &LicenseCheckResult) {
+        if let Some(sink) = &self.audit_sink {
+            sink.record(&AuditEvent::from_result(
+                action,
+                &self.config.product_id,
+                result,
+            ));
+        }
+    }
+
+    /// The last valid cached result, if it was verified within the TTL for
+    /// the current machine fingerprint
+    fn memoized_check(&mut self) -> Option<LicenseCheckResult> {
+        let machine_fingerprint = self.get_machine_fingerprint().to_string();
+        let memo = self.verify_memo.lock().unwrap_or_else(|e| e.into_inner());
+        memo.as_ref()
+            .filter(|memo| memo.machine_fingerprint == machine_fingerprint)
+            .filter(|memo| memo.verified_at.elapsed() < VERIFY_MEMO_TTL)
+            .map(|memo| memo.result.clone())
+    }
+
+    /// Remember a valid result for the cached license on `machine_fingerprint`
+    fn memoize_check(&self, machine_fingerprint: &str, result: &LicenseCheckResult) {
+        *self.verify_memo.lock().unwrap_or_else(|e| e.into_inner()) = Some(VerifyMemo {
+            machine_fingerprint: machine_fingerprint.to_string(),
+            result: result.clone(),
+            verified_at: Instant::now(),
+        });
+    }
+
+    /// Forget the memoized check after the cache or revocations change
+    fn invalidate_check(&self) {
+        *self.verify_memo.lock().unwrap_or_else(|e| e.into_inner()) = None;
+    }
+
     /// Get the machine fingerprint, caching it for subsequent calls.
     pub fn get_machine_fingerprint(&mut self) -> &str {
         if self.machine_fingerprint.is_none() {
-            self.machine_fingerprint = Some(get_machine_fingerprint());
+            self.machine_fingerprint = Some(self.config.machine_fingerprint());
         }
         self.machine_fingerprint.as_ref().unwrap()
     }
 
+    /// `payload_to_details`, with `TuishConfig::product_name` filled in for
+    /// licenses of this application
+    fn payload_to_details(
+        &self,
+        payload: &LicensePayload,
+        status: LicenseStatus,
+    ) -> LicenseDetails {
+        let mut details = payload_to_details(payload, status);
+        if self.config.accepts_product(&details.product_id) {
+            details.product_name = self.config.product_name.clone();
+        }
+        details
+    }
+
     /// Check if the user has a valid license (async version).
     ///
     /// This is the main entry point for async license verification. It follows
@@ -140,8 +334,22 @@ impl LicenseManager {
     /// - `valid`: Whether the license is currently valid
     /// - `license`: License details if valid or partially valid
     /// - `reason`: Reason for invalid license
-    /// - `offline_verified`: Always true for this method
+    /// - `source`: `Cache`, or `NotFound` if nothing is cached
     pub async fn check_license_async(&mut self) -> Result<LicenseCheckResult, TuishError> {
+        let result = match self.memoized_check() {
+            Some(result) => Ok(result),
+            None => self.check_cached_license_async().await,
+        };
+        let result = result.map(|result| self.with_warning(result));
+        metrics::record_check(result.as_ref());
+        if let Ok(result) = &result {
+            self.observe(result);
+            self.audit(AuditAction::Check, result);
+        }
+        result
+    }
+
+    async fn check_cached_license_async(&mut self) -> Result<LicenseCheckResult, TuishError> {
         let machine_fingerprint = self.get_machine_fingerprint().to_string();
 
         // Try to load cached license
@@ -149,12 +357,18 @@ impl LicenseManager {
 
         match cached {
             Some(cached_data) => {
+                metrics::record_cache_hit();
                 if self.config.debug {
                     debug!("Found cached license, verifying offline");
                 }
 
                 // Verify offline
-                let result = self.verify_offline(&cached_data.license_key, &machine_fingerprint);
+                let result = self
+                    .verify_offline(&cached_data.license_key, &machine_fingerprint)
+                    .map(|result| LicenseCheckResult {
+                        source: ResultSource::Cache,
+                        ..result
+                    });
 
                 match &result {
                     Ok(check_result) if check_result.valid => {
@@ -170,6 +384,7 @@ impl LicenseManager {
                             "License verified successfully"
                         );
 
+                        self.memoize_check(&machine_fingerprint, check_result);
                         Ok(check_result.clone())
                     }
                     Ok(check_result) => {
@@ -200,7 +415,10 @@ impl LicenseManager {
                     valid: false,
                     license: None,
                     reason: Some(LicenseInvalidReason::NotFound),
-                    offline_verified: false,
+                    source: ResultSource::NotFound,
+                    stale: false,
+                    mismatch_detail: None,
+                    warning: None,
                 })
             }
         }
@@ -222,19 +440,42 @@ impl LicenseManager {
     /// - `valid`: Whether the license passed all checks
     /// - `license`: License details (even if expired or invalid)
     /// - `reason`: Specific reason for invalid license
-    /// - `offline_verified`: Always true
+    /// - `source`: Always `Offline`
     pub fn verify_offline(
         &self,
         license_key: &str,
         machine_fingerprint: &str,
     ) -> Result<LicenseCheckResult, TuishError> {
-        match verify_license(license_key, &self.public_key_hex, Some(machine_fingerprint)) {
+        self.verify_offline_for(&self.config.product_id, license_key, machine_fingerprint)
+    }
+
+    /// `verify_offline` against another product sharing this manager's public key.
+    ///
+    /// The revocation list and `accepted_product_ids` only apply to this
+    /// manager's own product.
+    fn verify_offline_for(
+        &self,
+        product_id: &str,
+        license_key: &str,
+        machine_fingerprint: &str,
+    ) -> Result<LicenseCheckResult, TuishError> {
+        match verify_license_with_skew(
+            license_key,
+            &self.public_key_hex,
+            Some(machine_fingerprint),
+            self.config.clock_skew,
+        ) {
             Ok(payload) => {
-                // Check if license is for this product
-                if payload.pid != self.config.product_id {
+                // Check if license is for this product (or an accepted alternate)
+                let accepted = if product_id == self.config.product_id {
+                    self.config.accepts_product(&payload.pid)
+                } else {
+                    payload.pid == product_id
+                };
+                if !accepted {
                     if self.config.debug {
                         debug!(
-                            expected = %self.config.product_id,
+                            expected = %product_id,
                             actual = %payload.pid,
                             "License is for different product"
                         );
@@ -243,55 +484,176 @@ impl LicenseManager {
                         valid: false,
                         license: None,
                         reason: Some(LicenseInvalidReason::InvalidFormat),
-                        offline_verified: true,
+                        source: ResultSource::Offline,
+                        stale: false,
+                        mismatch_detail: None,
+                        warning: None,
                     });
                 }
 
-                let license = payload_to_details(&payload, LicenseStatus::Active);
+                if product_id == self.config.product_id
+                    && self
+                        .revocation_list
+                        .as_ref()
+                        .is_some_and(|list| list.is_revoked(&payload.lid))
+                {
+                    if self.config.debug {
+                        debug!(license_id = %payload.lid, "License is on the revocation list");
+                    }
+                    return Ok(LicenseCheckResult {
+                        valid: false,
+                        license: Some(self.payload_to_details(&payload, LicenseStatus::Revoked)),
+                        reason: Some(LicenseInvalidReason::Revoked),
+                        source: ResultSource::Offline,
+                        stale: false,
+                        mismatch_detail: None,
+                        warning: None,
+                    });
+                }
+
+                let license = self.payload_to_details(&payload, LicenseStatus::Active);
                 Ok(LicenseCheckResult {
                     valid: true,
                     license: Some(license),
                     reason: None,
-                    offline_verified: true,
+                    source: ResultSource::Offline,
+                    stale: false,
+                    mismatch_detail: None,
+                    warning: None,
                 })
             }
             Err(TuishError::ExpiredLicense) => {
                 // Extract payload for display even though expired
                 let license = extract_license_payload(license_key)
-                    .map(|p| payload_to_details(&p, LicenseStatus::Expired));
+                    .map(|p| self.payload_to_details(&p, LicenseStatus::Expired));
 
                 Ok(LicenseCheckResult {
                     valid: false,
                     license,
                     reason: Some(LicenseInvalidReason::Expired),
-                    offline_verified: true,
+                    source: ResultSource::Offline,
+                    stale: false,
+                    mismatch_detail: None,
+                    warning: None,
                 })
             }
-            Err(TuishError::InvalidSignature) => Ok(LicenseCheckResult {
-                valid: false,
-                license: None,
-                reason: Some(LicenseInvalidReason::InvalidSignature),
-                offline_verified: true,
-            }),
-            Err(TuishError::InvalidMachineId) => {
+            Err(TuishError::NotYetValid) => {
                 let license = extract_license_payload(license_key)
-                    .map(|p| payload_to_details(&p, LicenseStatus::Revoked));
+                    .map(|p| self.payload_to_details(&p, LicenseStatus::Active));
+
+                Ok(LicenseCheckResult {
+                    valid: false,
+                    license,
+                    reason: Some(LicenseInvalidReason::NotYetValid),
+                    source: ResultSource::Offline,
+                    stale: false,
+                    mismatch_detail: None,
+                    warning: None,
+                })
+            }
+            Err(TuishError::InvalidMachineId) => {
+                let payload = extract_license_payload(license_key);
+                let license = payload
+                    .as_ref()
+                    .map(|p| self.payload_to_details(p, LicenseStatus::Revoked));
+                let mismatch_detail = payload.map(|p| MachineMismatch {
+                    expected: p
+                        .mid
+                        .or_else(|| p.mids.into_iter().next())
+                        .unwrap_or_default(),
+                    actual: machine_fingerprint.to_string(),
+                });
 
                 Ok(LicenseCheckResult {
                     valid: false,
                     license,
                     reason: Some(LicenseInvalidReason::MachineMismatch),
-                    offline_verified: true,
+                    source: ResultSource::Offline,
+                    stale: false,
+                    mismatch_detail,
+                    warning: None,
                 })
             }
-            Err(TuishError::InvalidLicense(_)) => Ok(LicenseCheckResult {
-                valid: false,
-                license: None,
-                reason: Some(LicenseInvalidReason::InvalidFormat),
-                offline_verified: true,
-            }),
-            Err(e) => Err(e),
+            Err(e) => match LicenseInvalidReason::from_error(&e) {
+                Some(reason) => Ok(LicenseCheckResult {
+                    valid: false,
+                    license: None,
+                    reason: Some(reason),
+                    source: ResultSource::Offline,
+                    stale: false,
+                    mismatch_detail: None,
+                    warning: None,
+                }),
+                None => Err(e),
+            },
+        }
+    }
+
+    /// Check the cached licenses of several products at once (async version).
+    ///
+    /// For apps bundling several products signed with the same key. The
+    /// machine fingerprint is computed once and the cache reads run
+    /// concurrently. Unlike `check_license_async`, invalid cached licenses
+    /// are left in place, since they may belong to other products.
+    ///
+    /// # Returns
+    ///
+    /// A result per product ID. Products whose cache cannot be read are
+    /// reported as `NotFound`, as in `check_license`.
+    pub async fn check_all(&mut self, product_ids: &[&str]) -> HashMap<String, LicenseCheckResult> {
+        let machine_fingerprint = self.get_machine_fingerprint().to_string();
+
+        let mut reads = tokio::task::JoinSet::new();
+        for product_id in product_ids {
+            let storage = self.storage.clone();
+            let product_id = product_id.to_string();
+            reads.spawn(async move {
+                let cached = storage.load_license(&product_id).await;
+                (product_id, cached)
+            });
+        }
+
+        let mut results = HashMap::with_capacity(product_ids.len());
+        while let Some(joined) = reads.join_next().await {
+            let (product_id, cached) = match joined {
+                Ok(read) => read,
+                Err(e) => std::panic::resume_unwind(e.into_panic()),
+            };
+            let result = match cached {
+                Ok(Some(cached_data)) => {
+                    metrics::record_cache_hit();
+                    self.verify_offline_for(
+                        &product_id,
+                        &cached_data.license_key,
+                        &machine_fingerprint,
+                    )
+                    .map(|result| LicenseCheckResult {
+                        source: ResultSource::Cache,
+                        ..result
+                    })
+                    .unwrap_or_else(|e| {
+                        warn!(product_id = %product_id, error = %e, "License verification error");
+                        LicenseCheckResult {
+                            valid: false,
+                            license: None,
+                            reason: Some(LicenseInvalidReason::InvalidFormat),
+                            source: ResultSource::Cache,
+                            stale: false,
+                            mismatch_detail: None,
+                            warning: None,
+                        }
+                    })
+                }
+                Ok(None) => not_found_result(),
+                Err(e) => {
+                    warn!(product_id = %product_id, error = %e, "Failed to load cached license");
+                    not_found_result()
+                }
+            };
+            metrics::record_check(Ok(&result));
+            results.insert(product_id, result);
         }
+        results
     }
 
     /// Save a new license key to the cache (async version).
@@ -307,6 +669,8 @@ impl LicenseManager {
         &mut self,
         license_key: &str,
     ) -> Result<LicenseCheckResult, TuishError> {
+        self.invalidate_check();
+        let license_key = &*normalize_license_key(license_key);
         let machine_fingerprint = self.get_machine_fingerprint().to_string();
 
         // Verify the license first
@@ -328,6 +692,7 @@ impl LicenseManager {
 
     /// Clear the cached license (async version).
     pub async fn clear_license_async(&mut self) -> Result<(), TuishError> {
+        self.invalidate_check();
         self.storage.delete_license(&self.config.product_id).await?;
 
         if self.config.debug {
@@ -358,14 +723,7 @@ impl LicenseManager {
     ///
     /// Returns license details if the format is valid, `None` otherwise.
     pub fn extract_license_info(&self, license_key: &str) -> Option<LicenseDetails> {
-        extract_license_payload(license_key).map(|payload| {
-            let status = if payload.is_expired() {
-                LicenseStatus::Expired
-            } else {
-                LicenseStatus::Active
-            };
-            payload_to_details(&payload, status)
-        })
+        extract_unverified_details(license_key)
     }
 
     /// Check if a cached license needs online refresh.
@@ -401,59 +759,109 @@ impl LicenseManager {
     /// Validate license online via API.
     #[cfg(feature = "http")]
     pub async fn validate_online(&self, license_key: &str) -> Result<LicenseCheckResult, TuishError> {
+        if self.config.offline_only {
+            return Err(TuishError::Offline);
+        }
         let client = self.client.as_ref().ok_or_else(|| {
             TuishError::FeatureNotAvailable("HTTP client not configured".to_string())
         })?;
 
-        let machine_fingerprint = get_machine_fingerprint();
+        let machine_fingerprint = self.config.machine_fingerprint();
         let req = crate::types::LicenseValidateRequest {
             license_key: license_key.to_string(),
             machine_fingerprint: machine_fingerprint.clone(),
+            installation_id: Some(self.installation_id()),
         };
 
-        let response = client.validate_license(req).await?;
+        self.invalidate_check();
+        let started = Instant::now();
+        let response = client.validate_license(req).await;
+        metrics::record_refresh(started.elapsed());
+        let response = response?;
 
         if response.valid {
             // Save the validated license
             self.storage
                 .save_license_key(&self.config.product_id, license_key, &machine_fingerprint)
                 .await?;
+        }
 
-            let details = response.license.map(|info| LicenseDetails {
-                id: info.id,
-                product_id: info.product_id,
-                product_name: Some(info.product_name),
-                features: info.features,
-                status: info.status,
-                issued_at: info.issued_at,
-                expires_at: info.expires_at,
-            });
+        let result = validation_result(response);
+        self.observe(&result);
+        self.audit(AuditAction::Refresh, &result);
+        Ok(result)
+    }
 
-            Ok(LicenseCheckResult {
-                valid: true,
-                license: details,
-                reason: None,
-                offline_verified: false,
-            })
-        } else {
-            let reason = match response.reason {
-                Some(crate::types::ApiValidationReason::Expired) => LicenseInvalidReason::Expired,
-                Some(crate::types::ApiValidationReason::Revoked) => LicenseInvalidReason::Revoked,
-                Some(crate::types::ApiValidationReason::MachineMismatch) => {
-                    LicenseInvalidReason::MachineMismatch
-                }
-                _ => LicenseInvalidReason::NotFound,
-            };
+    /// Check the license, refreshing a stale cache online.
+    ///
+    /// Verifies the cached license offline first. If it is valid but due for
+    /// refresh, validates it online. When the refresh fails with a network
+    /// error, the offline result is returned with `stale: true` so the app
+    /// keeps working until the network is back.
+    #[cfg(feature = "http")]
+    pub async fn check_license_with_network(&mut self) -> Result<LicenseCheckResult, TuishError> {
+        let result = self.check_license();
+        if !result.valid || !self.needs_refresh_sync() {
+            return Ok(result);
+        }
 
-            Ok(LicenseCheckResult {
-                valid: false,
-                license: None,
-                reason: Some(reason),
-                offline_verified: false,
-            })
+        let Some(license_key) = self.get_cached_license_key() else {
+            return Ok(result);
+        };
+
+        match self.validate_online(&license_key).await {
+            Err(TuishError::NetworkError(e)) => {
+                warn!(error = %e, "Online refresh failed, using stale cached license");
+                Ok(stale_result(result))
+            }
+            Err(TuishError::Offline) => {
+                debug!("Offline-only mode, using stale cached license");
+                Ok(stale_result(result))
+            }
+            other => other,
         }
     }
 
+    /// Validate license online via API using a blocking client.
+    ///
+    /// Same behaviour as `validate_online`, for callers without an async runtime.
+    #[cfg(feature = "blocking")]
+    pub fn validate_online_blocking(
+        &self,
+        client: &TuishClientBlocking,
+        license_key: &str,
+    ) -> Result<LicenseCheckResult, TuishError> {
+        if self.config.offline_only {
+            return Err(TuishError::Offline);
+        }
+
+        let machine_fingerprint = self.config.machine_fingerprint();
+        let req = crate::types::LicenseValidateRequest {
+            license_key: license_key.to_string(),
+            machine_fingerprint: machine_fingerprint.clone(),
+            installation_id: Some(self.installation_id()),
+        };
+
+        self.invalidate_check();
+        let started = Instant::now();
+        let response = client.validate_license(req);
+        metrics::record_refresh(started.elapsed());
+        let response = response?;
+
+        if response.valid {
+            self.storage.save_license_key_sync(
+                &self.config.product_id,
+                license_key,
+                &machine_fingerprint,
+            )?;
+        }
+
+        let result = validation_result(response);
+        self.observe(&result);
+        self.audit(AuditAction::Refresh, &result);
+        Ok(result)
+    }
+
     // =========================================================================
     // Synchronous API (for non-async contexts)
     // =========================================================================
@@ -461,7 +869,33 @@ impl LicenseManager {
     /// Check if the user has a valid license.
     ///
     /// This is the primary sync API for license verification.
+    ///
+    /// A valid result is reused for a short time without reading or
+    /// verifying the cache again, so calling this every frame is cheap.
+    /// Saving or clearing the license through this manager resets it.
     pub fn check_license(&mut self) -> LicenseCheckResult {
+        let result = match self.memoized_check() {
+            Some(result) => result,
+            None => self.check_cached_license(),
+        };
+        let result = self.with_warning(result);
+        metrics::record_check(Ok(&result));
+        self.observe(&result);
+        self.audit(AuditAction::Check, &result);
+        result
+    }
+
+    /// Set the expiry or support warning on a valid result.
+    fn with_warning(&self, result: LicenseCheckResult) -> LicenseCheckResult {
+        let warning = result
+            .license
+            .as_ref()
+            .filter(|_| result.valid)
+            .and_then(|license| license.warning(self.config.expiry_warning));
+        LicenseCheckResult { warning, ..result }
+    }
+
+    fn check_cached_license(&mut self) -> LicenseCheckResult {
         let machine_fingerprint = self.get_machine_fingerprint().to_string();
 
         // Try to load cached license
@@ -473,19 +907,33 @@ impl LicenseManager {
                     valid: false,
                     license: None,
                     reason: Some(LicenseInvalidReason::NotFound),
-                    offline_verified: false,
+                    source: ResultSource::NotFound,
+                    stale: false,
+                    mismatch_detail: None,
+                    warning: None,
                 };
             }
         };
 
         match cached {
             Some(cached_data) => {
+                metrics::record_cache_hit();
                 if self.config.debug {
                     debug!("Found cached license, verifying offline");
                 }
 
-                match self.verify_offline(&cached_data.license_key, &machine_fingerprint) {
-                    Ok(result) if result.valid => result,
+                let verified = self
+                    .verify_offline(&cached_data.license_key, &machine_fingerprint)
+                    .map(|result| LicenseCheckResult {
+                        source: ResultSource::Cache,
+                        ..result
+                    });
+
+                match verified {
+                    Ok(result) if result.valid => {
+                        self.memoize_check(&machine_fingerprint, &result);
+                        result
+                    }
                     Ok(result) => {
                         // Remove invalid cached license
                         if let Err(e) = self.storage.delete_license_sync(&self.config.product_id) {
@@ -499,7 +947,10 @@ impl LicenseManager {
                             valid: false,
                             license: None,
                             reason: Some(LicenseInvalidReason::InvalidFormat),
-                            offline_verified: true,
+                            source: ResultSource::Cache,
+                            stale: false,
+                            mismatch_detail: None,
+                            warning: None,
                         }
                     }
                 }
@@ -513,7 +964,10 @@ impl LicenseManager {
                     valid: false,
                     license: None,
                     reason: Some(LicenseInvalidReason::NotFound),
-                    offline_verified: false,
+                    source: ResultSource::NotFound,
+                    stale: false,
+                    mismatch_detail: None,
+                    warning: None,
                 }
             }
         }
@@ -521,6 +975,8 @@ impl LicenseManager {
 
     /// Save a license key.
     pub fn save_license(&mut self, license_key: &str) -> Result<LicenseCheckResult, TuishError> {
+        self.invalidate_check();
+        let license_key = &*normalize_license_key(license_key);
         let machine_fingerprint = self.get_machine_fingerprint().to_string();
         let result = self.verify_offline(license_key, &machine_fingerprint)?;
 
@@ -531,7 +987,7 @@ impl LicenseManager {
                 .unwrap_or(0);
 
             let data = CachedLicenseData {
-                license_key: license_key.to_string(),
+                license_key: license_key.into(),
                 cached_at: now,
                 refresh_at: now + 24 * 60 * 60 * 1000, // 24 hours
                 product_id: self.config.product_id.clone(),
@@ -541,14 +997,71 @@ impl LicenseManager {
             self.storage.save_license_sync(&self.config.product_id, &data)?;
         }
 
+        self.audit(AuditAction::Save, &result);
         Ok(result)
     }
 
+    /// Import a license from armored bundle text (e.g. a `.tuishlic` file).
+    ///
+    /// The enclosed key is verified and saved exactly like `save_license`.
+    pub fn import_bundle(&mut self, bundle: &str) -> Result<LicenseCheckResult, TuishError> {
+        let bundle = LicenseBundle::from_bundle_string(bundle)?;
+        self.save_license(&bundle.license_key)
+    }
+
+    /// Load a license from a file, verify it offline, and cache it.
+    ///
+    /// The file may hold a bare license key or an armored bundle. No network
+    /// access is needed, which suits air-gapped installs.
+    ///
+    /// # Errors
+    ///
+    /// Returns `TuishError::StorageError` if the file cannot be read, or
+    /// `TuishError::InvalidLicense` if a bundle in it is malformed.
+    pub fn load_license_file(&mut self, path: &Path) -> Result<LicenseCheckResult, TuishError> {
+        let contents = std::fs::read_to_string(path).map_err(|e| {
+            TuishError::StorageError(format!(
+                "failed to read license file {}: {}",
+                path.display(),
+                e
+            ))
+        })?;
+
+        if LicenseBundle::is_bundle(&contents) {
+            self.import_bundle(&contents)
+        } else {
+            self.save_license(contents.trim())
+        }
+    }
+
     /// Clear the cached license.
     pub fn clear_license(&mut self) -> Result<(), TuishError> {
+        self.invalidate_check();
         self.storage.delete_license_sync(&self.config.product_id)
     }
 
+    /// Mark the cached license fresh without validating it online.
+    ///
+    /// For when the app already knows the license is still good (e.g. from
+    /// a webhook): `refresh_at` moves to now plus the refresh interval, so
+    /// `needs_refresh_sync` returns `false` again. The key is unchanged.
+    ///
+    /// # Errors
+    ///
+    /// Returns `TuishError::InvalidLicense` if no license is cached, or a
+    /// storage error if the cache cannot be rewritten.
+    pub fn touch_cache(&mut self) -> Result<(), TuishError> {
+        if !self.storage.touch_license_sync(&self.config.product_id)? {
+            return Err(TuishError::InvalidLicense(
+                "no cached license to touch".to_string(),
+            ));
+        }
+        if self.config.debug {
+            debug!("Extended cached license refresh time");
+        }
+        Ok(())
+    }
+
     /// Alias for `check_license` (for backward compatibility).
     #[inline]
     pub fn check_license_sync(&mut self) -> LicenseCheckResult {
@@ -567,11 +1080,131 @@ impl LicenseManager {
         self.clear_license()
     }
 
+    /// Time since a trial started for `product_id` on this machine.
+    ///
+    /// Returns `None` if no trial has been recorded. The marker lives outside
+    /// the license cache, so this survives `clear_license`. Best-effort
+    /// anti-abuse only; see the [`trial`](crate::trial) module.
+    pub fn trial_elapsed(&self, product_id: &str) -> Option<Duration> {
+        let fingerprint = self.current_fingerprint();
+        self.trial.as_ref()?.elapsed(product_id, &fingerprint)
+    }
+
+    /// Record that a trial started for this product on this machine.
+    ///
+    /// Keeps the original start time if one was already recorded. Returns
+    /// the recorded start time (Unix ms).
+    pub fn record_trial_start(&self) -> Result<i64, TuishError> {
+        let trial = self.trial.as_ref().ok_or_else(|| {
+            TuishError::StorageError("no trial marker location available".to_string())
+        })?;
+        trial.record_start(&self.config.product_id, &self.current_fingerprint())
+    }
+
+    /// The verified revocation list currently applied to offline checks.
+    pub fn revocation_list(&self) -> Option<&RevocationList> {
+        self.revocation_list.as_ref()
+    }
+
+    /// Verify and apply a signed revocation list.
+    ///
+    /// The list is cached next to the license so revocations keep applying
+    /// offline. A list older than the one already applied is ignored, so a
+    /// stale list cannot un-revoke a license.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the signature does not verify or the list is for
+    /// a different product.
+    pub fn set_revocation_list(
+        &mut self,
+        signed_list: &str,
+    ) -> Result<&RevocationList, TuishError> {
+        let list = verify_revocation_list(signed_list, &self.public_key_hex)?;
+        if list.product_id != self.config.product_id {
+            return Err(TuishError::InvalidLicense(format!(
+                "revocation list is for product {}",
+                list.product_id
+            )));
+        }
+
+        let is_newer = self
+            .revocation_list
+            .as_ref()
+            .is_none_or(|current| list.issued_at >= current.issued_at);
+        if is_newer {
+            self.storage
+                .save_revocation_list_sync(&self.config.product_id, signed_list.trim())?;
+            info!(revoked = list.revoked_ids.len(), "Applied revocation list");
+            self.revocation_list = Some(list);
+            self.invalidate_check();
+        } else if self.config.debug {
+            debug!("Ignoring revocation list older than the current one");
+        }
+
+        Ok(self.revocation_list.as_ref().unwrap())
+    }
+
+    /// Machine fingerprint, from the cache if already computed.
+    fn current_fingerprint(&self) -> String {
+        self.machine_fingerprint
+            .clone()
+            .unwrap_or_else(|| self.config.machine_fingerprint())
+    }
+
+    /// Stable ID of this installation, for counting installs.
+    ///
+    /// A random UUID created once in the storage directory (see
+    /// `LicenseStorage::installation_id_sync`). Unlike the machine
+    /// fingerprint it survives hostname and username changes, and it is
+    /// sent with online validation requests. If storage is unavailable, a
+    /// fresh ID is used for the life of this manager.
+    pub fn installation_id(&self) -> String {
+        self.installation_id
+            .get_or_init(|| {
+                self.storage.installation_id_sync().unwrap_or_else(|e| {
+                    warn!(error = %e, "Failed to persist installation ID");
+                    crate::storage::new_installation_id()
+                })
+            })
+            .clone()
+    }
+
     /// Get the cached license key (synchronous version).
     pub fn get_cached_license_key(&self) -> Option<String> {
         self.storage.get_license_key_sync(&self.config.product_id)
     }
 
+    /// Whether a license cache file exists for this product.
+    ///
+    /// A cheap hint for choosing a first screen before running
+    /// `check_license`: it only checks the file exists, so the cached
+    /// license may still be invalid, expired or for another machine.
+    pub fn has_cached_license(&self) -> bool {
+        self.storage.exists(&self.config.product_id)
+    }
+
+    /// Time left on the cached license if it expires within `within`.
+    ///
+    /// For "renew soon" reminders. Returns `None` if there is no valid
+    /// cached license, it is perpetual, or it expires later than `within`
+    /// from now.
+    pub fn renewal_due(&self, within: Duration) -> Option<Duration> {
+        let license_key = self.get_cached_license_key()?;
+        let result = self
+            .verify_offline(&license_key, &self.current_fingerprint())
+            .ok()
+            .filter(|result| result.valid)?;
+        let expires_at = result.license?.expires_at?;
+
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_millis() as i64)
+            .unwrap_or(0);
+        let remaining = Duration::from_millis(expires_at.saturating_sub(now).max(0) as u64);
+        (remaining <= within).then_some(remaining)
+    }
+
     /// Check if cache needs refresh (synchronous version).
     pub fn needs_refresh_sync(&self) -> bool {
         self.storage
@@ -586,23 +1219,9 @@ impl LicenseManager {
     ///
     /// This is the main verification method used by the Tuish high-level API.
     pub fn verify_license_key(&self, license_key: &str) -> LicenseCheckResult {
-        let machine_fingerprint = get_machine_fingerprint();
+        let machine_fingerprint = self.config.machine_fingerprint();
 
         match self.verify_offline(license_key, &machine_fingerprint) {
-            Ok(result) if result.valid => {
-                // Check if license is for this product
-                if let Some(ref license) = result.license {
-                    if license.product_id != self.config.product_id {
-                        return LicenseCheckResult {
-                            valid: false,
-                            license: None,
-                            reason: Some(LicenseInvalidReason::InvalidFormat),
-                            offline_verified: true,
-                        };
-                    }
-                }
-                result
-            }
             Ok(result) => result,
             Err(e) => {
                 warn!(error = %e, "License verification error");
@@ -610,13 +1229,43 @@ impl LicenseManager {
                     valid: false,
                     license: None,
                     reason: Some(LicenseInvalidReason::InvalidFormat),
-                    offline_verified: true,
+                    source: ResultSource::Offline,
+                    stale: false,
+                    mismatch_detail: None,
+                    warning: None,
                 }
             }
         }
     }
 }
 
+/// Decode license details without checking the signature.
+///
+/// Status is `Expired` or `Active` based on the expiration claim alone.
+pub(crate) fn extract_unverified_details(license_key: &str) -> Option<LicenseDetails> {
+    extract_license_payload(license_key).map(|payload| {
+        let status = if payload.is_expired() {
+            LicenseStatus::Expired
+        } else {
+            LicenseStatus::Active
+        };
+        payload_to_details(&payload, status)
+    })
+}
+
+/// Result for a product with no usable cached license.
+fn not_found_result() -> LicenseCheckResult {
+    LicenseCheckResult {
+        valid: false,
+        license: None,
+        reason: Some(LicenseInvalidReason::NotFound),
+        source: ResultSource::NotFound,
+        stale: false,
+        mismatch_detail: None,
+        warning: None,
+    }
+}
+
 /// Convert a license payload to license details.
 fn payload_to_details(payload: &LicensePayload, status: LicenseStatus) -> LicenseDetails {
     LicenseDetails {
@@ -627,13 +1276,16 @@ fn payload_to_details(payload: &LicensePayload, status: LicenseStatus) -> Licens
         status,
         issued_at: payload.iat,
         expires_at: payload.exp,
+        support_expires_at: payload.support_exp,
     }
 }
 
-/// Parse a public key from SPKI base64 or hex format.
+/// Parse a public key from SPKI base64, PEM, or hex format.
 ///
 /// Returns the raw 32-byte key as a hex string.
-fn parse_public_key(public_key: &str) -> Result<String, TuishError> {
+pub(crate) fn parse_public_key(public_key: &str) -> Result<String, TuishError> {
+    let public_key = &*strip_pem(public_key);
+
     // Check if it's SPKI base64 format
     if public_key.starts_with(ED25519_SPKI_HEADER) || public_key.starts_with("MCoq") {
         let decoded = base64::engine::general_purpose::STANDARD
@@ -659,14 +1311,78 @@ fn parse_public_key(public_key: &str) -> Result<String, TuishError> {
     }
 
     Err(TuishError::InvalidPublicKey(
-        "expected SPKI base64 (MCow...) or 64-character hex string".to_string(),
+        "expected SPKI base64 (MCow...), a PEM public key, or 64-character hex string".to_string(),
     ))
 }
 
+/// Mark a cached result as stale after a refresh that could not run.
+///
+/// `ExpiringSoon` outranks `UsingStaleCache`; see `LicenseWarning`.
+#[cfg(feature = "http")]
+fn stale_result(result: LicenseCheckResult) -> LicenseCheckResult {
+    let warning = match result.warning {
+        Some(warning @ crate::types::LicenseWarning::ExpiringSoon(_)) => Some(warning),
+        _ => Some(crate::types::LicenseWarning::UsingStaleCache),
+    };
+    LicenseCheckResult {
+        stale: true,
+        warning,
+        ..result
+    }
+}
+
+/// Convert an online validation response into a check result.
+#[cfg(feature = "http")]
+fn validation_result(response: crate::types::LicenseValidateResponse) -> LicenseCheckResult {
+    if response.valid {
+        let details = response.license.map(|info| LicenseDetails {
+            id: info.id,
+            product_id: info.product_id,
+            product_name: Some(info.product_name),
+            features: info.features,
+            status: info.status,
+            issued_at: info.issued_at,
+            expires_at: info.expires_at,
+            support_expires_at: info.support_expires_at,
+        });
+
+        LicenseCheckResult {
+            valid: true,
+            license: details,
+            reason: None,
+            source: ResultSource::Online,
+            stale: false,
+            mismatch_detail: None,
+            warning: None,
+        }
+    } else {
+        let reason = match response.reason {
+            Some(crate::types::ApiValidationReason::Expired) => LicenseInvalidReason::Expired,
+            Some(crate::types::ApiValidationReason::Revoked) => LicenseInvalidReason::Revoked,
+            Some(crate::types::ApiValidationReason::MachineMismatch) => {
+                LicenseInvalidReason::MachineMismatch
+            }
+            _ => LicenseInvalidReason::NotFound,
+        };
+
+        LicenseCheckResult {
+            valid: false,
+            license: None,
+            reason: Some(reason),
+            source: ResultSource::Online,
+            stale: false,
+            mismatch_detail: None,
+            warning: None,
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::crypto::to_base64url;
+    use crate::test_support::{sign_test_claims, sign_test_license};
+    use serde_json::json;
     use tempfile::TempDir;
 
     // Test public key (same as in crypto.rs tests)
@@ -708,6 +1424,17 @@ mod tests {
         format!("{}.{}.{}", header, payload_b64, sig)
     }
 
+    /// A stale `prod_test` cache entry whose license fails verification
+    fn unsigned_cache() -> CachedLicenseData {
+        CachedLicenseData {
+            license_key: create_test_license(false, None).into(),
+            cached_at: 0,
+            refresh_at: 0,
+            product_id: "prod_test".to_string(),
+            machine_fingerprint: String::new(),
+        }
+    }
+
     #[test]
     fn test_parse_public_key_hex() {
         let result = parse_public_key(TEST_PUBLIC_KEY_HEX);
@@ -725,6 +1452,24 @@ mod tests {
         assert!(hex.chars().all(|c| c.is_ascii_hexdigit()));
     }
 
+    #[test]
+    fn test_parse_public_key_pem() {
+        let body = format!(
+            "{}\n{}",
+            &TEST_PUBLIC_KEY_SPKI[..40],
+            &TEST_PUBLIC_KEY_SPKI[40..]
+        );
+        let lf = format!(
+            "-----BEGIN PUBLIC KEY-----\n{}\n-----END PUBLIC KEY-----\n",
+            body
+        );
+        let crlf = lf.replace('\n', "\r\n");
+
+        let expected = parse_public_key(TEST_PUBLIC_KEY_SPKI).unwrap();
+        assert_eq!(parse_public_key(&lf).unwrap(), expected);
+        assert_eq!(parse_public_key(&crlf).unwrap(), expected);
+    }
+
     #[test]
     fn test_parse_public_key_invalid() {
         // Too short
@@ -760,6 +1505,143 @@ mod tests {
         assert_eq!(result.reason, Some(LicenseInvalidReason::NotFound));
     }
 
+    #[cfg(feature = "metrics")]
+    #[test]
+    fn test_check_license_records_metrics() {
+        use metrics_util::debugging::{DebugValue, DebuggingRecorder};
+
+        let temp_dir = TempDir::new().unwrap();
+        let config = create_test_config(&temp_dir);
+        let mut manager = LicenseManager::new(config).unwrap();
+
+        let recorder = DebuggingRecorder::new();
+        let snapshotter = recorder.snapshotter();
+        ::metrics::with_local_recorder(&recorder, || {
+            manager.check_license();
+
+            // Cached, but the fake signature does not verify
+            let data = unsigned_cache();
+            manager
+                .storage
+                .save_license_sync("prod_test", &data)
+                .unwrap();
+            manager.check_license();
+        });
+
+        // Snapshots drain counters, so take a single one
+        let snapshot = snapshotter.snapshot().into_vec();
+        let counter = |name: &str, result: Option<&str>| {
+            snapshot.iter().find_map(|(key, _, _, value)| {
+                let key = key.key();
+                let label = key
+                    .labels()
+                    .find(|l| l.key() == "result")
+                    .map(|l| l.value());
+                match value {
+                    DebugValue::Counter(n) if key.name() == name && label == result => Some(*n),
+                    _ => None,
+                }
+            })
+        };
+        assert_eq!(
+            counter("tuish_license_checks_total", Some("not_found")),
+            Some(1)
+        );
+        assert_eq!(
+            counter("tuish_license_checks_total", Some("invalid_signature")),
+            Some(1)
+        );
+        assert_eq!(counter("tuish_license_checks_total", Some("valid")), None);
+        assert_eq!(counter("tuish_cache_hits_total", None), Some(1));
+    }
+
+    #[test]
+    fn test_on_change_fires_on_transition_only() {
+        use std::sync::{Arc, Mutex};
+
+        let temp_dir = TempDir::new().unwrap();
+        let config = create_test_config(&temp_dir);
+        let mut manager = LicenseManager::new(config).unwrap();
+
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let sink = Arc::clone(&seen);
+        manager.on_change(Box::new(move |result| {
+            sink.lock().unwrap().push(result.reason);
+        }));
+
+        // Starting state, then an unchanged repeat
+        manager.check_license();
+        manager.check_license();
+        assert!(seen.lock().unwrap().is_empty());
+
+        let data = unsigned_cache();
+        manager
+            .storage
+            .save_license_sync("prod_test", &data)
+            .unwrap();
+        manager.check_license();
+        assert_eq!(
+            *seen.lock().unwrap(),
+            vec![Some(LicenseInvalidReason::InvalidSignature)]
+        );
+    }
+
+    #[test]
+    fn test_has_cached_license() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut manager = LicenseManager::new(create_test_config(&temp_dir)).unwrap();
+        assert!(!manager.has_cached_license());
+
+        // Present but badly signed: a hint only, not a valid license
+        let data = unsigned_cache();
+        manager
+            .storage
+            .save_license_sync("prod_test", &data)
+            .unwrap();
+        assert!(manager.has_cached_license());
+        assert!(!manager.check_license().valid);
+    }
+
+    #[test]
+    fn test_audit_log() {
+        let temp_dir = TempDir::new().unwrap();
+        let audit_path = temp_dir.path().join("audit.jsonl");
+        let (license, public_key) = signed_license("lic_audit");
+        let config = TuishConfig::new("prod_test", &public_key)
+            .with_storage_dir(temp_dir.path().join("cache").to_string_lossy().to_string())
+            .with_audit_log(audit_path.to_string_lossy().to_string());
+        let mut manager = LicenseManager::new(config).unwrap();
+
+        manager.check_license();
+        let contents = std::fs::read_to_string(&audit_path).unwrap();
+        let lines: Vec<&str> = contents.lines().collect();
+        assert_eq!(lines.len(), 1);
+
+        let event: serde_json::Value = serde_json::from_str(lines[0]).unwrap();
+        assert!(event["timestamp"].as_i64().unwrap() > 0);
+        assert_eq!(event["action"], "check");
+        assert_eq!(event["productId"], "prod_test");
+        assert_eq!(event["valid"], false);
+        assert_eq!(event["reason"], "not_found");
+        assert_eq!(event["source"], "not_found");
+        assert!(event.get("licenseId").is_none());
+
+        // Saves and later checks append, naming the license but not the key
+        manager.save_license(&license).unwrap();
+        manager.check_license();
+        let contents = std::fs::read_to_string(&audit_path).unwrap();
+        assert!(!contents.contains(&license));
+        let events: Vec<serde_json::Value> = contents
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(events.len(), 3);
+        assert_eq!(events[1]["action"], "save");
+        assert_eq!(events[2]["action"], "check");
+        assert_eq!(events[2]["valid"], true);
+        assert_eq!(events[2]["licenseId"], "lic_audit");
+    }
+
     #[tokio::test]
     async fn test_extract_license_info() {
         let temp_dir = TempDir::new().unwrap();
@@ -852,6 +1734,11 @@ mod tests {
             iat: 1000,
             exp: Some(2000),
             mid: None,
+            mids: vec![],
+            entitlements: vec![],
+            support_exp: None,
+            nbf: None,
+            feature_groups: vec![],
         };
 
         let details = payload_to_details(&payload, LicenseStatus::Active);
@@ -865,6 +1752,28 @@ mod tests {
         assert!(details.product_name.is_none());
     }
 
+    #[test]
+    fn test_offline_details_use_configured_product_name() {
+        let temp_dir = TempDir::new().unwrap();
+        let (license, public_key) = signed_license("lic_named");
+        let config = TuishConfig::new("prod_test", &public_key)
+            .with_storage_dir(temp_dir.path().to_string_lossy().to_string());
+        let manager = LicenseManager::new(config.clone()).unwrap();
+        let result = manager.verify_offline(&license, "fp").unwrap();
+        assert!(result.license.unwrap().product_name.is_none());
+
+        let mut manager = LicenseManager::new(config.with_product_name("Test Product")).unwrap();
+        let result = manager.verify_offline(&license, "fp").unwrap();
+        assert_eq!(
+            result.license.unwrap().product_name.as_deref(),
+            Some("Test Product")
+        );
+
+        manager.save_license(&license).unwrap();
+        let cached = manager.check_license().license.unwrap();
+        assert_eq!(cached.product_name.as_deref(), Some("Test Product"));
+    }
+
     #[test]
     fn test_verify_offline_invalid_format() {
         let temp_dir = TempDir::new().unwrap();
@@ -889,6 +1798,7 @@ mod tests {
         let result = manager.check_license_sync();
         assert!(!result.valid);
         assert_eq!(result.reason, Some(LicenseInvalidReason::NotFound));
+        assert_eq!(result.source, ResultSource::NotFound);
 
         // Clear should work
         assert!(manager.clear_license_sync().is_ok());
@@ -896,4 +1806,422 @@ mod tests {
         // Needs refresh should be false
         assert!(!manager.needs_refresh_sync());
     }
+
+    #[test]
+    fn test_import_bundle() {
+        let temp_dir = TempDir::new().unwrap();
+        let config = create_test_config(&temp_dir);
+        let mut manager = LicenseManager::new(config).unwrap();
+
+        // Well-formed bundle: key is extracted and verified (fake signature fails)
+        let bundle = LicenseBundle::new(create_test_license(false, None))
+            .with_issued_to("alice@example.com")
+            .to_bundle_string();
+        let result = manager.import_bundle(&bundle).unwrap();
+        assert!(!result.valid);
+        assert_eq!(result.reason, Some(LicenseInvalidReason::InvalidSignature));
+        assert!(manager.get_cached_license_key().is_none());
+
+        // Malformed bundle is an error
+        assert!(matches!(
+            manager.import_bundle("not a bundle"),
+            Err(TuishError::InvalidLicense(_))
+        ));
+    }
+
+    #[test]
+    fn test_load_license_file() {
+        let temp_dir = TempDir::new().unwrap();
+        let (license, public_key) = signed_license("lic_file");
+        let config = TuishConfig::new("prod_test", &public_key)
+            .with_storage_dir(temp_dir.path().join("cache").to_string_lossy().to_string());
+        let mut manager = LicenseManager::new(config).unwrap();
+
+        // Bare key with surrounding whitespace
+        let key_path = temp_dir.path().join("license.key");
+        std::fs::write(&key_path, format!("\n  {}\r\n", license)).unwrap();
+        let result = manager.load_license_file(&key_path).unwrap();
+        assert!(result.valid, "bare key should verify: {:?}", result.reason);
+        assert_eq!(
+            manager.get_cached_license_key().as_deref(),
+            Some(license.as_str())
+        );
+
+        // Bundle format
+        manager.clear_license().unwrap();
+        let bundle_path = temp_dir.path().join("license.tuishlic");
+        std::fs::write(
+            &bundle_path,
+            LicenseBundle::new(&license)
+                .with_issued_to("ops@example.com")
+                .to_bundle_string(),
+        )
+        .unwrap();
+        assert!(manager.load_license_file(&bundle_path).unwrap().valid);
+        assert_eq!(
+            manager.get_cached_license_key().as_deref(),
+            Some(license.as_str())
+        );
+
+        // Missing file
+        assert!(matches!(
+            manager.load_license_file(&temp_dir.path().join("missing.key")),
+            Err(TuishError::StorageError(_))
+        ));
+    }
+
+    #[test]
+    fn test_trial_elapsed_survives_cache_deletion() {
+        let temp_dir = TempDir::new().unwrap();
+        let trial_dir = TempDir::new().unwrap();
+        let config = create_test_config(&temp_dir)
+            .with_trial_dir(trial_dir.path().to_string_lossy().to_string());
+        let manager = LicenseManager::new(config.clone()).unwrap();
+
+        // First run: no trial yet
+        assert!(manager.trial_elapsed("prod_test").is_none());
+        let started_at = manager.record_trial_start().unwrap();
+        assert!(manager.trial_elapsed("prod_test").is_some());
+
+        // Wiping the license cache does not reset the trial
+        std::fs::remove_dir_all(temp_dir.path()).unwrap();
+        let manager = LicenseManager::new(config).unwrap();
+        assert!(manager.trial_elapsed("prod_test").is_some());
+        assert_eq!(manager.record_trial_start().unwrap(), started_at);
+    }
+
+    fn signed_license(license_id: &str) -> (String, String) {
+        sign_test_license(
+            json!({"lid":license_id,"pid":"prod_test","cid":"cus_456","did":"dev_789","features":[],"iat":1000,"exp":null,"mid":null}),
+        )
+    }
+
+    fn signed_revocation_list(product_id: &str, revoked: &[&str], issued_at: i64) -> String {
+        let list = RevocationList {
+            product_id: product_id.to_string(),
+            revoked_ids: revoked.iter().map(|id| id.to_string()).collect(),
+            issued_at,
+        };
+        sign_test_claims(&list).0
+    }
+
+    #[test]
+    fn test_touch_cache_resets_staleness() {
+        let temp_dir = TempDir::new().unwrap();
+        let config = create_test_config(&temp_dir);
+        let mut manager = LicenseManager::new(config).unwrap();
+
+        assert!(matches!(
+            manager.touch_cache(),
+            Err(TuishError::InvalidLicense(_))
+        ));
+
+        let stale = CachedLicenseData {
+            machine_fingerprint: "fp".to_string(),
+            ..unsigned_cache()
+        };
+        manager
+            .storage
+            .save_license_sync("prod_test", &stale)
+            .unwrap();
+        assert!(manager.needs_refresh_sync());
+
+        manager.touch_cache().unwrap();
+        assert!(!manager.needs_refresh_sync());
+
+        let touched = manager
+            .storage
+            .load_license_sync("prod_test")
+            .unwrap()
+            .unwrap();
+        assert_eq!(touched.license_key, stale.license_key);
+        assert_eq!(touched.cached_at, 0);
+        assert_eq!(touched.machine_fingerprint, "fp");
+    }
+
+    #[test]
+    fn test_accepted_product_ids() {
+        let temp_dir = TempDir::new().unwrap();
+        let (legacy_license, public_key) = sign_test_license(
+            json!({"lid":"lic_1","pid":"prod_pro_legacy","cid":"cus_456","did":"dev_789","features":[],"iat":1000,"exp":null,"mid":null}),
+        );
+        let (other_license, _) = sign_test_license(
+            json!({"lid":"lic_2","pid":"prod_other","cid":"cus_456","did":"dev_789","features":[],"iat":1000,"exp":null,"mid":null}),
+        );
+        let config = TuishConfig::new("prod_pro", &public_key)
+            .with_storage_dir(temp_dir.path().to_string_lossy().to_string());
+
+        let manager = LicenseManager::new(config.clone()).unwrap();
+        assert!(!manager.verify_offline(&legacy_license, "fp").unwrap().valid);
+
+        let manager =
+            LicenseManager::new(config.with_accepted_product_id("prod_pro_legacy")).unwrap();
+        let result = manager.verify_offline(&legacy_license, "fp").unwrap();
+        assert!(result.valid);
+        assert_eq!(result.license.unwrap().product_id, "prod_pro_legacy");
+
+        let result = manager.verify_license_key(&legacy_license);
+        assert!(result.valid);
+        assert_eq!(result.license.unwrap().product_id, "prod_pro_legacy");
+
+        let result = manager.verify_offline(&other_license, "fp").unwrap();
+        assert!(!result.valid);
+        assert_eq!(result.reason, Some(LicenseInvalidReason::InvalidFormat));
+
+        let result = manager.verify_license_key(&other_license);
+        assert!(!result.valid);
+        assert_eq!(result.reason, Some(LicenseInvalidReason::InvalidFormat));
+    }
+
+    #[test]
+    fn test_not_yet_valid_reason() {
+        let temp_dir = TempDir::new().unwrap();
+        let starts_at = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_millis() as i64
+            + 3_600_000;
+        let (license, public_key) = sign_test_license(
+            json!({"lid":"lic_1","pid":"prod_test","cid":"cus_456","did":"dev_789","features":[],"iat":1000,"exp":null,"mid":null,"nbf":starts_at}),
+        );
+        let config = TuishConfig::new("prod_test", &public_key)
+            .with_storage_dir(temp_dir.path().to_string_lossy().to_string());
+
+        let manager = LicenseManager::new(config.clone()).unwrap();
+        let result = manager.verify_offline(&license, "fp").unwrap();
+        assert!(!result.valid);
+        assert_eq!(result.reason, Some(LicenseInvalidReason::NotYetValid));
+        assert_eq!(result.license.unwrap().id, "lic_1");
+
+        let manager =
+            LicenseManager::new(config.with_clock_skew(Duration::from_secs(2 * 3600))).unwrap();
+        assert!(manager.verify_offline(&license, "fp").unwrap().valid);
+    }
+
+    #[test]
+    fn test_renewal_due() {
+        let temp_dir = TempDir::new().unwrap();
+        let day = Duration::from_secs(86_400);
+        let expires_at = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_millis() as i64
+            + 2 * 86_400_000;
+        let (expiring, public_key) = sign_test_license(
+            json!({"lid":"lic_1","pid":"prod_test","cid":"cus_456","did":"dev_789","features":[],"iat":1000,"exp":expires_at,"mid":null}),
+        );
+        let (perpetual, _) = signed_license("lic_2");
+        let config = TuishConfig::new("prod_test", &public_key)
+            .with_storage_dir(temp_dir.path().to_string_lossy().to_string());
+        let mut manager = LicenseManager::new(config).unwrap();
+
+        assert!(manager.renewal_due(7 * day).is_none());
+
+        manager.save_license(&expiring).unwrap();
+        let remaining = manager.renewal_due(3 * day).unwrap();
+        assert!(remaining > day && remaining <= 2 * day);
+        assert!(manager.renewal_due(day).is_none());
+
+        manager.save_license(&perpetual).unwrap();
+        assert!(manager.renewal_due(365 * day).is_none());
+    }
+
+    #[test]
+    fn test_check_license_warnings() {
+        use crate::types::LicenseWarning;
+
+        let temp_dir = TempDir::new().unwrap();
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_millis() as i64;
+        let (tomorrow, public_key) = sign_test_license(
+            json!({"lid":"lic_1","pid":"prod_test","cid":"cus_456","did":"dev_789","features":[],"iat":1000,"exp":now + 86_400_000,"mid":null}),
+        );
+        let (lapsed, _) = sign_test_license(
+            json!({"lid":"lic_2","pid":"prod_test","cid":"cus_456","did":"dev_789","features":[],"iat":1000,"exp":null,"mid":null,"support_exp":now - 1000}),
+        );
+        let (perpetual, _) = signed_license("lic_3");
+        let config = TuishConfig::new("prod_test", &public_key)
+            .with_storage_dir(temp_dir.path().to_string_lossy().to_string());
+
+        let mut manager = LicenseManager::new(config.clone()).unwrap();
+        manager.save_license(&tomorrow).unwrap();
+        match manager.check_license().warning {
+            Some(LicenseWarning::ExpiringSoon(left)) => {
+                assert!(left > Duration::from_secs(86_000) && left <= Duration::from_secs(86_400));
+            }
+            other => panic!("expected ExpiringSoon, got {:?}", other),
+        }
+
+        manager.save_license(&lapsed).unwrap();
+        assert_eq!(
+            manager.check_license().warning,
+            Some(LicenseWarning::SupportLapsed)
+        );
+        manager.save_license(&perpetual).unwrap();
+        assert_eq!(manager.check_license().warning, None);
+
+        // Below the configured threshold, no nag
+        let mut manager =
+            LicenseManager::new(config.with_expiry_warning(Duration::from_secs(3600))).unwrap();
+        manager.save_license(&tomorrow).unwrap();
+        assert_eq!(manager.check_license().warning, None);
+    }
+
+    #[test]
+    fn test_machine_mismatch_detail() {
+        let temp_dir = TempDir::new().unwrap();
+        let (license, public_key) = sign_test_license(
+            json!({"lid":"lic_1","pid":"prod_test","cid":"cus_456","did":"dev_789","features":[],"iat":1000,"exp":null,"mid":"fp_old"}),
+        );
+        let config = TuishConfig::new("prod_test", &public_key)
+            .with_storage_dir(temp_dir.path().to_string_lossy().to_string());
+        let manager = LicenseManager::new(config).unwrap();
+
+        let result = manager.verify_offline(&license, "fp_new").unwrap();
+        assert_eq!(result.reason, Some(LicenseInvalidReason::MachineMismatch));
+        assert_eq!(
+            result.mismatch_detail,
+            Some(MachineMismatch {
+                expected: "fp_old".to_string(),
+                actual: "fp_new".to_string(),
+            })
+        );
+
+        let result = manager.verify_offline(&license, "fp_old").unwrap();
+        assert!(result.valid);
+        assert!(result.mismatch_detail.is_none());
+    }
+
+    #[test]
+    fn test_revocation_list_rejects_revoked_license() {
+        let temp_dir = TempDir::new().unwrap();
+        let (revoked_license, public_key) = signed_license("lic_revoked");
+        let (good_license, _) = signed_license("lic_good");
+        let config = TuishConfig::new("prod_test", &public_key)
+            .with_storage_dir(temp_dir.path().to_string_lossy().to_string());
+        let mut manager = LicenseManager::new(config.clone()).unwrap();
+
+        assert!(
+            manager
+                .verify_offline(&revoked_license, "fp")
+                .unwrap()
+                .valid
+        );
+
+        manager
+            .set_revocation_list(&signed_revocation_list("prod_test", &["lic_revoked"], 2000))
+            .unwrap();
+
+        let result = manager.verify_offline(&revoked_license, "fp").unwrap();
+        assert!(!result.valid);
+        assert_eq!(result.reason, Some(LicenseInvalidReason::Revoked));
+        assert_eq!(result.license.unwrap().status, LicenseStatus::Revoked);
+        assert!(manager.verify_offline(&good_license, "fp").unwrap().valid);
+
+        // The list is cached and applies on the next run
+        let manager = LicenseManager::new(config).unwrap();
+        assert!(manager.revocation_list().unwrap().is_revoked("lic_revoked"));
+        assert!(
+            !manager
+                .verify_offline(&revoked_license, "fp")
+                .unwrap()
+                .valid
+        );
+    }
+
+    #[test]
+    fn test_check_result_source() {
+        let temp_dir = TempDir::new().unwrap();
+        let (license, public_key) = signed_license("lic_1");
+        let config = TuishConfig::new("prod_test", &public_key)
+            .with_storage_dir(temp_dir.path().to_string_lossy().to_string());
+        let mut manager = LicenseManager::new(config).unwrap();
+
+        let saved = manager.save_license(&license).unwrap();
+        assert_eq!(saved.source, ResultSource::Offline);
+
+        let cached = manager.check_license();
+        assert!(cached.valid);
+        assert_eq!(cached.source, ResultSource::Cache);
+        assert!(cached.offline_verified());
+    }
+
+    #[test]
+    fn test_check_license_memoizes_within_ttl() {
+        let temp_dir = TempDir::new().unwrap();
+        let (license, public_key) = signed_license("lic_memo");
+        let config = TuishConfig::new("prod_test", &public_key)
+            .with_storage_dir(temp_dir.path().to_string_lossy().to_string());
+        let mut manager = LicenseManager::new(config).unwrap();
+        manager.save_license(&license).unwrap();
+        assert!(manager.check_license().valid);
+
+        // Replace the cache behind the manager's back: a re-read would see a
+        // bad signature and delete the file
+        let tampered = unsigned_cache();
+        manager
+            .storage
+            .save_license_sync("prod_test", &tampered)
+            .unwrap();
+        let memoized = manager.check_license();
+        assert!(memoized.valid);
+        assert_eq!(memoized.source, ResultSource::Cache);
+        assert!(manager.has_cached_license());
+
+        // Once the TTL has passed the cache is read and verified again
+        manager
+            .verify_memo
+            .lock()
+            .unwrap()
+            .as_mut()
+            .unwrap()
+            .verified_at -= VERIFY_MEMO_TTL;
+        let result = manager.check_license();
+        assert_eq!(result.reason, Some(LicenseInvalidReason::InvalidSignature));
+        assert!(!manager.has_cached_license());
+
+        // Saving and clearing drop the memo immediately
+        manager.save_license(&license).unwrap();
+        assert!(manager.check_license().valid);
+        manager.clear_license().unwrap();
+        assert_eq!(
+            manager.check_license().reason,
+            Some(LicenseInvalidReason::NotFound)
+        );
+    }
+
+    #[test]
+    fn test_set_revocation_list_validation() {
+        let temp_dir = TempDir::new().unwrap();
+        let (_, public_key) = signed_license("lic_1");
+        let config = TuishConfig::new("prod_test", &public_key)
+            .with_storage_dir(temp_dir.path().to_string_lossy().to_string());
+        let mut manager = LicenseManager::new(config).unwrap();
+
+        // Wrong product
+        assert!(manager
+            .set_revocation_list(&signed_revocation_list("prod_other", &["lic_1"], 2000))
+            .is_err());
+
+        // Signed by a different key
+        let mut forged = signed_revocation_list("prod_test", &["lic_1"], 2000);
+        forged.replace_range(forged.rfind('.').unwrap() + 1.., &to_base64url(&[0u8; 64]));
+        assert!(matches!(
+            manager.set_revocation_list(&forged),
+            Err(TuishError::InvalidSignature)
+        ));
+        assert!(manager.revocation_list().is_none());
+
+        // An older list does not replace a newer one
+        manager
+            .set_revocation_list(&signed_revocation_list("prod_test", &["lic_1"], 2000))
+            .unwrap();
+        let applied = manager
+            .set_revocation_list(&signed_revocation_list("prod_test", &[], 1000))
+            .unwrap();
+        assert_eq!(applied.issued_at, 2000);
+        assert!(applied.is_revoked("lic_1"));
+    }
 }
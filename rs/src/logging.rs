@@ -0,0 +1,64 @@
+//! Logging shim
+//!
+//! Re-exports the `tracing` macros when the `tracing` feature is enabled.
+//! Without it, the log macros expand to nothing and spans are zero-sized
+//! stubs, so `tracing` is not compiled in at all.
+
+#[cfg(feature = "tracing")]
+#[allow(unused_imports)]
+pub(crate) use tracing::{debug, info, info_span, trace, warn, Instrument};
+
+#[cfg(not(feature = "tracing"))]
+pub(crate) use disabled::*;
+
+#[cfg(not(feature = "tracing"))]
+#[allow(unused_macros, unused_imports, dead_code)]
+mod disabled {
+    macro_rules! disabled_debug {
+        ($($arg:tt)*) => {};
+    }
+
+    macro_rules! disabled_info {
+        ($($arg:tt)*) => {};
+    }
+
+    macro_rules! disabled_trace {
+        ($($arg:tt)*) => {};
+    }
+
+    macro_rules! disabled_warn {
+        ($($arg:tt)*) => {};
+    }
+
+    macro_rules! disabled_info_span {
+        ($($arg:tt)*) => {
+            $crate::logging::Span
+        };
+    }
+
+    // Renamed on export: `warn` would clash with the builtin attribute here
+    pub(crate) use {
+        disabled_debug as debug, disabled_info as info, disabled_info_span as info_span,
+        disabled_trace as trace, disabled_warn as warn,
+    };
+
+    /// Stand-in for `tracing::Span`
+    #[derive(Debug, Clone)]
+    pub(crate) struct Span;
+
+    impl Span {
+        /// Stand-in for `tracing::Span::entered`
+        pub(crate) fn entered(self) -> Self {
+            self
+        }
+    }
+
+    /// Stand-in for `tracing::Instrument`
+    pub(crate) trait Instrument: Sized {
+        fn instrument(self, _span: Span) -> Self {
+            self
+        }
+    }
+
+    impl<T> Instrument for T {}
+}
@@ -0,0 +1,67 @@
+//! Metrics shim
+//!
+//! Records license-check metrics through the `metrics` facade when the
+//! `metrics` feature is enabled; install any recorder (e.g.
+//! `metrics-exporter-prometheus`) to collect them. Without the feature the
+//! record functions are empty and `metrics` is not a dependency.
+//!
+//! | Name | Kind | Labels |
+//! |------|------|--------|
+//! | `tuish_license_checks_total` | counter | `result`: `valid`, a `LicenseInvalidReason`, or `error` |
+//! | `tuish_refresh_duration_seconds` | histogram | |
+//! | `tuish_cache_hits_total` | counter | |
+
+#[cfg(feature = "metrics")]
+pub(crate) use enabled::*;
+
+#[cfg(not(feature = "metrics"))]
+pub(crate) use disabled::*;
+
+#[cfg(feature = "metrics")]
+mod enabled {
+    use crate::error::TuishError;
+    use crate::types::LicenseCheckResult;
+
+    pub(crate) const LICENSE_CHECKS_TOTAL: &str = "tuish_license_checks_total";
+    #[cfg(feature = "http")]
+    pub(crate) const REFRESH_DURATION_SECONDS: &str = "tuish_refresh_duration_seconds";
+    pub(crate) const CACHE_HITS_TOTAL: &str = "tuish_cache_hits_total";
+
+    /// Count a completed license check by outcome
+    pub(crate) fn record_check(result: Result<&LicenseCheckResult, &TuishError>) {
+        let label = match result {
+            Ok(result) if result.valid => "valid".to_string(),
+            Ok(result) => result
+                .reason
+                .map_or_else(|| "invalid".to_string(), |reason| reason.to_string()),
+            Err(_) => "error".to_string(),
+        };
+        ::metrics::counter!(LICENSE_CHECKS_TOTAL, "result" => label).increment(1);
+    }
+
+    /// Record how long an online refresh took
+    #[cfg(feature = "http")]
+    pub(crate) fn record_refresh(elapsed: std::time::Duration) {
+        ::metrics::histogram!(REFRESH_DURATION_SECONDS).record(elapsed.as_secs_f64());
+    }
+
+    /// Count a check that found a cached license
+    pub(crate) fn record_cache_hit() {
+        ::metrics::counter!(CACHE_HITS_TOTAL).increment(1);
+    }
+}
+
+#[cfg(not(feature = "metrics"))]
+#[allow(dead_code)]
+mod disabled {
+    use std::time::Duration;
+
+    use crate::error::TuishError;
+    use crate::types::LicenseCheckResult;
+
+    pub(crate) fn record_check(_result: Result<&LicenseCheckResult, &TuishError>) {}
+
+    pub(crate) fn record_refresh(_elapsed: Duration) {}
+
+    pub(crate) fn record_cache_hit() {}
+}
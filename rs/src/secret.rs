@@ -0,0 +1,158 @@
+//! Strings holding secrets
+//!
+//! [`SecretString`] holds license keys and identity tokens. With the
+//! `zeroize` feature its buffer is overwritten with zeros when it drops, so
+//! the secret doesn't linger in freed memory. Without the feature it
+//! behaves like a plain `String`.
+//!
+//! It serializes as a bare string, so cache files and API payloads are
+//! unchanged.
+
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::ops::Deref;
+
+/// A `String` that is wiped on drop when the `zeroize` feature is enabled
+///
+/// Dereferences to `str`. Copies made with `to_string` or `into_inner` are
+/// ordinary strings and are not wiped.
+#[derive(Clone, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct SecretString(String);
+
+impl SecretString {
+    /// Wrap `secret`
+    pub fn new(secret: impl Into<String>) -> Self {
+        Self(secret.into())
+    }
+
+    /// The secret as a string slice
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// Unwrap into a plain `String`, which is no longer wiped on drop
+    pub fn into_inner(mut self) -> String {
+        std::mem::take(&mut self.0)
+    }
+}
+
+impl Deref for SecretString {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl AsRef<str> for SecretString {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl From<String> for SecretString {
+    fn from(secret: String) -> Self {
+        Self(secret)
+    }
+}
+
+impl From<&str> for SecretString {
+    fn from(secret: &str) -> Self {
+        Self(secret.to_string())
+    }
+}
+
+impl PartialEq<str> for SecretString {
+    fn eq(&self, other: &str) -> bool {
+        self.0 == other
+    }
+}
+
+impl PartialEq<&str> for SecretString {
+    fn eq(&self, other: &&str) -> bool {
+        self.0 == *other
+    }
+}
+
+impl PartialEq<String> for SecretString {
+    fn eq(&self, other: &String) -> bool {
+        &self.0 == other
+    }
+}
+
+impl PartialEq<SecretString> for String {
+    fn eq(&self, other: &SecretString) -> bool {
+        *self == other.0
+    }
+}
+
+impl PartialEq<SecretString> for &str {
+    fn eq(&self, other: &SecretString) -> bool {
+        *self == other.0
+    }
+}
+
+impl fmt::Debug for SecretString {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&self.0, f)
+    }
+}
+
+impl fmt::Display for SecretString {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.0, f)
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl zeroize::Zeroize for SecretString {
+    fn zeroize(&mut self) {
+        self.0.zeroize();
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl Drop for SecretString {
+    fn drop(&mut self) {
+        zeroize::Zeroize::zeroize(self);
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl zeroize::ZeroizeOnDrop for SecretString {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_serializes_as_plain_string() {
+        let secret = SecretString::from("eyJ.key.sig");
+        assert_eq!(serde_json::to_string(&secret).unwrap(), "\"eyJ.key.sig\"");
+
+        let parsed: SecretString = serde_json::from_str("\"eyJ.key.sig\"").unwrap();
+        assert_eq!(parsed, secret);
+        assert_eq!(parsed, "eyJ.key.sig");
+        assert_eq!(parsed.len(), 11);
+        assert_eq!(parsed.into_inner(), "eyJ.key.sig");
+    }
+
+    #[cfg(feature = "zeroize")]
+    #[test]
+    fn test_wiped_on_drop() {
+        use zeroize::{Zeroize, ZeroizeOnDrop};
+
+        fn assert_zeroize_on_drop<T: ZeroizeOnDrop>(_: &T) {}
+
+        let mut secret = SecretString::new("eyJ.key.sig");
+        assert_zeroize_on_drop(&secret);
+        secret.zeroize();
+        assert_eq!(secret, "");
+
+        let secret = SecretString::new("eyJ.key.sig");
+        let copy = secret.clone().into_inner();
+        drop(secret);
+        assert_eq!(copy, "eyJ.key.sig");
+    }
+}
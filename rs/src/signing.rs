@@ -0,0 +1,169 @@
+//! License signing for tests and fixtures
+//!
+//! Generates Ed25519 key pairs and signs license payloads in the
+//! `header.payload.signature` format that `verify_license` accepts, so
+//! conformance tests and downstream crates can build their own fixtures
+//! instead of sharing one hardcoded key. Production licenses are signed by
+//! the Tuish API; never ship a signing key in an application.
+//!
+//! # Example
+//!
+//! ```rust
+//! use tuish::signing::{generate_keypair, sign_license};
+//! use tuish::{verify_license, LicensePayload};
+//!
+//! # fn main() -> Result<(), tuish::TuishError> {
+//! let (signing_key, public_key) = generate_keypair(Some([7u8; 32]));
+//! let payload = LicensePayload {
+//!     lid: "lic_1".to_string(),
+//!     pid: "prod_1".to_string(),
+//!     cid: "cus_1".to_string(),
+//!     did: "dev_1".to_string(),
+//!     features: vec!["pro".to_string()],
+//!     iat: 0,
+//!     exp: None,
+//!     mid: None,
+//!     mids: Vec::new(),
+//!     entitlements: Vec::new(),
+//!     support_exp: None,
+//!     nbf: None,
+//!     feature_groups: Vec::new(),
+//! };
+//!
+//! let license = sign_license(&payload, &signing_key)?;
+//! assert_eq!(verify_license(&license, &public_key, None)?, payload);
+//! # Ok(())
+//! # }
+//! ```
+
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use ed25519_dalek::{Signer, SigningKey};
+use serde::Serialize;
+
+use crate::crypto::{bytes_to_hex, hex_to_bytes, to_base64url};
+use crate::error::TuishError;
+use crate::types::{LicenseHeader, LicensePayload};
+
+/// Ed25519 secret key (the 32-byte seed) as 64 hex characters
+pub type SigningKeyHex = String;
+
+/// Ed25519 public key as SPKI base64 (`MCowBQYDK2VwAyEA...`)
+pub type VerifyingKeySpki = String;
+
+/// DER prefix of an Ed25519 SubjectPublicKeyInfo, followed by the raw key
+const SPKI_ED25519_HEADER: [u8; 12] = [
+    0x30, 0x2a, 0x30, 0x05, 0x06, 0x03, 0x2b, 0x65, 0x70, 0x03, 0x21, 0x00,
+];
+
+/// Generate an Ed25519 key pair.
+///
+/// The same `seed` always yields the same pair, which keeps spec vectors
+/// reproducible. With `None` the seed comes from the OS random number
+/// generator.
+///
+/// # Panics
+///
+/// Panics if `seed` is `None` and the OS random number generator fails.
+pub fn generate_keypair(seed: Option<[u8; 32]>) -> (SigningKeyHex, VerifyingKeySpki) {
+    let seed = seed.unwrap_or_else(|| {
+        let mut seed = [0u8; 32];
+        getrandom::getrandom(&mut seed).expect("OS random number generator failed");
+        seed
+    });
+    let key = SigningKey::from_bytes(&seed);
+
+    let mut spki = SPKI_ED25519_HEADER.to_vec();
+    spki.extend_from_slice(key.verifying_key().as_bytes());
+    (bytes_to_hex(&seed), STANDARD.encode(spki))
+}
+
+/// Sign `payload` as a license string.
+///
+/// # Arguments
+///
+/// * `payload` - The license claims to sign
+/// * `signing_key` - Secret key as 64 hex characters, e.g. from `generate_keypair`
+///
+/// # Errors
+///
+/// Returns `TuishError::ParseError` if `signing_key` is not 64 hex characters.
+pub fn sign_license(payload: &LicensePayload, signing_key: &str) -> Result<String, TuishError> {
+    sign_claims(payload, signing_key)
+}
+
+/// Sign any JSON `claims` in the license format, e.g. a revocation list.
+pub(crate) fn sign_claims<T: Serialize>(
+    claims: &T,
+    signing_key: &str,
+) -> Result<String, TuishError> {
+    let seed: [u8; 32] = hex_to_bytes(signing_key)?
+        .try_into()
+        .map_err(|_| TuishError::ParseError("signing key must be 64 hex characters".to_string()))?;
+    let key = SigningKey::from_bytes(&seed);
+
+    let header = to_base64url(&serde_json::to_vec(&LicenseHeader::default())?);
+    let message = format!("{}.{}", header, to_base64url(&serde_json::to_vec(claims)?));
+    let signature = to_base64url(&key.sign(message.as_bytes()).to_bytes());
+    Ok(format!("{}.{}", message, signature))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::crypto::verify_license;
+
+    fn test_payload() -> LicensePayload {
+        LicensePayload {
+            lid: "lic_1".to_string(),
+            pid: "prod_1".to_string(),
+            cid: "cus_1".to_string(),
+            did: "dev_1".to_string(),
+            features: vec!["pro".to_string()],
+            iat: 1000,
+            exp: None,
+            mid: Some("fp_a".to_string()),
+            mids: Vec::new(),
+            entitlements: Vec::new(),
+            support_exp: None,
+            nbf: None,
+            feature_groups: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_generate_keypair_is_deterministic() {
+        let (secret, public) = generate_keypair(Some([3u8; 32]));
+        assert_eq!(
+            generate_keypair(Some([3u8; 32])),
+            (secret.clone(), public.clone())
+        );
+        assert_ne!(generate_keypair(Some([4u8; 32])).1, public);
+
+        assert_eq!(secret, "03".repeat(32));
+        assert!(public.starts_with("MCowBQYDK2VwAyEA"));
+        assert_eq!(public.len(), 60);
+
+        // Unseeded pairs differ
+        assert_ne!(generate_keypair(None), generate_keypair(None));
+    }
+
+    #[test]
+    fn test_sign_license_round_trips() {
+        let (secret, public) = generate_keypair(Some([5u8; 32]));
+        let license = sign_license(&test_payload(), &secret).unwrap();
+
+        assert_eq!(
+            verify_license(&license, &public, Some("fp_a")).unwrap(),
+            test_payload()
+        );
+        assert!(matches!(
+            verify_license(&license, &generate_keypair(Some([6u8; 32])).1, None),
+            Err(TuishError::InvalidSignature)
+        ));
+        assert!(matches!(
+            sign_license(&test_payload(), "abcd"),
+            Err(TuishError::ParseError(_))
+        ));
+    }
+}
@@ -8,25 +8,133 @@
 //! - Cache directory: `~/.tuish/licenses/`
 //! - File naming: First 16 hex chars of SHA256(product_id) + `.json`
 //! - Cache refresh: 24 hours
-
+//!
+//! # Storage directory
+//!
+//! `LicenseStorage::new` (and so `Tuish::builder()` without `storage_dir`)
+//! picks the first of:
+//!
+//! 1. The `TUISH_STORAGE_DIR` environment variable, if set and non-empty
+//! 2. `~/.tuish/licenses/`
+//! 3. `tuish/licenses/` in the system temp dir, when there is no home
+//!    directory (e.g. some containers and CI runners). Licenses cached here
+//!    may not survive a reboot.
+//!
+//! # History
+//!
+//! With `with_history(true)`, saving a different license key for a product
+//! first moves the existing entry to `<hash>.prev.json`, keeping one
+//! generation; read it back with `load_previous`. This is a safety net for
+//! e.g. a bad renewal, not an audit log.
+//!
+//! # Permissions
+//!
+//! On Unix the storage directory is created with mode `0700` and new cache
+//! files with mode `0600`, so other local users cannot read license keys.
+//! Turn this off with `with_strict_permissions(false)` to use the process
+//! umask instead. Files that already exist keep their mode.
+//!
+//! # Integrity
+//!
+//! Each cache file carries a `checksum` field: an HMAC-SHA256 of the record
+//! keyed by this machine's fingerprint. A record whose checksum is missing
+//! or does not match (e.g. a hand-edited `refreshAt`) still loads, but with
+//! `refresh_at` reset so it is due for online re-validation. This stops
+//! casual edits from dodging refresh and revocation; it is not a defence
+//! against someone who reads this source.
+
+use hmac::{Hmac, Mac};
+use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
-use std::path::PathBuf;
+use std::ffi::OsString;
+use std::path::{Path, PathBuf};
 use tokio::fs;
 use tokio::io::{AsyncReadExt, AsyncWriteExt};
-use tracing::{debug, trace, warn};
 
 use crate::error::TuishError;
+use crate::logging::{debug, trace, warn};
 use crate::types::CachedLicenseData;
 
 /// Default storage directory: ~/.tuish/licenses/
 const DEFAULT_STORAGE_SUBDIR: &str = ".tuish/licenses";
 
+/// Storage directory under the system temp dir when there is no home directory
+const TEMP_STORAGE_SUBDIR: &str = "tuish/licenses";
+
+/// Environment variable that overrides the default storage directory
+pub const STORAGE_DIR_ENV: &str = "TUISH_STORAGE_DIR";
+
 /// Cache refresh interval in hours (matches TypeScript SDK)
 const CACHE_REFRESH_HOURS: i64 = 24;
 
 /// Cache refresh interval in milliseconds
 const CACHE_REFRESH_MS: i64 = CACHE_REFRESH_HOURS * 60 * 60 * 1000;
 
+/// Suffix of the file holding the license a save replaced
+const PREVIOUS_SUFFIX: &str = ".prev.json";
+
+/// File extension for cached signed revocation lists
+const REVOCATION_LIST_EXTENSION: &str = "revocations";
+
+/// File holding this installation's ID
+const INSTALLATION_ID_FILE: &str = "install.id";
+
+/// Domain separator for cache checksums
+const CACHE_CHECKSUM_CONTEXT: &str = "tuish-cache-v1";
+
+/// Mode of the storage directory with strict permissions
+#[cfg(unix)]
+const STRICT_DIR_MODE: u32 = 0o700;
+
+/// Mode of new cache files with strict permissions
+#[cfg(unix)]
+const STRICT_FILE_MODE: u32 = 0o600;
+
+/// A cache entry as written by the TypeScript SDK.
+///
+/// Every field is optional and unknown fields are ignored, so files from
+/// older or newer TS SDK versions still import.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+struct ImportedCacheEntry {
+    license_key: Option<String>,
+    cached_at: Option<i64>,
+    refresh_at: Option<i64>,
+    product_id: Option<String>,
+    machine_fingerprint: Option<String>,
+}
+
+/// A cache file as written by this SDK: the record plus its checksum
+#[derive(Serialize)]
+struct ChecksummedEntry<'a> {
+    #[serde(flatten)]
+    data: &'a CachedLicenseData,
+    checksum: String,
+}
+
+/// A cache file as read back; files from older versions have no checksum
+#[derive(Deserialize)]
+struct StoredEntry {
+    #[serde(flatten)]
+    data: CachedLicenseData,
+    #[serde(default)]
+    checksum: Option<String>,
+}
+
+/// Resolve the default storage directory from `TUISH_STORAGE_DIR` and the home directory
+fn default_base_dir(env_dir: Option<OsString>, home_dir: Option<PathBuf>) -> PathBuf {
+    if let Some(dir) = env_dir.filter(|dir| !dir.is_empty()) {
+        return PathBuf::from(dir);
+    }
+    if let Some(home_dir) = home_dir {
+        return home_dir.join(DEFAULT_STORAGE_SUBDIR);
+    }
+
+    let dir = std::env::temp_dir().join(TEMP_STORAGE_SUBDIR);
+    warn!(path = ?dir, "Could not determine home directory, caching licenses in temp dir");
+    dir
+}
+
 /// File-based license storage for caching licenses locally.
 ///
 /// Licenses are stored as JSON files in the storage directory, with
@@ -54,25 +162,31 @@ pub struct LicenseStorage {
     base_dir: PathBuf,
     /// Enable debug logging
     debug: bool,
+    /// Keep the previous license when a save replaces it
+    keep_history: bool,
+    /// Restrict the directory and cache files to the current user (Unix)
+    strict_permissions: bool,
 }
 
 impl LicenseStorage {
-    /// Create a new LicenseStorage with the default directory (~/.tuish/licenses/).
+    /// Create a new LicenseStorage with the default directory.
+    ///
+    /// Uses `TUISH_STORAGE_DIR` if set, else `~/.tuish/licenses/`, else a
+    /// directory under the system temp dir; see the
+    /// [module docs](self#storage-directory).
     ///
     /// # Errors
     ///
-    /// Returns an error if the home directory cannot be determined.
+    /// Does not currently fail; the temp dir is the last resort.
     #[cfg(feature = "storage")]
     pub fn new() -> Result<Self, TuishError> {
-        let home_dir = dirs::home_dir().ok_or_else(|| {
-            TuishError::StorageError("could not determine home directory".to_string())
-        })?;
-
-        let base_dir = home_dir.join(DEFAULT_STORAGE_SUBDIR);
+        let base_dir = default_base_dir(std::env::var_os(STORAGE_DIR_ENV), dirs::home_dir());
 
         Ok(Self {
             base_dir,
             debug: false,
+            keep_history: false,
+            strict_permissions: true,
         })
     }
 
@@ -92,6 +206,8 @@ impl LicenseStorage {
         Self {
             base_dir: dir,
             debug: false,
+            keep_history: false,
+            strict_permissions: true,
         }
     }
 
@@ -101,6 +217,23 @@ impl LicenseStorage {
         self
     }
 
+    /// Keep the replaced license when a save changes the key.
+    ///
+    /// See the [module docs](self#history).
+    pub fn with_history(mut self, keep_history: bool) -> Self {
+        self.keep_history = keep_history;
+        self
+    }
+
+    /// Restrict the storage directory and cache files to the current user.
+    ///
+    /// On by default; has no effect outside Unix. See the
+    /// [module docs](self#permissions).
+    pub fn with_strict_permissions(mut self, strict: bool) -> Self {
+        self.strict_permissions = strict;
+        self
+    }
+
     /// Get the storage directory path.
     pub fn base_dir(&self) -> &PathBuf {
         &self.base_dir
@@ -115,6 +248,12 @@ impl LicenseStorage {
         self.base_dir.join(format!("{}.json", hash))
     }
 
+    /// Get the file path for a product's previous license.
+    fn get_previous_path(&self, product_id: &str) -> PathBuf {
+        let hash = self.hash_product_id(product_id);
+        self.base_dir.join(format!("{}{}", hash, PREVIOUS_SUFFIX))
+    }
+
     /// Hash a product ID to create a safe filename.
     ///
     /// Returns the first 16 hex characters of SHA256(product_id).
@@ -141,6 +280,7 @@ impl LicenseStorage {
                     self.base_dir, e
                 ))
             })?;
+            self.restrict_storage_dir()?;
 
             if self.debug {
                 debug!(path = ?self.base_dir, "Created storage directory");
@@ -149,6 +289,46 @@ impl LicenseStorage {
         Ok(())
     }
 
+    /// Set the storage directory's mode after creating it.
+    fn restrict_storage_dir(&self) -> Result<(), TuishError> {
+        #[cfg(unix)]
+        if self.strict_permissions {
+            use std::os::unix::fs::PermissionsExt;
+
+            std::fs::set_permissions(
+                &self.base_dir,
+                std::fs::Permissions::from_mode(STRICT_DIR_MODE),
+            )
+            .map_err(|e| {
+                TuishError::StorageError(format!(
+                    "failed to set storage directory permissions: {}",
+                    e
+                ))
+            })?;
+        }
+        Ok(())
+    }
+
+    /// Open options that create or truncate a cache file.
+    fn cache_file_options(&self) -> std::fs::OpenOptions {
+        let mut options = std::fs::OpenOptions::new();
+        options.write(true).create(true).truncate(true);
+        #[cfg(unix)]
+        if self.strict_permissions {
+            use std::os::unix::fs::OpenOptionsExt;
+
+            options.mode(STRICT_FILE_MODE);
+        }
+        options
+    }
+
+    /// Write `contents` to a cache file, creating it if needed.
+    fn write_cache_file_sync(&self, path: &Path, contents: &[u8]) -> std::io::Result<()> {
+        use std::io::Write;
+
+        self.cache_file_options().open(path)?.write_all(contents)
+    }
+
     /// Load a cached license from disk.
     ///
     /// # Arguments
@@ -186,9 +366,8 @@ impl LicenseStorage {
             TuishError::StorageError(format!("failed to read cache file: {}", e))
         })?;
 
-        let data: CachedLicenseData = serde_json::from_str(&contents).map_err(|e| {
-            TuishError::StorageError(format!("failed to parse cache file: {}", e))
-        })?;
+        let data = decode_cache_entry(&contents)
+            .map_err(|e| TuishError::StorageError(format!("failed to parse cache file: {}", e)))?;
 
         if self.debug {
             debug!(product_id = product_id, path = ?path, "Loaded cached license");
@@ -215,18 +394,30 @@ impl LicenseStorage {
         self.ensure_storage_dir().await?;
 
         let path = self.get_license_path(product_id);
-        let json =
-            serde_json::to_string_pretty(data).map_err(|e| {
-                TuishError::StorageError(format!("failed to serialize license: {}", e))
-            })?;
+        if self.keep_history && self.replaces_license_sync(product_id, data) {
+            fs::rename(&path, self.get_previous_path(product_id))
+                .await
+                .map_err(|e| {
+                    TuishError::StorageError(format!("failed to keep previous license: {}", e))
+                })?;
+        }
 
-        let mut file = fs::File::create(&path).await.map_err(|e| {
-            TuishError::StorageError(format!("failed to create cache file: {}", e))
-        })?;
+        let json = encode_cache_entry(data)
+            .map_err(|e| TuishError::StorageError(format!("failed to serialize license: {}", e)))?;
+
+        let mut file = fs::OpenOptions::from(self.cache_file_options())
+            .open(&path)
+            .await
+            .map_err(|e| TuishError::StorageError(format!("failed to create cache file: {}", e)))?;
 
         file.write_all(json.as_bytes()).await.map_err(|e| {
             TuishError::StorageError(format!("failed to write cache file: {}", e))
         })?;
+        // tokio writes in the background; make sure the data is on disk
+        // before a sync read of the same file
+        file.flush()
+            .await
+            .map_err(|e| TuishError::StorageError(format!("failed to write cache file: {}", e)))?;
 
         if self.debug {
             debug!(product_id = product_id, path = ?path, "Saved license to cache");
@@ -240,6 +431,10 @@ impl LicenseStorage {
     /// Creates a `CachedLicenseData` with current timestamps and saves it.
     /// This matches the TypeScript SDK behavior.
     ///
+    /// If the same key is already cached for the same machine, the file is
+    /// left alone while it is fresh, and only `refresh_at` is pushed back
+    /// once it is due; use `save_license_key_forced` to always rewrite it.
+    ///
     /// # Arguments
     ///
     /// * `product_id` - The product ID.
@@ -250,10 +445,28 @@ impl LicenseStorage {
         product_id: &str,
         license_key: &str,
         machine_fingerprint: &str,
+    ) -> Result<(), TuishError> {
+        let existing = self.load_license(product_id).await.ok().flatten();
+        match self.unchanged_save(existing, license_key, machine_fingerprint) {
+            Some(None) => Ok(()),
+            Some(Some(touched)) => self.save_license(product_id, &touched).await,
+            None => {
+                self.save_license_key_forced(product_id, license_key, machine_fingerprint)
+                    .await
+            }
+        }
+    }
+
+    /// Save a license with fresh timestamps, even if it is already cached.
+    pub async fn save_license_key_forced(
+        &self,
+        product_id: &str,
+        license_key: &str,
+        machine_fingerprint: &str,
     ) -> Result<(), TuishError> {
         let now = current_time_millis();
         let data = CachedLicenseData {
-            license_key: license_key.to_string(),
+            license_key: license_key.into(),
             cached_at: now,
             refresh_at: now + CACHE_REFRESH_MS,
             product_id: product_id.to_string(),
@@ -303,7 +516,8 @@ impl LicenseStorage {
 
     /// Clear all cached licenses.
     ///
-    /// Removes all `.json` files from the storage directory.
+    /// Removes all `.json` files from the storage directory, including
+    /// previous licenses kept in history mode.
     pub async fn clear_all(&self) -> Result<(), TuishError> {
         if !self.base_dir.exists() {
             return Ok(());
@@ -331,6 +545,60 @@ impl LicenseStorage {
         Ok(())
     }
 
+    /// Import license caches written by the TypeScript SDK.
+    ///
+    /// Reads every `.json` file in `dir` and saves it under this storage's
+    /// base directory in the canonical Rust shape. Missing fields are filled
+    /// in: the product ID from the license payload, timestamps from the
+    /// current time. Files that are not license caches are skipped with a
+    /// warning.
+    ///
+    /// # Returns
+    ///
+    /// The product IDs that were imported.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `dir` cannot be read or an imported license
+    /// cannot be written.
+    pub async fn import_from(&self, dir: &Path) -> Result<Vec<String>, TuishError> {
+        let mut entries = fs::read_dir(dir).await.map_err(|e| {
+            TuishError::StorageError(format!("failed to read import directory {:?}: {}", dir, e))
+        })?;
+
+        let mut imported = Vec::new();
+        while let Some(entry) = entries.next_entry().await.map_err(|e| {
+            TuishError::StorageError(format!("failed to read directory entry: {}", e))
+        })? {
+            let path = entry.path();
+            if path.extension().and_then(|s| s.to_str()) != Some("json")
+                || path.to_string_lossy().ends_with(PREVIOUS_SUFFIX)
+            {
+                continue;
+            }
+
+            let contents = match fs::read_to_string(&path).await {
+                Ok(contents) => contents,
+                Err(e) => {
+                    warn!(path = ?path, error = %e, "Skipping unreadable cache file");
+                    continue;
+                }
+            };
+            let Some(data) = canonical_cache_entry(&contents) else {
+                warn!(path = ?path, "Skipping file that is not a license cache");
+                continue;
+            };
+
+            self.save_license(&data.product_id, &data).await?;
+            if self.debug {
+                debug!(product_id = %data.product_id, path = ?path, "Imported cached license");
+            }
+            imported.push(data.product_id);
+        }
+
+        Ok(imported)
+    }
+
     // =========================================================================
     // Synchronous API (for non-async contexts)
     // =========================================================================
@@ -350,9 +618,8 @@ impl LicenseStorage {
             TuishError::StorageError(format!("failed to read cache file: {}", e))
         })?;
 
-        let data: CachedLicenseData = serde_json::from_str(&contents).map_err(|e| {
-            TuishError::StorageError(format!("failed to parse cache file: {}", e))
-        })?;
+        let data = decode_cache_entry(&contents)
+            .map_err(|e| TuishError::StorageError(format!("failed to parse cache file: {}", e)))?;
 
         Ok(Some(data))
     }
@@ -371,18 +638,99 @@ impl LicenseStorage {
                     e
                 ))
             })?;
+            self.restrict_storage_dir()?;
         }
 
         let path = self.get_license_path(product_id);
-        let json = serde_json::to_string_pretty(data)?;
+        if self.keep_history && self.replaces_license_sync(product_id, data) {
+            std::fs::rename(&path, self.get_previous_path(product_id)).map_err(|e| {
+                TuishError::StorageError(format!("failed to keep previous license: {}", e))
+            })?;
+        }
 
-        std::fs::write(&path, json).map_err(|e| {
-            TuishError::StorageError(format!("failed to write cache file: {}", e))
-        })?;
+        let json = encode_cache_entry(data)?;
+
+        self.write_cache_file_sync(&path, json.as_bytes())
+            .map_err(|e| TuishError::StorageError(format!("failed to write cache file: {}", e)))?;
 
         Ok(())
     }
 
+    /// Save a license with automatic timestamp management (synchronous version).
+    ///
+    /// Skips or reduces unchanged saves like `save_license_key`.
+    pub fn save_license_key_sync(
+        &self,
+        product_id: &str,
+        license_key: &str,
+        machine_fingerprint: &str,
+    ) -> Result<(), TuishError> {
+        let existing = self.load_license_sync(product_id).ok().flatten();
+        match self.unchanged_save(existing, license_key, machine_fingerprint) {
+            Some(None) => Ok(()),
+            Some(Some(touched)) => self.save_license_sync(product_id, &touched),
+            None => self.save_license_key_forced_sync(product_id, license_key, machine_fingerprint),
+        }
+    }
+
+    /// Save a license with fresh timestamps, even if it is already cached
+    /// (synchronous version).
+    pub fn save_license_key_forced_sync(
+        &self,
+        product_id: &str,
+        license_key: &str,
+        machine_fingerprint: &str,
+    ) -> Result<(), TuishError> {
+        let now = current_time_millis();
+        let data = CachedLicenseData {
+            license_key: license_key.into(),
+            cached_at: now,
+            refresh_at: now + CACHE_REFRESH_MS,
+            product_id: product_id.to_string(),
+            machine_fingerprint: machine_fingerprint.to_string(),
+        };
+
+        self.save_license_sync(product_id, &data)
+    }
+
+    /// What an unforced save of the same key needs to write.
+    ///
+    /// `None` if `existing` holds a different key or machine, so a full
+    /// save is needed; `Some(None)` if it is identical and fresh, so
+    /// nothing is written; `Some(Some(data))` with `refresh_at` pushed back
+    /// if it is identical but due for refresh.
+    fn unchanged_save(
+        &self,
+        existing: Option<CachedLicenseData>,
+        license_key: &str,
+        machine_fingerprint: &str,
+    ) -> Option<Option<CachedLicenseData>> {
+        let mut existing = existing.filter(|data| {
+            data.license_key == license_key && data.machine_fingerprint == machine_fingerprint
+        })?;
+
+        if !self.needs_refresh(&existing) {
+            trace!(product_id = %existing.product_id, "License unchanged, skipping save");
+            return Some(None);
+        }
+        existing.refresh_at = current_time_millis() + CACHE_REFRESH_MS;
+        Some(Some(existing))
+    }
+
+    /// Push back a cached license's next refresh without changing it.
+    ///
+    /// Sets `refresh_at` to now plus the 24-hour interval; the key and other
+    /// fields are kept. Returns `false` if nothing is cached for `product_id`.
+    pub fn touch_license_sync(&self, product_id: &str) -> Result<bool, TuishError> {
+        let Some(mut data) = self.load_license_sync(product_id)? else {
+            return Ok(false);
+        };
+
+        data.refresh_at = current_time_millis() + CACHE_REFRESH_MS;
+        self.save_license_sync(product_id, &data)?;
+        Ok(true)
+    }
+
     /// Delete a cached license (synchronous version).
     pub fn delete_license_sync(&self, product_id: &str) -> Result<(), TuishError> {
         let path = self.get_license_path(product_id);
@@ -396,6 +744,40 @@ impl LicenseStorage {
         Ok(())
     }
 
+    /// Load the license replaced by the last save in history mode.
+    ///
+    /// Returns `Ok(None)` if no previous license was kept.
+    pub async fn load_previous(
+        &self,
+        product_id: &str,
+    ) -> Result<Option<CachedLicenseData>, TuishError> {
+        let path = self.get_previous_path(product_id);
+        let contents = match fs::read_to_string(&path).await {
+            Ok(contents) => contents,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+            Err(e) => {
+                return Err(TuishError::StorageError(format!(
+                    "failed to read previous license: {}",
+                    e
+                )));
+            }
+        };
+
+        let data = decode_cache_entry(&contents).map_err(|e| {
+            TuishError::StorageError(format!("failed to parse previous license: {}", e))
+        })?;
+        Ok(Some(data))
+    }
+
+    /// Whether saving `data` would replace a different cached license key.
+    ///
+    /// Refreshes and touches of the same key don't count, so they never
+    /// overwrite the kept generation.
+    fn replaces_license_sync(&self, product_id: &str, data: &CachedLicenseData) -> bool {
+        self.get_license_key_sync(product_id)
+            .is_some_and(|current| current != data.license_key)
+    }
+
     /// Check if a cached license exists.
     pub fn exists(&self, product_id: &str) -> bool {
         self.get_license_path(product_id).exists()
@@ -406,8 +788,178 @@ impl LicenseStorage {
         self.load_license_sync(product_id)
             .ok()
             .flatten()
-            .map(|data| data.license_key)
+            .map(|data| data.license_key.into_inner())
+    }
+
+    // =========================================================================
+    // Revocation Lists
+    // =========================================================================
+
+    /// Path to the cached signed revocation list for a product.
+    ///
+    /// Uses a separate extension so `clear_all` leaves it in place.
+    fn get_revocation_list_path(&self, product_id: &str) -> PathBuf {
+        let hash = self.hash_product_id(product_id);
+        self.base_dir
+            .join(format!("{}.{}", hash, REVOCATION_LIST_EXTENSION))
+    }
+
+    /// Load the cached signed revocation list token, if any.
+    ///
+    /// The token is returned as stored; callers must verify its signature.
+    pub fn load_revocation_list_sync(&self, product_id: &str) -> Option<String> {
+        std::fs::read_to_string(self.get_revocation_list_path(product_id))
+            .ok()
+            .map(|contents| contents.trim().to_string())
+    }
+
+    /// Save a signed revocation list token to disk.
+    pub fn save_revocation_list_sync(
+        &self,
+        product_id: &str,
+        signed_list: &str,
+    ) -> Result<(), TuishError> {
+        if !self.base_dir.exists() {
+            std::fs::create_dir_all(&self.base_dir).map_err(|e| {
+                TuishError::StorageError(format!("failed to create storage directory: {}", e))
+            })?;
+            self.restrict_storage_dir()?;
+        }
+
+        let path = self.get_revocation_list_path(product_id);
+        self.write_cache_file_sync(&path, signed_list.as_bytes())
+            .map_err(|e| {
+                TuishError::StorageError(format!("failed to write revocation list: {}", e))
+            })?;
+
+        if self.debug {
+            debug!(product_id = %product_id, "Saved revocation list");
+        }
+
+        Ok(())
+    }
+
+    // =========================================================================
+    // Installation ID
+    // =========================================================================
+
+    /// Read this installation's ID, creating it on first use.
+    ///
+    /// A random UUID stored once as `install.id` in the storage directory,
+    /// shared by all products. Unlike the machine fingerprint it doesn't
+    /// depend on the hostname or username; it only changes if the file is
+    /// deleted. `clear_all` leaves it in place.
+    pub fn installation_id_sync(&self) -> Result<String, TuishError> {
+        let path = self.base_dir.join(INSTALLATION_ID_FILE);
+        if let Ok(contents) = std::fs::read_to_string(&path) {
+            let id = contents.trim();
+            if !id.is_empty() {
+                return Ok(id.to_string());
+            }
+        }
+
+        if !self.base_dir.exists() {
+            std::fs::create_dir_all(&self.base_dir).map_err(|e| {
+                TuishError::StorageError(format!("failed to create storage directory: {}", e))
+            })?;
+            self.restrict_storage_dir()?;
+        }
+
+        let id = new_installation_id();
+        self.write_cache_file_sync(&path, id.as_bytes())
+            .map_err(|e| {
+                TuishError::StorageError(format!("failed to write installation ID: {}", e))
+            })?;
+
+        if self.debug {
+            debug!(installation_id = %id, "Created installation ID");
+        }
+
+        Ok(id)
+    }
+}
+
+/// Generate a random (version 4) UUID for `install.id`.
+pub(crate) fn new_installation_id() -> String {
+    let mut bytes = [0u8; 16];
+    getrandom::getrandom(&mut bytes).expect("OS random number generator failed");
+    bytes[6] = (bytes[6] & 0x0f) | 0x40;
+    bytes[8] = (bytes[8] & 0x3f) | 0x80;
+
+    let hex: String = bytes.iter().map(|b| format!("{:02x}", b)).collect();
+    format!(
+        "{}-{}-{}-{}-{}",
+        &hex[..8],
+        &hex[8..12],
+        &hex[12..16],
+        &hex[16..20],
+        &hex[20..]
+    )
+}
+
+/// Convert a TypeScript SDK cache file into `CachedLicenseData`.
+///
+/// Returns `None` without a license key, or when the product ID is neither
+/// stored nor readable from the license payload.
+fn canonical_cache_entry(contents: &str) -> Option<CachedLicenseData> {
+    let entry: ImportedCacheEntry = serde_json::from_str(contents).ok()?;
+    let license_key = entry.license_key.filter(|key| !key.is_empty())?;
+    let product_id = entry
+        .product_id
+        .filter(|id| !id.is_empty())
+        .or_else(|| crate::crypto::extract_license_payload(&license_key).map(|p| p.pid))?;
+
+    let cached_at = entry.cached_at.unwrap_or_else(current_time_millis);
+    Some(CachedLicenseData {
+        license_key: license_key.into(),
+        cached_at,
+        refresh_at: entry.refresh_at.unwrap_or(cached_at + CACHE_REFRESH_MS),
+        product_id,
+        machine_fingerprint: entry.machine_fingerprint.unwrap_or_default(),
+    })
+}
+
+/// Serialize a cache record with its checksum.
+fn encode_cache_entry(data: &CachedLicenseData) -> Result<String, serde_json::Error> {
+    serde_json::to_string_pretty(&ChecksummedEntry {
+        data,
+        checksum: cache_checksum(data),
+    })
+}
+
+/// Parse a cache file, marking the record due for refresh if its checksum
+/// is missing or wrong.
+fn decode_cache_entry(contents: &str) -> Result<CachedLicenseData, serde_json::Error> {
+    let StoredEntry { mut data, checksum } = serde_json::from_str(contents)?;
+    if checksum.as_deref() != Some(cache_checksum(&data).as_str()) {
+        warn!(product_id = %data.product_id, "Cache checksum mismatch, forcing re-validation");
+        data.refresh_at = 0;
     }
+    Ok(data)
+}
+
+/// HMAC-SHA256 of a cache record, keyed by this machine's fingerprint.
+fn cache_checksum(data: &CachedLicenseData) -> String {
+    let key = crate::fingerprint::get_machine_fingerprint_cached();
+    let mut mac =
+        Hmac::<Sha256>::new_from_slice(key.as_bytes()).expect("HMAC accepts any key length");
+    mac.update(
+        format!(
+            "{}|{}|{}|{}|{}|{}",
+            CACHE_CHECKSUM_CONTEXT,
+            data.license_key,
+            data.cached_at,
+            data.refresh_at,
+            data.product_id,
+            data.machine_fingerprint
+        )
+        .as_bytes(),
+    );
+    mac.finalize()
+        .into_bytes()
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect()
 }
 
 /// Get the current time in milliseconds since Unix epoch.
@@ -433,7 +985,7 @@ mod tests {
     fn create_test_cached_data(product_id: &str) -> CachedLicenseData {
         let now = current_time_millis();
         CachedLicenseData {
-            license_key: "test-license-key".to_string(),
+            license_key: "test-license-key".into(),
             cached_at: now,
             refresh_at: now + CACHE_REFRESH_MS,
             product_id: product_id.to_string(),
@@ -451,6 +1003,44 @@ mod tests {
         assert!(storage.base_dir().ends_with(".tuish/licenses"));
     }
 
+    #[tokio::test]
+    async fn test_default_base_dir_uses_env_without_home() {
+        let temp_dir = TempDir::new().unwrap();
+        let base_dir = default_base_dir(Some(temp_dir.path().into()), None);
+        assert_eq!(base_dir, temp_dir.path());
+
+        let storage = LicenseStorage::with_base_dir(base_dir);
+        storage
+            .save_license("prod_test", &create_test_cached_data("prod_test"))
+            .await
+            .unwrap();
+        assert!(temp_dir
+            .path()
+            .join(format!("{}.json", storage.hash_product_id("prod_test")))
+            .exists());
+    }
+
+    #[test]
+    fn test_default_base_dir_precedence() {
+        let home = PathBuf::from("/home/user");
+
+        // The env var wins over the home directory; an empty one is ignored
+        assert_eq!(
+            default_base_dir(Some("/srv/tuish".into()), Some(home.clone())),
+            PathBuf::from("/srv/tuish")
+        );
+        assert_eq!(
+            default_base_dir(Some("".into()), Some(home.clone())),
+            home.join(DEFAULT_STORAGE_SUBDIR)
+        );
+
+        // Without either, fall back to the temp dir
+        assert_eq!(
+            default_base_dir(None, None),
+            std::env::temp_dir().join(TEMP_STORAGE_SUBDIR)
+        );
+    }
+
     #[tokio::test]
     async fn test_storage_with_base_dir() {
         let custom_path = PathBuf::from("/tmp/custom-licenses");
@@ -508,6 +1098,35 @@ mod tests {
         assert_eq!(loaded.machine_fingerprint, data.machine_fingerprint);
     }
 
+    #[tokio::test]
+    async fn test_checksum_detects_tampering() {
+        let (storage, _temp) = create_test_storage().await;
+        let data = create_test_cached_data("prod_test");
+        storage.save_license("prod_test", &data).await.unwrap();
+
+        // Intact record loads unchanged
+        let loaded = storage.load_license("prod_test").await.unwrap().unwrap();
+        assert_eq!(loaded.refresh_at, data.refresh_at);
+        assert!(!storage.needs_refresh(&loaded));
+
+        // Pushing refreshAt out by hand breaks the checksum
+        let path = storage.get_license_path("prod_test");
+        let mut entry: serde_json::Value =
+            serde_json::from_str(&std_fs::read_to_string(&path).unwrap()).unwrap();
+        entry["refreshAt"] = serde_json::json!(i64::MAX / 2);
+        std_fs::write(&path, entry.to_string()).unwrap();
+
+        let loaded = storage.load_license_sync("prod_test").unwrap().unwrap();
+        assert_eq!(loaded.license_key, data.license_key);
+        assert!(storage.needs_refresh(&loaded));
+
+        // So does dropping the checksum
+        entry.as_object_mut().unwrap().remove("checksum");
+        std_fs::write(&path, entry.to_string()).unwrap();
+        let loaded = storage.load_license("prod_test").await.unwrap().unwrap();
+        assert!(storage.needs_refresh(&loaded));
+    }
+
     #[tokio::test]
     async fn test_load_nonexistent_license() {
         let (storage, _temp) = create_test_storage().await;
@@ -555,7 +1174,7 @@ mod tests {
 
         // Fresh cache (refresh_at is in the future)
         let fresh = CachedLicenseData {
-            license_key: "key".to_string(),
+            license_key: "key".into(),
             cached_at: now,
             refresh_at: now + 3600000, // 1 hour from now
             product_id: "prod".to_string(),
@@ -565,7 +1184,7 @@ mod tests {
 
         // Stale cache (refresh_at is in the past)
         let stale = CachedLicenseData {
-            license_key: "key".to_string(),
+            license_key: "key".into(),
             cached_at: now - 90000000, // 25 hours ago
             refresh_at: now - 3600000, // 1 hour ago
             product_id: "prod".to_string(),
@@ -652,6 +1271,157 @@ mod tests {
         assert_eq!(loaded.license_key, data.license_key);
     }
 
+    #[test]
+    fn test_revocation_list_save_and_load() {
+        let temp_dir = TempDir::new().unwrap();
+        let storage = LicenseStorage::with_base_dir(temp_dir.path().join("nested"));
+
+        assert!(storage.load_revocation_list_sync("prod_test").is_none());
+
+        storage
+            .save_revocation_list_sync("prod_test", "h.p.s")
+            .unwrap();
+        assert_eq!(
+            storage.load_revocation_list_sync("prod_test").as_deref(),
+            Some("h.p.s")
+        );
+        assert!(storage.load_revocation_list_sync("prod_other").is_none());
+        // Not a license cache file
+        assert!(!storage.exists("prod_test"));
+    }
+
+    #[test]
+    fn test_installation_id_is_stable_until_deleted() {
+        let temp_dir = TempDir::new().unwrap();
+        let storage = LicenseStorage::with_base_dir(temp_dir.path().join("nested"));
+
+        let id = storage.installation_id_sync().unwrap();
+        assert_eq!(id.len(), 36);
+        assert_eq!(&id[14..15], "4");
+        assert!(matches!(&id[19..20], "8" | "9" | "a" | "b"));
+        assert_eq!(storage.installation_id_sync().unwrap(), id);
+        assert_eq!(
+            LicenseStorage::with_base_dir(temp_dir.path().join("nested"))
+                .installation_id_sync()
+                .unwrap(),
+            id
+        );
+
+        std_fs::remove_file(temp_dir.path().join("nested").join(INSTALLATION_ID_FILE)).unwrap();
+        let regenerated = storage.installation_id_sync().unwrap();
+        assert_ne!(regenerated, id);
+        assert_eq!(storage.installation_id_sync().unwrap(), regenerated);
+    }
+
+    #[tokio::test]
+    async fn test_history_keeps_previous_license() {
+        let (storage, _temp_dir) = create_test_storage().await;
+        let storage = storage.with_history(true);
+        let product_id = "prod_history";
+        assert!(storage.load_previous(product_id).await.unwrap().is_none());
+
+        let first = create_test_cached_data(product_id);
+        storage.save_license(product_id, &first).await.unwrap();
+        assert!(storage.load_previous(product_id).await.unwrap().is_none());
+
+        let renewal = CachedLicenseData {
+            license_key: "renewed-license-key".into(),
+            ..first.clone()
+        };
+        storage.save_license(product_id, &renewal).await.unwrap();
+
+        let current = storage.load_license(product_id).await.unwrap().unwrap();
+        assert_eq!(current.license_key, "renewed-license-key");
+        let previous = storage.load_previous(product_id).await.unwrap().unwrap();
+        assert_eq!(previous.license_key, "test-license-key");
+
+        // Re-saving the same key (e.g. a refresh) keeps the older generation
+        storage.save_license_sync(product_id, &renewal).unwrap();
+        let previous = storage.load_previous(product_id).await.unwrap().unwrap();
+        assert_eq!(previous.license_key, "test-license-key");
+    }
+
+    #[test]
+    fn test_history_disabled_by_default() {
+        let temp_dir = TempDir::new().unwrap();
+        let storage = LicenseStorage::with_base_dir(temp_dir.path().to_path_buf());
+        let product_id = "prod_no_history";
+
+        let first = create_test_cached_data(product_id);
+        storage.save_license_sync(product_id, &first).unwrap();
+        let renewal = CachedLicenseData {
+            license_key: "renewed-license-key".into(),
+            ..first
+        };
+        storage.save_license_sync(product_id, &renewal).unwrap();
+
+        assert!(!storage.get_previous_path(product_id).exists());
+    }
+
+    #[tokio::test]
+    async fn test_save_license_key_skips_unchanged() {
+        let (storage, _temp_dir) = create_test_storage().await;
+        let product_id = "prod_idempotent";
+        let path = storage.get_license_path(product_id);
+
+        storage
+            .save_license_key(product_id, "key", "fp")
+            .await
+            .unwrap();
+        let written = std_fs::read_to_string(&path).unwrap();
+        let modified = std_fs::metadata(&path).unwrap().modified().unwrap();
+
+        std::thread::sleep(std::time::Duration::from_millis(20));
+        storage
+            .save_license_key(product_id, "key", "fp")
+            .await
+            .unwrap();
+        storage
+            .save_license_key_sync(product_id, "key", "fp")
+            .unwrap();
+        assert_eq!(
+            std_fs::metadata(&path).unwrap().modified().unwrap(),
+            modified
+        );
+        assert_eq!(std_fs::read_to_string(&path).unwrap(), written);
+
+        // A different machine is a real change
+        storage
+            .save_license_key_sync(product_id, "key", "fp_other")
+            .unwrap();
+        let changed = storage.load_license_sync(product_id).unwrap().unwrap();
+        assert_eq!(changed.machine_fingerprint, "fp_other");
+    }
+
+    #[test]
+    fn test_save_license_key_touches_stale_and_forces() {
+        let temp_dir = TempDir::new().unwrap();
+        let storage = LicenseStorage::with_base_dir(temp_dir.path().to_path_buf());
+        let product_id = "prod_stale";
+        let stale = CachedLicenseData {
+            license_key: "key".into(),
+            cached_at: 0,
+            refresh_at: 0,
+            product_id: product_id.to_string(),
+            machine_fingerprint: "fp".to_string(),
+        };
+        storage.save_license_sync(product_id, &stale).unwrap();
+
+        // Same key, due for refresh: only refresh_at moves
+        storage
+            .save_license_key_sync(product_id, "key", "fp")
+            .unwrap();
+        let touched = storage.load_license_sync(product_id).unwrap().unwrap();
+        assert_eq!(touched.cached_at, 0);
+        assert!(!storage.needs_refresh(&touched));
+
+        storage
+            .save_license_key_forced_sync(product_id, "key", "fp")
+            .unwrap();
+        let forced = storage.load_license_sync(product_id).unwrap().unwrap();
+        assert!(forced.cached_at > 0);
+    }
+
     #[test]
     fn test_exists() {
         let temp_dir = TempDir::new().unwrap();
@@ -665,4 +1435,95 @@ mod tests {
 
         assert!(storage.exists(product_id));
     }
+
+    #[tokio::test]
+    async fn test_import_from_ts_sdk_cache() {
+        let ts_dir = TempDir::new().unwrap();
+        let (storage, _temp_dir) = create_test_storage().await;
+
+        // Shape written by the TypeScript SDK, with an extra field and no refreshAt
+        std_fs::write(
+            ts_dir.path().join("a1b2c3d4e5f60718.json"),
+            r#"{
+                "licenseKey": "ts-license-key",
+                "cachedAt": 1700000000000,
+                "productId": "prod_ts",
+                "machineFingerprint": "ts-fingerprint",
+                "sdkVersion": "0.4.2"
+            }"#,
+        )
+        .unwrap();
+
+        // No productId: recovered from the license payload
+        let license_key = [
+            crate::crypto::to_base64url(br#"{"alg":"ed25519","ver":1}"#),
+            crate::crypto::to_base64url(
+                br#"{"lid":"lic_1","pid":"prod_from_payload","cid":"c","did":"d","features":[],"iat":0,"exp":null,"mid":null}"#,
+            ),
+            crate::crypto::to_base64url(&[0u8; 64]),
+        ]
+        .join(".");
+        std_fs::write(
+            ts_dir.path().join("other.json"),
+            serde_json::json!({ "licenseKey": license_key }).to_string(),
+        )
+        .unwrap();
+
+        // Not license caches
+        std_fs::write(ts_dir.path().join("settings.json"), r#"{"theme": "dark"}"#).unwrap();
+        std_fs::write(ts_dir.path().join("broken.json"), "not json").unwrap();
+        std_fs::write(ts_dir.path().join("prod_ts.revocations"), "h.p.s").unwrap();
+
+        let mut imported = storage.import_from(ts_dir.path()).await.unwrap();
+        imported.sort();
+        assert_eq!(imported, vec!["prod_from_payload", "prod_ts"]);
+
+        let ts = storage.load_license("prod_ts").await.unwrap().unwrap();
+        assert_eq!(ts.license_key, "ts-license-key");
+        assert_eq!(ts.cached_at, 1700000000000);
+        assert_eq!(ts.refresh_at, 1700000000000 + CACHE_REFRESH_MS);
+        assert_eq!(ts.machine_fingerprint, "ts-fingerprint");
+
+        // Rewritten in the canonical shape, without the unknown field
+        let rewritten = std_fs::read_to_string(storage.get_license_path("prod_ts")).unwrap();
+        let json: serde_json::Value = serde_json::from_str(&rewritten).unwrap();
+        assert_eq!(json["refreshAt"], 1700000000000i64 + CACHE_REFRESH_MS);
+        assert!(json.get("sdkVersion").is_none());
+
+        let recovered = storage
+            .load_license("prod_from_payload")
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(recovered.product_id, "prod_from_payload");
+        assert!(recovered.machine_fingerprint.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_import_from_missing_dir() {
+        let (storage, temp_dir) = create_test_storage().await;
+        let result = storage.import_from(&temp_dir.path().join("missing")).await;
+        assert!(matches!(result, Err(TuishError::StorageError(_))));
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_strict_permissions() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let temp_dir = TempDir::new().unwrap();
+        let mode = |path: &Path| std_fs::metadata(path).unwrap().permissions().mode() & 0o777;
+
+        let storage = LicenseStorage::with_base_dir(temp_dir.path().join("strict"));
+        storage
+            .save_license_key("prod_async", "key", "fp")
+            .await
+            .unwrap();
+        storage
+            .save_license_key_sync("prod_sync", "key", "fp")
+            .unwrap();
+        assert_eq!(mode(storage.base_dir()), 0o700);
+        assert_eq!(mode(&storage.get_license_path("prod_async")), 0o600);
+        assert_eq!(mode(&storage.get_license_path("prod_sync")), 0o600);
+    }
 }
@@ -0,0 +1,32 @@
+//! Fixtures shared by the unit tests
+
+use serde::Serialize;
+
+use crate::signing::{generate_keypair, sign_claims, sign_license};
+use crate::types::LicensePayload;
+
+/// Seed of the key pair that signs test fixtures
+const TEST_SIGNING_SEED: [u8; 32] = [7u8; 32];
+
+/// Public key (SPKI base64) matching the fixtures' signing key
+#[allow(dead_code)]
+pub(crate) fn test_public_key() -> String {
+    generate_keypair(Some(TEST_SIGNING_SEED)).1
+}
+
+/// Sign a license with `claims` as its payload.
+///
+/// Returns the license and the matching public key (SPKI base64).
+pub(crate) fn sign_test_license(claims: serde_json::Value) -> (String, String) {
+    let payload: LicensePayload =
+        serde_json::from_value(claims).expect("claims are a license payload");
+    let (signing_key, public_key) = generate_keypair(Some(TEST_SIGNING_SEED));
+    (sign_license(&payload, &signing_key).unwrap(), public_key)
+}
+
+/// Sign other JSON, e.g. a revocation list, with the same key as
+/// `sign_test_license`.
+pub(crate) fn sign_test_claims<T: Serialize>(claims: &T) -> (String, String) {
+    let (signing_key, public_key) = generate_keypair(Some(TEST_SIGNING_SEED));
+    (sign_claims(claims, &signing_key).unwrap(), public_key)
+}
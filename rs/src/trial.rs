@@ -0,0 +1,223 @@
+//! Offline trial tracking
+//!
+//! Records when a trial first started for a product on this machine, in a
+//! directory separate from the license cache, so deleting `~/.tuish/licenses/`
+//! does not reset the trial.
+//!
+//! This is best-effort anti-abuse, not cryptographic protection: a user who
+//! finds and deletes the marker can still start over. Markers are named by a
+//! hash of the product ID and machine fingerprint, and carry a checksum so an
+//! edited timestamp is detected and treated as a trial that started long ago.
+//!
+//! Default location: the platform local data directory, e.g.
+//! `~/.local/share/.tuish-state/` on Linux.
+
+use sha2::{Digest, Sha256};
+use std::path::PathBuf;
+use std::time::Duration;
+
+use crate::error::TuishError;
+use crate::logging::{debug, warn};
+
+/// Marker directory under the platform local data directory
+const DEFAULT_TRIAL_SUBDIR: &str = ".tuish-state";
+
+/// Domain separator for marker checksums
+const MARKER_CHECKSUM_CONTEXT: &str = "tuish-trial-v1";
+
+/// Stores per-product trial start markers.
+#[derive(Debug, Clone)]
+pub struct TrialTracker {
+    /// Directory holding marker files
+    dir: PathBuf,
+}
+
+impl TrialTracker {
+    /// Create a tracker in the default location.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the local data directory cannot be determined.
+    pub fn new() -> Result<Self, TuishError> {
+        let data_dir = dirs::data_local_dir().ok_or_else(|| {
+            TuishError::StorageError("could not determine local data directory".to_string())
+        })?;
+
+        Ok(Self {
+            dir: data_dir.join(DEFAULT_TRIAL_SUBDIR),
+        })
+    }
+
+    /// Create a tracker that stores markers in `dir`.
+    pub fn with_dir(dir: PathBuf) -> Self {
+        Self { dir }
+    }
+
+    /// Get the marker directory path.
+    pub fn dir(&self) -> &PathBuf {
+        &self.dir
+    }
+
+    /// When the trial for `product_id` started on this machine (Unix ms).
+    ///
+    /// Returns `None` if no trial has been recorded. A marker that fails its
+    /// checksum reports a start time of 0 (the Unix epoch).
+    pub fn started_at(&self, product_id: &str, machine_fingerprint: &str) -> Option<i64> {
+        let path = self.marker_path(product_id, machine_fingerprint);
+        let contents = std::fs::read_to_string(&path).ok()?;
+
+        let parsed = contents
+            .trim()
+            .split_once(':')
+            .and_then(|(started_at, checksum)| {
+                let started_at: i64 = started_at.parse().ok()?;
+                (checksum == marker_checksum(product_id, machine_fingerprint, started_at))
+                    .then_some(started_at)
+            });
+
+        match parsed {
+            Some(started_at) => Some(started_at),
+            None => {
+                warn!(path = %path.display(), "Trial marker failed validation");
+                Some(0)
+            }
+        }
+    }
+
+    /// Time since the trial for `product_id` started, or `None` if it never has.
+    pub fn elapsed(&self, product_id: &str, machine_fingerprint: &str) -> Option<Duration> {
+        let started_at = self.started_at(product_id, machine_fingerprint)?;
+        let elapsed_ms = current_time_millis().saturating_sub(started_at).max(0);
+        Some(Duration::from_millis(elapsed_ms as u64))
+    }
+
+    /// Record that the trial for `product_id` started now.
+    ///
+    /// If a trial was already recorded the existing marker is kept, so the
+    /// first start time always wins. Returns the recorded start time (Unix ms).
+    pub fn record_start(
+        &self,
+        product_id: &str,
+        machine_fingerprint: &str,
+    ) -> Result<i64, TuishError> {
+        if let Some(started_at) = self.started_at(product_id, machine_fingerprint) {
+            return Ok(started_at);
+        }
+
+        std::fs::create_dir_all(&self.dir).map_err(|e| {
+            TuishError::StorageError(format!("failed to create trial directory: {}", e))
+        })?;
+
+        let started_at = current_time_millis();
+        let marker = format!(
+            "{}:{}",
+            started_at,
+            marker_checksum(product_id, machine_fingerprint, started_at)
+        );
+        std::fs::write(self.marker_path(product_id, machine_fingerprint), marker).map_err(|e| {
+            TuishError::StorageError(format!("failed to write trial marker: {}", e))
+        })?;
+
+        debug!(product_id = %product_id, "Recorded trial start");
+        Ok(started_at)
+    }
+
+    /// Marker file path: first 16 hex chars of SHA256(product_id | fingerprint).
+    fn marker_path(&self, product_id: &str, machine_fingerprint: &str) -> PathBuf {
+        let name = sha256_hex(&format!("{}|{}", product_id, machine_fingerprint));
+        self.dir.join(&name[..16])
+    }
+}
+
+fn marker_checksum(product_id: &str, machine_fingerprint: &str, started_at: i64) -> String {
+    let digest = sha256_hex(&format!(
+        "{}|{}|{}|{}",
+        MARKER_CHECKSUM_CONTEXT, product_id, machine_fingerprint, started_at
+    ));
+    digest[..32].to_string()
+}
+
+fn sha256_hex(input: &str) -> String {
+    Sha256::digest(input.as_bytes())
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect()
+}
+
+/// Get the current time in milliseconds since Unix epoch.
+fn current_time_millis() -> i64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_millis() as i64)
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn create_test_tracker() -> (TrialTracker, TempDir) {
+        let temp_dir = TempDir::new().unwrap();
+        (
+            TrialTracker::with_dir(temp_dir.path().join("state")),
+            temp_dir,
+        )
+    }
+
+    #[test]
+    fn test_first_run_has_no_trial() {
+        let (tracker, _temp_dir) = create_test_tracker();
+
+        assert!(tracker.started_at("prod_test", "fp_1").is_none());
+        assert!(tracker.elapsed("prod_test", "fp_1").is_none());
+    }
+
+    #[test]
+    fn test_subsequent_run_detects_trial() {
+        let (tracker, _temp_dir) = create_test_tracker();
+
+        let started_at = tracker.record_start("prod_test", "fp_1").unwrap();
+        assert_eq!(tracker.started_at("prod_test", "fp_1"), Some(started_at));
+        assert!(tracker.elapsed("prod_test", "fp_1").unwrap() < Duration::from_secs(60));
+
+        // A second start keeps the original timestamp
+        std::thread::sleep(Duration::from_millis(5));
+        assert_eq!(
+            tracker.record_start("prod_test", "fp_1").unwrap(),
+            started_at
+        );
+
+        // A fresh tracker on the same directory (next app run) sees it too
+        let next_run = TrialTracker::with_dir(tracker.dir().clone());
+        assert_eq!(next_run.started_at("prod_test", "fp_1"), Some(started_at));
+    }
+
+    #[test]
+    fn test_trials_are_per_product_and_machine() {
+        let (tracker, _temp_dir) = create_test_tracker();
+
+        tracker.record_start("prod_a", "fp_1").unwrap();
+        assert!(tracker.started_at("prod_b", "fp_1").is_none());
+        assert!(tracker.started_at("prod_a", "fp_2").is_none());
+    }
+
+    #[test]
+    fn test_tampered_marker_counts_as_old_trial() {
+        let (tracker, _temp_dir) = create_test_tracker();
+        tracker.record_start("prod_test", "fp_1").unwrap();
+
+        // Push the start time forward without updating the checksum
+        let path = tracker.marker_path("prod_test", "fp_1");
+        let contents = std::fs::read_to_string(&path).unwrap();
+        let (_, checksum) = contents.split_once(':').unwrap();
+        std::fs::write(&path, format!("{}:{}", i64::MAX / 2, checksum)).unwrap();
+
+        assert_eq!(tracker.started_at("prod_test", "fp_1"), Some(0));
+        assert!(
+            tracker.elapsed("prod_test", "fp_1").unwrap() > Duration::from_secs(365 * 24 * 3600)
+        );
+        // Re-recording does not reset a tampered marker
+        assert_eq!(tracker.record_start("prod_test", "fp_1").unwrap(), 0);
+    }
+}
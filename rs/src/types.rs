@@ -4,11 +4,24 @@
 //! to ensure compatibility between the Node.js and Rust SDKs.
 
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt;
+
+use crate::secret::SecretString;
+
+#[cfg(feature = "chrono")]
+use chrono::{DateTime, Utc};
 
 // ============================================================================
 // License Types
 // ============================================================================
 
+/// Feature flag carried by trial licenses
+pub const TRIAL_FEATURE: &str = "trial";
+
+/// Default `TuishConfig::expiry_warning`: 7 days
+pub const DEFAULT_EXPIRY_WARNING: std::time::Duration = std::time::Duration::from_secs(7 * 86_400);
+
 /// License header containing algorithm and version information
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub struct LicenseHeader {
@@ -53,6 +66,66 @@ pub struct LicensePayload {
 
     /// Machine ID hash for binding (null if not machine-bound)
     pub mid: Option<String>,
+
+    /// Additional machine ID hashes for floating/team licenses
+    ///
+    /// The license verifies on `mid` or any of these. Omitted when empty.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub mids: Vec<String>,
+
+    /// Per-feature expiry for add-ons sold separately from the base license
+    ///
+    /// Omitted when empty.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub entitlements: Vec<Entitlement>,
+
+    /// End of the support/updates window (milliseconds since Unix epoch)
+    ///
+    /// Lets a perpetual license (`exp: None`) keep working while updates
+    /// stop after this date. Omitted when there is no separate window.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub support_exp: Option<i64>,
+
+    /// Not-before timestamp (milliseconds since Unix epoch)
+    ///
+    /// The license is rejected as not yet valid until this time, for
+    /// licenses issued ahead of their start date. Omitted when the license
+    /// is valid from issue.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub nbf: Option<i64>,
+
+    /// Named feature groups, each standing for a set of features
+    ///
+    /// Keeps payloads small for licenses with many features; the app maps
+    /// groups to features, see `effective_features`. Omitted when empty.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub feature_groups: Vec<String>,
+}
+
+/// A feature grant with its own expiration date
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct Entitlement {
+    /// Feature flag this entitlement applies to
+    pub feature: String,
+
+    /// Expiration timestamp (milliseconds since Unix epoch, null for no separate expiry)
+    pub exp: Option<i64>,
+}
+
+impl Entitlement {
+    /// Check if this entitlement has expired
+    pub fn is_expired(&self) -> bool {
+        match self.exp {
+            Some(exp) => {
+                let now = std::time::SystemTime::now()
+                    .duration_since(std::time::UNIX_EPOCH)
+                    .map(|d| d.as_millis() as i64)
+                    .unwrap_or(0);
+                exp < now
+            }
+            None => false,
+        }
+    }
 }
 
 impl LicensePayload {
@@ -82,9 +155,112 @@ impl LicensePayload {
     }
 
     /// Check if this license has a specific feature
+    ///
+    /// A plain membership check; use `feature_active` to honour expiry.
     pub fn has_feature(&self, feature: &str) -> bool {
         self.features.iter().any(|f| f == feature)
     }
+
+    /// All granted features, with `feature_groups` expanded via `groups`
+    ///
+    /// Returns `features` followed by each group's features, in license
+    /// order without duplicates. Groups missing from `groups` grant
+    /// nothing.
+    pub fn effective_features(&self, groups: &HashMap<String, Vec<String>>) -> Vec<String> {
+        let expanded = self
+            .feature_groups
+            .iter()
+            .filter_map(|group| groups.get(group))
+            .flatten();
+
+        let mut effective: Vec<String> = Vec::new();
+        for feature in self.features.iter().chain(expanded) {
+            if !effective.contains(feature) {
+                effective.push(feature.clone());
+            }
+        }
+        effective
+    }
+
+    /// Check if a feature is granted directly or through a feature group
+    ///
+    /// Like `has_feature`, but also looks inside `feature_groups` using the
+    /// app's `groups` map.
+    pub fn has_feature_in_groups(
+        &self,
+        feature: &str,
+        groups: &HashMap<String, Vec<String>>,
+    ) -> bool {
+        self.has_feature(feature)
+            || self
+                .feature_groups
+                .iter()
+                .filter_map(|group| groups.get(group))
+                .any(|features| features.iter().any(|f| f == feature))
+    }
+
+    /// Check if a feature is granted and currently usable
+    ///
+    /// The feature must be listed in `features`, the license must not have
+    /// expired, and no entitlement for the feature may have expired.
+    pub fn feature_active(&self, feature: &str) -> bool {
+        self.has_feature(feature)
+            && !self.is_expired()
+            && !self
+                .entitlements
+                .iter()
+                .any(|e| e.feature == feature && e.is_expired())
+    }
+
+    /// Check if the support/updates window is open
+    ///
+    /// Support ends at `support_exp`, or with the license itself when there
+    /// is no separate window. An expired license never has active support.
+    pub fn support_active(&self) -> bool {
+        support_active(self.exp, self.support_exp)
+    }
+
+    /// Check if this license is bound to one or more machines
+    pub fn is_machine_bound(&self) -> bool {
+        self.mid.is_some() || !self.mids.is_empty()
+    }
+
+    /// Check if this license may be used on the machine with `machine_id`
+    ///
+    /// Unbound licenses allow any machine. Bound licenses allow `mid` and
+    /// every entry in `mids`.
+    pub fn allows_machine(&self, machine_id: &str) -> bool {
+        !self.is_machine_bound()
+            || self.mid.as_deref() == Some(machine_id)
+            || self.mids.iter().any(|mid| mid == machine_id)
+    }
+
+    /// Check if this is a trial license (carries the `trial` feature flag)
+    pub fn is_trial(&self) -> bool {
+        self.has_feature(TRIAL_FEATURE)
+    }
+
+    /// Split the granted features into those in `known` and those that are not.
+    ///
+    /// Returns `(granted_known, granted_unknown)`, each in license order.
+    /// Unknown features usually mean the license was issued for a newer
+    /// version of the app.
+    pub fn classify_features(&self, known: &[&str]) -> (Vec<String>, Vec<String>) {
+        classify_features(&self.features, known)
+    }
+
+    /// Issued-at time as a `DateTime<Utc>` (requires `chrono` feature)
+    #[cfg(feature = "chrono")]
+    pub fn issued_at_datetime(&self) -> DateTime<Utc> {
+        millis_to_datetime(self.iat)
+    }
+
+    /// Expiration time as a `DateTime<Utc>`, `None` for perpetual licenses
+    /// (requires `chrono` feature)
+    #[cfg(feature = "chrono")]
+    pub fn expires_at_datetime(&self) -> Option<DateTime<Utc>> {
+        self.exp.map(millis_to_datetime)
+    }
 }
 
 /// A parsed signed license containing header, payload, and signature
@@ -98,6 +274,56 @@ pub struct SignedLicense {
     pub signature: String,
 }
 
+/// Claims a license must satisfy for `verify_license_expecting`
+///
+/// Every field is optional; the default expects nothing beyond a valid license.
+///
+/// ```rust
+/// use std::time::Duration;
+/// use tuish::Expectations;
+///
+/// let expect = Expectations::new()
+///     .product_id("prod_xyz789")
+///     .require_feature("pro")
+///     .min_remaining(Duration::from_secs(7 * 24 * 60 * 60));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Expectations {
+    /// Product the license must be issued for (`pid`)
+    pub product_id: Option<String>,
+
+    /// Features that must be granted and currently active
+    pub require_features: Vec<String>,
+
+    /// Minimum time left before the license expires (perpetual licenses always pass)
+    pub min_remaining: Option<std::time::Duration>,
+}
+
+impl Expectations {
+    /// Expect nothing beyond a valid license
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Require the license to be for `product_id`
+    pub fn product_id(mut self, product_id: impl Into<String>) -> Self {
+        self.product_id = Some(product_id.into());
+        self
+    }
+
+    /// Require `feature` to be granted and active
+    pub fn require_feature(mut self, feature: impl Into<String>) -> Self {
+        self.require_features.push(feature.into());
+        self
+    }
+
+    /// Require at least `remaining` before the license expires
+    pub fn min_remaining(mut self, remaining: std::time::Duration) -> Self {
+        self.min_remaining = Some(remaining);
+        self
+    }
+}
+
 /// License status from API validation
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
@@ -118,18 +344,79 @@ pub struct TuishConfig {
     /// Product ID for this application
     pub product_id: String,
 
-    /// Ed25519 public key for offline license verification (SPKI base64 or hex format)
+    /// Display name for this product, shown for offline results (optional)
+    ///
+    /// Signed licenses carry only the product ID, so offline
+    /// `LicenseDetails::product_name` is this name. Online results use
+    /// the name from the API.
+    pub product_name: Option<String>,
+
+    /// Other product IDs whose licenses also unlock this application
+    ///
+    /// For interchangeable SKUs, e.g. `prod_pro_legacy` alongside
+    /// `prod_pro`. `product_id` is always accepted.
+    pub accepted_product_ids: Vec<String>,
+
+    /// Ed25519 public key for offline license verification (SPKI base64, PEM, or hex format)
     pub public_key: String,
 
     /// API base URL (defaults to production)
     pub api_base_url: String,
 
+    /// Path prefix for self-hosted APIs, placed before `/v1/...` (optional)
+    pub api_prefix: Option<String>,
+
     /// API key for authenticated requests (optional)
     pub api_key: Option<String>,
 
-    /// Custom storage directory (defaults to ~/.tuish/licenses/)
+    /// Custom storage directory (defaults to `TUISH_STORAGE_DIR`, then ~/.tuish/licenses/)
     pub storage_dir: Option<String>,
 
+    /// Keep the previous license when a renewal replaces it (off by default)
+    ///
+    /// See [`storage`](crate::storage#history).
+    pub license_history: bool,
+
+    /// Custom trial marker directory (defaults to the platform local data dir)
+    pub trial_dir: Option<String>,
+
+    /// File to append a JSON audit line to for every license decision (off by default)
+    ///
+    /// See [`audit`](crate::audit).
+    pub audit_log: Option<String>,
+
+    /// Clock skew tolerated when checking a license's `nbf` claim
+    pub clock_skew: std::time::Duration,
+
+    /// How close to expiry a license gets `LicenseWarning::ExpiringSoon`
+    pub expiry_warning: std::time::Duration,
+
+    /// App-specific salt mixed into the machine fingerprint (unsalted by default)
+    ///
+    /// Prevents fingerprint correlation across apps, but changes the
+    /// fingerprint; see [`fingerprint`](crate::fingerprint#salting).
+    pub fingerprint_salt: Option<String>,
+
+    /// Fixed machine fingerprint, used instead of computing one
+    ///
+    /// Takes precedence over `TUISH_MACHINE_FINGERPRINT` and the salt. For
+    /// CI and cloned VM images; it disables real machine binding, see
+    /// [`fingerprint`](crate::fingerprint#override).
+    pub machine_fingerprint_override: Option<String>,
+
+    /// Never contact the network, even with the `http` feature
+    ///
+    /// API calls fail with `TuishError::Offline` before any request is
+    /// made; refreshes keep the cached license, marked `stale`.
+    pub offline_only: bool,
+
+    /// Whether `Tuish::purchase_in_terminal` may prompt through its callbacks
+    ///
+    /// `None` (the default) checks whether stdin is a terminal. When it
+    /// cannot prompt, the OTPs come from `TUISH_LOGIN_OTP` and
+    /// `TUISH_PURCHASE_OTP`, or the flow fails with `TuishError::NotATerminal`.
+    pub interactive: Option<bool>,
+
     /// Enable debug logging
     pub debug: bool,
 }
@@ -139,10 +426,22 @@ impl TuishConfig {
     pub fn new(product_id: impl Into<String>, public_key: impl Into<String>) -> Self {
         Self {
             product_id: product_id.into(),
+            product_name: None,
+            accepted_product_ids: Vec::new(),
             public_key: public_key.into(),
             api_base_url: "https://api.tuish.dev".to_string(),
+            api_prefix: None,
             api_key: None,
             storage_dir: None,
+            license_history: false,
+            trial_dir: None,
+            audit_log: None,
+            clock_skew: crate::crypto::DEFAULT_CLOCK_SKEW,
+            expiry_warning: DEFAULT_EXPIRY_WARNING,
+            fingerprint_salt: None,
+            machine_fingerprint_override: None,
+            offline_only: false,
+            interactive: None,
             debug: false,
         }
     }
@@ -153,6 +452,12 @@ impl TuishConfig {
         self
     }
 
+    /// Serve requests from `{api_base_url}{prefix}/v1/...`
+    pub fn with_api_prefix(mut self, prefix: impl Into<String>) -> Self {
+        self.api_prefix = Some(prefix.into());
+        self
+    }
+
     /// Set the API key
     pub fn with_api_key(mut self, key: impl Into<String>) -> Self {
         self.api_key = Some(key.into());
@@ -165,11 +470,120 @@ impl TuishConfig {
         self
     }
 
+    /// Set the product name used for offline license details
+    pub fn with_product_name(mut self, name: impl Into<String>) -> Self {
+        self.product_name = Some(name.into());
+        self
+    }
+
+    /// Also accept licenses issued for `product_id`
+    pub fn with_accepted_product_id(mut self, product_id: impl Into<String>) -> Self {
+        self.accepted_product_ids.push(product_id.into());
+        self
+    }
+
+    /// Whether a license for `product_id` unlocks this application
+    pub fn accepts_product(&self, product_id: &str) -> bool {
+        product_id == self.product_id || self.accepted_product_ids.iter().any(|id| id == product_id)
+    }
+
+    /// Keep the previous license when a renewal replaces it
+    pub fn with_license_history(mut self, enabled: bool) -> Self {
+        self.license_history = enabled;
+        self
+    }
+
+    /// Set the trial marker directory
+    pub fn with_trial_dir(mut self, dir: impl Into<String>) -> Self {
+        self.trial_dir = Some(dir.into());
+        self
+    }
+
+    /// Set the audit log file
+    pub fn with_audit_log(mut self, path: impl Into<String>) -> Self {
+        self.audit_log = Some(path.into());
+        self
+    }
+
+    /// Set the clock skew tolerated for `nbf`
+    pub fn with_clock_skew(mut self, skew: std::time::Duration) -> Self {
+        self.clock_skew = skew;
+        self
+    }
+
+    /// Set the threshold for `LicenseWarning::ExpiringSoon`
+    pub fn with_expiry_warning(mut self, within: std::time::Duration) -> Self {
+        self.expiry_warning = within;
+        self
+    }
+
+    /// Set the fingerprint salt
+    pub fn with_fingerprint_salt(mut self, salt: impl Into<String>) -> Self {
+        self.fingerprint_salt = Some(salt.into());
+        self
+    }
+
+    /// Pin the machine fingerprint
+    pub fn with_machine_fingerprint_override(mut self, fingerprint: impl Into<String>) -> Self {
+        self.machine_fingerprint_override = Some(fingerprint.into());
+        self
+    }
+
+    /// Disable all network access
+    pub fn with_offline_only(mut self, offline_only: bool) -> Self {
+        self.offline_only = offline_only;
+        self
+    }
+
+    /// Override terminal detection for the terminal purchase flow
+    pub fn with_interactive(mut self, interactive: bool) -> Self {
+        self.interactive = Some(interactive);
+        self
+    }
+
     /// Enable debug logging
     pub fn with_debug(mut self, debug: bool) -> Self {
         self.debug = debug;
         self
     }
+
+    /// This machine's fingerprint, salted with `fingerprint_salt` if set
+    ///
+    /// `machine_fingerprint_override`, if set, is returned instead.
+    pub fn machine_fingerprint(&self) -> String {
+        if let Some(fingerprint) = &self.machine_fingerprint_override {
+            return fingerprint.to_ascii_lowercase();
+        }
+        match self.fingerprint_salt.as_deref() {
+            Some(salt) => crate::fingerprint::get_machine_fingerprint_salted(salt),
+            None => crate::fingerprint::get_machine_fingerprint(),
+        }
+    }
+}
+
+/// Optional SDK features compiled into this build
+///
+/// Lets a single binary branch at runtime, e.g. to hide a "Buy" button when
+/// it was built without `http`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Capabilities {
+    /// Online validation, checkout, and other API calls (`http` feature)
+    pub http: bool,
+    /// Opening checkout URLs in a browser (`browser` feature)
+    pub browser: bool,
+    /// Local license caching (`storage` feature)
+    pub storage: bool,
+}
+
+impl Capabilities {
+    /// The features this build of the SDK was compiled with
+    pub const fn current() -> Self {
+        Self {
+            http: cfg!(feature = "http"),
+            browser: cfg!(feature = "browser"),
+            storage: cfg!(feature = "storage"),
+        }
+    }
 }
 
 // ============================================================================
@@ -177,7 +591,8 @@ impl TuishConfig {
 // ============================================================================
 
 /// Result of a license check operation
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct LicenseCheckResult {
     /// Whether the license is valid
     pub valid: bool,
@@ -188,8 +603,224 @@ pub struct LicenseCheckResult {
     /// Reason for invalid license
     pub reason: Option<crate::error::LicenseInvalidReason>,
 
+    /// Where the result came from (offline verification, API, or cache)
+    pub source: ResultSource,
+
+    /// Whether the cached license was due for an online refresh that failed
+    /// with a network error, so this result comes from the stale cache
+    #[serde(default)]
+    pub stale: bool,
+
+    /// Which machine the license expected, for `MachineMismatch` results
+    /// from offline verification; `None` otherwise
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mismatch_detail: Option<MachineMismatch>,
+
+    /// Something the user should be told about a valid license, set by
+    /// `check_license`; `None` otherwise
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub warning: Option<LicenseWarning>,
+}
+
+/// Reason to nag the user about an otherwise valid license
+///
+/// When several apply, the most urgent is reported: `ExpiringSoon`, then
+/// `UsingStaleCache`, then `SupportLapsed`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LicenseWarning {
+    /// The license expires within `TuishConfig::expiry_warning`; holds the
+    /// time left
+    ExpiringSoon(std::time::Duration),
+    /// The license is valid but its support/updates window has closed
+    SupportLapsed,
+    /// The online refresh could not run, so the result is from a stale cache
+    UsingStaleCache,
+}
+
+/// Expected and actual machine fingerprints behind a `MachineMismatch`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MachineMismatch {
+    /// Fingerprint the license is bound to (`mid`, or the first of `mids`)
+    pub expected: String,
+    /// This machine's fingerprint
+    pub actual: String,
+}
+
+/// What differs between two license check results, from
+/// `LicenseCheckResult::diff`
+///
+/// Features are compared by flag; a result without license details counts
+/// as having none, so losing the license removes every feature.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LicenseChange {
+    /// `valid` differs
+    pub validity_changed: bool,
+
+    /// `reason` differs, e.g. the license became `Expired`
+    pub reason_changed: bool,
+
+    /// Features granted now but not before, e.g. after an upgrade
+    pub features_added: Vec<String>,
+
+    /// Features granted before but not now, e.g. after a downgrade
+    pub features_removed: Vec<String>,
+
+    /// The license's `expires_at` differs, e.g. after a renewal
+    pub expiry_changed: bool,
+}
+
+impl LicenseChange {
+    /// Whether nothing tracked by the diff changed
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// Everything needed to render a license panel, from `Tuish::status`
+///
+/// The fields are computed together from the local cache, so they are
+/// consistent with each other. No network calls are made.
+#[derive(Debug, Clone)]
+pub struct LicenseStatusSnapshot {
+    /// Offline check of the cached license
+    pub result: LicenseCheckResult,
+
+    /// Whether the cached license is due for an online refresh
+    pub needs_refresh: bool,
+
+    /// Time until the license expires; zero once expired, `None` for
+    /// perpetual licenses or when there is no license
+    pub time_until_expiry: Option<std::time::Duration>,
+
+    /// Where `result` came from
+    pub source: ResultSource,
+
+    /// This machine's fingerprint, as used for the check
+    pub fingerprint: String,
+}
+
+/// Where a license check result came from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ResultSource {
+    /// A license key verified offline against the public key
+    Offline,
+    /// Validated by the Tuish API
+    Online,
+    /// No license was available to check
+    NotFound,
+    /// The cached license, verified offline
+    Cache,
+}
+
+impl ResultSource {
+    /// Whether the license was verified locally rather than by the API
+    pub fn is_offline(&self) -> bool {
+        matches!(self, Self::Offline | Self::Cache)
+    }
+}
+
+impl fmt::Display for ResultSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Offline => write!(f, "offline"),
+            Self::Online => write!(f, "online"),
+            Self::NotFound => write!(f, "not_found"),
+            Self::Cache => write!(f, "cache"),
+        }
+    }
+}
+
+impl LicenseCheckResult {
     /// Whether the license was verified offline (true) or via API (false)
-    pub offline_verified: bool,
+    ///
+    /// Derived from `source`; kept for compatibility.
+    pub fn offline_verified(&self) -> bool {
+        self.source.is_offline()
+    }
+
+    /// What changed between `prev` and this result, e.g. to tell the user
+    /// "your plan was upgraded: +analytics" from an `on_change` callback
+    pub fn diff(&self, prev: &Self) -> LicenseChange {
+        let features = |result: &Self| {
+            result
+                .license
+                .as_ref()
+                .map(|l| l.features.as_slice())
+                .unwrap_or_default()
+                .to_vec()
+        };
+        let (now, before) = (features(self), features(prev));
+        let expires_at = |result: &Self| result.license.as_ref().and_then(|l| l.expires_at);
+
+        LicenseChange {
+            validity_changed: self.valid != prev.valid,
+            reason_changed: self.reason != prev.reason,
+            features_added: now
+                .iter()
+                .filter(|f| !before.contains(f))
+                .cloned()
+                .collect(),
+            features_removed: before
+                .iter()
+                .filter(|f| !now.contains(f))
+                .cloned()
+                .collect(),
+            expiry_changed: expires_at(self) != expires_at(prev),
+        }
+    }
+
+    /// One-line human-readable summary, e.g.
+    /// `valid (offline) · product prod_x · expires 2025-01-01` or `invalid: expired`
+    pub fn summary(&self) -> String {
+        if !self.valid {
+            return match &self.reason {
+                Some(reason) => format!("invalid: {}", reason),
+                None => "invalid: no license".to_string(),
+            };
+        }
+
+        let source = match self.source {
+            ResultSource::Online => "online",
+            ResultSource::Cache => "cached",
+            ResultSource::Offline | ResultSource::NotFound => "offline",
+        };
+        let stale = if self.stale { ", stale" } else { "" };
+        let mut summary = format!("valid ({}{})", source, stale);
+        if let Some(license) = &self.license {
+            summary.push_str(&format!(" · product {}", license.product_id));
+            match license.expires_at {
+                Some(expires_at) => {
+                    summary.push_str(&format!(" · expires {}", format_date(expires_at)))
+                }
+                None => summary.push_str(" · perpetual"),
+            }
+        }
+        summary
+    }
+}
+
+impl fmt::Display for LicenseCheckResult {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.summary())
+    }
+}
+
+/// Format a Unix timestamp (ms) as a UTC `YYYY-MM-DD` date.
+fn format_date(millis: i64) -> String {
+    // Civil-from-days conversion (proleptic Gregorian calendar)
+    let days = millis.div_euclid(86_400_000);
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z - era * 146_097;
+    let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    format!("{:04}-{:02}-{:02}", year, month, day)
 }
 
 /// Detailed license information
@@ -217,6 +848,78 @@ pub struct LicenseDetails {
 
     /// Expires at (Unix timestamp ms, null for perpetual)
     pub expires_at: Option<i64>,
+
+    /// End of the support/updates window (Unix timestamp ms, null if none)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub support_expires_at: Option<i64>,
+}
+
+impl LicenseDetails {
+    /// Check if the support/updates window is open
+    ///
+    /// See [`LicensePayload::support_active`].
+    pub fn support_active(&self) -> bool {
+        support_active(self.expires_at, self.support_expires_at)
+    }
+
+    /// Check if this is a trial license (carries the `trial` feature flag)
+    pub fn is_trial(&self) -> bool {
+        self.features.iter().any(|f| f == TRIAL_FEATURE)
+    }
+
+    /// The expiry or support warning for this license, if any
+    ///
+    /// `ExpiringSoon` if it expires within `expiry_warning`, else
+    /// `SupportLapsed` if the support window has closed.
+    pub fn warning(&self, expiry_warning: std::time::Duration) -> Option<LicenseWarning> {
+        if let Some(expires_at) = self.expires_at {
+            let now = std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .map(|d| d.as_millis() as i64)
+                .unwrap_or(0);
+            let remaining =
+                std::time::Duration::from_millis(expires_at.saturating_sub(now).max(0) as u64);
+            if remaining <= expiry_warning {
+                return Some(LicenseWarning::ExpiringSoon(remaining));
+            }
+        }
+        (!self.support_active()).then_some(LicenseWarning::SupportLapsed)
+    }
+
+    /// Split the granted features into known and unknown sets.
+    ///
+    /// See [`LicensePayload::classify_features`].
+    pub fn classify_features(&self, known: &[&str]) -> (Vec<String>, Vec<String>) {
+        classify_features(&self.features, known)
+    }
+}
+
+fn support_active(exp: Option<i64>, support_exp: Option<i64>) -> bool {
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_millis() as i64)
+        .unwrap_or(0);
+    exp.is_none_or(|exp| exp >= now) && support_exp.is_none_or(|end| end >= now)
+}
+
+fn classify_features(features: &[String], known: &[&str]) -> (Vec<String>, Vec<String>) {
+    features
+        .iter()
+        .cloned()
+        .partition(|feature| known.contains(&feature.as_str()))
+}
+
+#[cfg(feature = "chrono")]
+impl LicenseDetails {
+    /// Issued-at time as a `DateTime<Utc>`
+    pub fn issued_at_datetime(&self) -> DateTime<Utc> {
+        millis_to_datetime(self.issued_at)
+    }
+
+    /// Expiration time as a `DateTime<Utc>`, `None` for perpetual licenses
+    pub fn expires_at_datetime(&self) -> Option<DateTime<Utc>> {
+        self.expires_at.map(millis_to_datetime)
+    }
 }
 
 impl From<&LicensePayload> for LicenseDetails {
@@ -235,10 +938,39 @@ impl From<&LicensePayload> for LicenseDetails {
             status,
             issued_at: payload.iat,
             expires_at: payload.exp,
+            support_expires_at: payload.support_exp,
         }
     }
 }
 
+// ============================================================================
+// Revocation List
+// ============================================================================
+
+/// Verified list of revoked license IDs for a product
+///
+/// Obtained from a signed token with `verify_revocation_list`. Lets offline
+/// verification reject licenses that were revoked server-side.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RevocationList {
+    /// Product the list applies to
+    pub product_id: String,
+
+    /// Revoked license IDs (`lid` values)
+    pub revoked_ids: Vec<String>,
+
+    /// When the list was issued (Unix timestamp ms)
+    pub issued_at: i64,
+}
+
+impl RevocationList {
+    /// Check if a license ID is revoked
+    pub fn is_revoked(&self, license_id: &str) -> bool {
+        self.revoked_ids.iter().any(|id| id == license_id)
+    }
+}
+
 // ============================================================================
 // Cached License Data
 // ============================================================================
@@ -248,7 +980,7 @@ impl From<&LicensePayload> for LicenseDetails {
 #[serde(rename_all = "camelCase")]
 pub struct CachedLicenseData {
     /// Raw license key string
-    pub license_key: String,
+    pub license_key: SecretString,
 
     /// When the license was cached (Unix timestamp ms)
     pub cached_at: i64,
@@ -263,6 +995,27 @@ pub struct CachedLicenseData {
     pub machine_fingerprint: String,
 }
 
+#[cfg(feature = "chrono")]
+impl CachedLicenseData {
+    /// When the license was cached, as a `DateTime<Utc>`
+    pub fn cached_at_datetime(&self) -> DateTime<Utc> {
+        millis_to_datetime(self.cached_at)
+    }
+
+    /// When the cache should be refreshed, as a `DateTime<Utc>`
+    pub fn refresh_at_datetime(&self) -> DateTime<Utc> {
+        millis_to_datetime(self.refresh_at)
+    }
+}
+
+/// Convert a millisecond timestamp to `DateTime<Utc>`.
+///
+/// Timestamps outside chrono's representable range map to the Unix epoch.
+#[cfg(feature = "chrono")]
+fn millis_to_datetime(millis: i64) -> DateTime<Utc> {
+    DateTime::from_timestamp_millis(millis).unwrap_or_default()
+}
+
 // ============================================================================
 // API Request/Response Types
 // ============================================================================
@@ -449,6 +1202,12 @@ pub struct PurchaseConfirmRequest {
     pub otp_id: String,
     /// OTP code
     pub otp: String,
+    /// Key that lets the API recognize a retried confirmation
+    ///
+    /// Sent as the `Idempotency-Key` header rather than in the body. When
+    /// unset, `TuishClient::confirm_purchase` generates one per call.
+    #[serde(skip)]
+    pub idempotency_key: Option<String>,
 }
 
 /// Response from purchase confirmation
@@ -474,6 +1233,17 @@ pub struct PurchaseConfirmResponse {
     pub error: Option<String>,
 }
 
+/// A terminal purchase paused for 3D Secure authentication
+///
+/// Carried by `TuishError::PurchaseActionRequired`.
+#[derive(Debug, Clone)]
+pub struct PendingPurchase {
+    /// URL the customer must open to authenticate the payment
+    pub action_url: String,
+    /// Confirmation request to re-send once authentication is complete
+    pub confirm_request: PurchaseConfirmRequest,
+}
+
 // ----------------------------------------------------------------------------
 // License Validation
 // ----------------------------------------------------------------------------
@@ -486,6 +1256,9 @@ pub struct LicenseValidateRequest {
     pub license_key: String,
     /// Machine fingerprint for validation
     pub machine_fingerprint: String,
+    /// Stable installation ID, see `LicenseManager::installation_id`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub installation_id: Option<String>,
 }
 
 /// License information from API
@@ -506,6 +1279,9 @@ pub struct LicenseInfo {
     pub issued_at: i64,
     /// Expires at (Unix timestamp ms, null for perpetual)
     pub expires_at: Option<i64>,
+    /// Support/updates window end (Unix timestamp ms, null if none)
+    #[serde(default)]
+    pub support_expires_at: Option<i64>,
 }
 
 /// API validation reason for invalid license
@@ -532,6 +1308,134 @@ pub struct LicenseValidateResponse {
     pub reason: Option<ApiValidationReason>,
 }
 
+/// Response from the revocation list endpoint
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RevocationListResponse {
+    /// Signed revocation list token (header.payload.signature)
+    pub list: String,
+}
+
+// ----------------------------------------------------------------------------
+// Machine Binding
+// ----------------------------------------------------------------------------
+
+/// Request to release a license's seat on this machine
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LicenseDeactivateRequest {
+    /// License key string
+    pub license_key: String,
+    /// Fingerprint of the machine being released
+    pub machine_fingerprint: String,
+}
+
+/// Response from license deactivation
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LicenseDeactivateResponse {
+    /// Whether the machine seat was released
+    pub deactivated: bool,
+}
+
+/// Request to bind an existing license to a new machine
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LicenseRebindRequest {
+    /// License key string
+    pub license_key: String,
+    /// Fingerprint of the machine to bind to
+    pub machine_fingerprint: String,
+}
+
+/// Response from license rebinding
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LicenseRebindResponse {
+    /// Freshly signed license key bound to the new machine
+    pub license: String,
+}
+
+// ----------------------------------------------------------------------------
+// Trials
+// ----------------------------------------------------------------------------
+
+/// Request to start a trial on this machine
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TrialStartRequest {
+    /// Product ID
+    pub product_id: String,
+    /// Machine fingerprint the trial is bound to
+    pub machine_fingerprint: String,
+}
+
+/// Response from starting a trial
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TrialStartResponse {
+    /// Signed trial license key (carries the `trial` feature flag)
+    pub license: String,
+    /// When the trial ends (Unix timestamp ms)
+    pub expires_at: i64,
+}
+
+// ----------------------------------------------------------------------------
+// Account
+// ----------------------------------------------------------------------------
+
+/// Developer account an API key belongs to
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Developer {
+    /// Developer ID
+    pub id: String,
+    /// Account email
+    pub email: String,
+    /// Display name, if one has been set
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+}
+
+// ----------------------------------------------------------------------------
+// Analytics
+// ----------------------------------------------------------------------------
+
+/// Sales and license summary for a time window
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AnalyticsSummary {
+    /// Window the figures cover (e.g., "7d")
+    pub period: String,
+    /// Licenses currently active
+    pub active_licenses: u64,
+    /// Customers whose first purchase fell in the window
+    pub new_customers: u64,
+    /// Revenue in the window, in cents
+    pub revenue: i64,
+    /// Currency code (e.g., "usd")
+    pub currency: String,
+    /// Per-product breakdown
+    #[serde(default)]
+    pub by_product: Vec<ProductAnalytics>,
+}
+
+/// One product's share of an `AnalyticsSummary`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProductAnalytics {
+    /// Product ID
+    pub product_id: String,
+    /// Product name
+    pub product_name: String,
+    /// Licenses currently active for this product
+    pub active_licenses: u64,
+    /// New customers for this product in the window
+    pub new_customers: u64,
+    /// Revenue for this product in the window, in cents
+    pub revenue: i64,
+}
+
 // ----------------------------------------------------------------------------
 // Usage Tracking
 // ----------------------------------------------------------------------------
@@ -547,6 +1451,9 @@ pub struct UsageRecordRequest {
     /// Idempotency key to prevent duplicates
     #[serde(skip_serializing_if = "Option::is_none")]
     pub idempotency_key: Option<String>,
+    /// Stable installation ID, see `LicenseManager::installation_id`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub installation_id: Option<String>,
 }
 
 /// Response from usage recording
@@ -580,6 +1487,11 @@ mod tests {
             iat: now - 1000,
             exp: Some(now - 500),
             mid: None,
+            mids: vec![],
+            entitlements: vec![],
+            support_exp: None,
+            nbf: None,
+            feature_groups: vec![],
         };
         assert!(expired.is_expired());
 
@@ -593,6 +1505,11 @@ mod tests {
             iat: now - 1000,
             exp: Some(now + 86400000),
             mid: None,
+            mids: vec![],
+            entitlements: vec![],
+            support_exp: None,
+            nbf: None,
+            feature_groups: vec![],
         };
         assert!(!valid.is_expired());
 
@@ -606,10 +1523,77 @@ mod tests {
             iat: now - 1000,
             exp: None,
             mid: None,
+            mids: vec![],
+            entitlements: vec![],
+            support_exp: None,
+            nbf: None,
+            feature_groups: vec![],
         };
         assert!(!perpetual.is_expired());
     }
 
+    #[test]
+    fn test_perpetual_license_support_window() {
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_millis() as i64;
+
+        let active = LicensePayload {
+            lid: "lic_123".into(),
+            pid: "prod_456".into(),
+            cid: "cus_789".into(),
+            did: "dev_012".into(),
+            features: vec![],
+            iat: now - 1000,
+            exp: None,
+            mid: None,
+            mids: vec![],
+            entitlements: vec![],
+            support_exp: Some(now + 86400000),
+            nbf: None,
+            feature_groups: vec![],
+        };
+        assert!(!active.is_expired());
+        assert!(active.support_active());
+        assert!(LicenseDetails::from(&active).support_active());
+
+        // Lapsed support leaves the perpetual license itself valid
+        let lapsed = LicensePayload {
+            support_exp: Some(now - 500),
+            ..active.clone()
+        };
+        assert!(!lapsed.is_expired());
+        assert!(!lapsed.support_active());
+        let details = LicenseDetails::from(&lapsed);
+        assert_eq!(details.status, LicenseStatus::Active);
+        assert!(!details.support_active());
+
+        // Without a window, support follows the license expiry
+        let no_window = LicensePayload {
+            support_exp: None,
+            ..active.clone()
+        };
+        assert!(no_window.support_active());
+        let expired = LicensePayload {
+            exp: Some(now - 500),
+            ..active.clone()
+        };
+        assert!(!expired.support_active());
+
+        // The claim round-trips and is omitted when absent
+        let json = serde_json::to_value(&active).unwrap();
+        assert_eq!(json["support_exp"], now + 86400000);
+        assert!(serde_json::to_value(&no_window)
+            .unwrap()
+            .get("support_exp")
+            .is_none());
+        assert_eq!(
+            serde_json::from_value::<LicensePayload>(json).unwrap(),
+            active
+        );
+    }
+
     #[test]
     fn test_license_payload_has_feature() {
         let payload = LicensePayload {
@@ -621,6 +1605,11 @@ mod tests {
             iat: 0,
             exp: None,
             mid: None,
+            mids: vec![],
+            entitlements: vec![],
+            support_exp: None,
+            nbf: None,
+            feature_groups: vec![],
         };
 
         assert!(payload.has_feature("pro"));
@@ -628,6 +1617,470 @@ mod tests {
         assert!(!payload.has_feature("enterprise"));
     }
 
+    #[test]
+    fn test_license_payload_feature_active() {
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_millis() as i64;
+
+        let mut payload = LicensePayload {
+            lid: "lic_123".into(),
+            pid: "prod_456".into(),
+            cid: "cus_789".into(),
+            did: "dev_012".into(),
+            features: vec!["pro".into(), "cloud".into(), "ai".into()],
+            iat: 0,
+            exp: None,
+            mid: None,
+            mids: vec![],
+            entitlements: vec![
+                Entitlement {
+                    feature: "cloud".into(),
+                    exp: Some(now - 1000),
+                },
+                Entitlement {
+                    feature: "ai".into(),
+                    exp: Some(now + 86400000),
+                },
+            ],
+            support_exp: None,
+            nbf: None,
+            feature_groups: Vec::new(),
+        };
+
+        // Listed but its entitlement has expired
+        assert!(payload.has_feature("cloud"));
+        assert!(!payload.feature_active("cloud"));
+
+        assert!(payload.feature_active("pro"));
+        assert!(payload.feature_active("ai"));
+
+        // An entitlement alone does not grant an unlisted feature
+        payload.entitlements.push(Entitlement {
+            feature: "sso".into(),
+            exp: None,
+        });
+        assert!(!payload.feature_active("sso"));
+
+        // Base expiry disables every feature
+        payload.exp = Some(now - 1000);
+        assert!(!payload.feature_active("pro"));
+        assert!(!payload.feature_active("ai"));
+    }
+
+    #[test]
+    fn test_license_payload_entitlements_serde() {
+        let json = r#"{"lid":"l","pid":"p","cid":"c","did":"d","features":["cloud"],"iat":0,"exp":null,"mid":null,"entitlements":[{"feature":"cloud","exp":1}]}"#;
+        let payload: LicensePayload = serde_json::from_str(json).unwrap();
+        assert_eq!(
+            payload.entitlements,
+            vec![Entitlement {
+                feature: "cloud".into(),
+                exp: Some(1)
+            }]
+        );
+        assert!(!payload.feature_active("cloud"));
+
+        // Omitted when empty, so older payloads round-trip unchanged
+        let plain = LicensePayload {
+            entitlements: vec![],
+            ..payload
+        };
+        assert!(serde_json::to_value(&plain)
+            .unwrap()
+            .get("entitlements")
+            .is_none());
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn test_payload_datetime_accessors() {
+        let payload = LicensePayload {
+            lid: "lic_123".into(),
+            pid: "prod_456".into(),
+            cid: "cus_789".into(),
+            did: "dev_012".into(),
+            features: vec![],
+            iat: 1704067200000,
+            exp: Some(1735689600123),
+            mid: None,
+            mids: vec![],
+            entitlements: vec![],
+            support_exp: None,
+            nbf: None,
+            feature_groups: vec![],
+        };
+
+        let issued = payload.issued_at_datetime();
+        assert_eq!(issued.to_rfc3339(), "2024-01-01T00:00:00+00:00");
+        assert_eq!(issued.timestamp_millis(), payload.iat);
+
+        let expires = payload.expires_at_datetime().unwrap();
+        assert_eq!(expires.to_rfc3339(), "2025-01-01T00:00:00.123+00:00");
+        assert_eq!(expires.timestamp_millis(), payload.exp.unwrap());
+
+        let perpetual = LicensePayload {
+            exp: None,
+            ..payload
+        };
+        assert!(perpetual.expires_at_datetime().is_none());
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn test_details_and_cache_datetime_accessors() {
+        let details = LicenseDetails {
+            id: "lic_123".into(),
+            product_id: "prod_456".into(),
+            product_name: None,
+            features: vec![],
+            status: LicenseStatus::Active,
+            issued_at: 0,
+            expires_at: Some(86_400_000),
+            support_expires_at: None,
+        };
+        assert_eq!(
+            details.issued_at_datetime().to_rfc3339(),
+            "1970-01-01T00:00:00+00:00"
+        );
+        assert_eq!(
+            details.expires_at_datetime().unwrap().to_rfc3339(),
+            "1970-01-02T00:00:00+00:00"
+        );
+
+        let cached = CachedLicenseData {
+            license_key: "key".into(),
+            cached_at: 1704067200000,
+            refresh_at: 1704153600000,
+            product_id: "prod_456".into(),
+            machine_fingerprint: "fp".into(),
+        };
+        assert_eq!(
+            cached.cached_at_datetime().timestamp_millis(),
+            cached.cached_at
+        );
+        assert_eq!(
+            cached.refresh_at_datetime().to_rfc3339(),
+            "2024-01-02T00:00:00+00:00"
+        );
+    }
+
+    fn sample_details(expires_at: Option<i64>) -> LicenseDetails {
+        LicenseDetails {
+            id: "lic_123".into(),
+            product_id: "prod_x".into(),
+            product_name: None,
+            features: vec![],
+            status: LicenseStatus::Active,
+            issued_at: 1704067200000,
+            expires_at,
+            support_expires_at: None,
+        }
+    }
+
+    #[test]
+    fn test_check_result_summary_valid() {
+        let result = LicenseCheckResult {
+            valid: true,
+            license: Some(sample_details(Some(1735689600000))),
+            reason: None,
+            source: ResultSource::Offline,
+            stale: false,
+            mismatch_detail: None,
+            warning: None,
+        };
+        assert_eq!(
+            result.summary(),
+            "valid (offline) · product prod_x · expires 2025-01-01"
+        );
+        assert_eq!(result.to_string(), result.summary());
+
+        let perpetual = LicenseCheckResult {
+            license: Some(sample_details(None)),
+            source: ResultSource::Online,
+            ..result
+        };
+        assert_eq!(
+            perpetual.summary(),
+            "valid (online) · product prod_x · perpetual"
+        );
+
+        let stale = LicenseCheckResult {
+            source: ResultSource::Cache,
+            stale: true,
+            mismatch_detail: None,
+            warning: None,
+            ..perpetual
+        };
+        assert_eq!(
+            stale.summary(),
+            "valid (cached, stale) · product prod_x · perpetual"
+        );
+    }
+
+    #[test]
+    fn test_check_result_summary_invalid_with_reason() {
+        let result = LicenseCheckResult {
+            valid: false,
+            license: Some(sample_details(Some(0))),
+            reason: Some(crate::error::LicenseInvalidReason::Expired),
+            source: ResultSource::Offline,
+            stale: false,
+            mismatch_detail: None,
+            warning: None,
+        };
+        assert_eq!(result.summary(), "invalid: expired");
+        assert_eq!(format!("{}", result), "invalid: expired");
+    }
+
+    #[test]
+    fn test_check_result_summary_no_license() {
+        let result = LicenseCheckResult {
+            valid: false,
+            license: None,
+            reason: None,
+            source: ResultSource::NotFound,
+            stale: false,
+            mismatch_detail: None,
+            warning: None,
+        };
+        assert_eq!(result.summary(), "invalid: no license");
+    }
+
+    fn valid_with_features(features: &[&str]) -> LicenseCheckResult {
+        LicenseCheckResult {
+            valid: true,
+            license: Some(LicenseDetails {
+                features: features.iter().map(|f| f.to_string()).collect(),
+                ..sample_details(Some(1735689600000))
+            }),
+            reason: None,
+            source: ResultSource::Cache,
+            stale: false,
+            mismatch_detail: None,
+            warning: None,
+        }
+    }
+
+    #[test]
+    fn test_diff_upgrade() {
+        let before = valid_with_features(&["pro"]);
+        let after = valid_with_features(&["pro", "analytics"]);
+
+        let change = after.diff(&before);
+        assert_eq!(change.features_added, vec!["analytics"]);
+        assert!(change.features_removed.is_empty());
+        assert!(!change.validity_changed && !change.reason_changed && !change.expiry_changed);
+        assert!(after.diff(&after).is_empty());
+    }
+
+    #[test]
+    fn test_diff_downgrade() {
+        let before = valid_with_features(&["pro", "analytics"]);
+        let after = valid_with_features(&["basic"]);
+
+        let change = after.diff(&before);
+        assert_eq!(change.features_added, vec!["basic"]);
+        assert_eq!(change.features_removed, vec!["pro", "analytics"]);
+        assert!(!change.validity_changed);
+    }
+
+    #[test]
+    fn test_diff_expiry() {
+        let before = valid_with_features(&["pro"]);
+        let expired = LicenseCheckResult {
+            valid: false,
+            reason: Some(crate::error::LicenseInvalidReason::Expired),
+            ..before.clone()
+        };
+
+        let change = expired.diff(&before);
+        assert!(change.validity_changed);
+        assert!(change.reason_changed);
+        assert!(!change.expiry_changed);
+        assert!(change.features_removed.is_empty());
+
+        // Renewal pushes the expiry out
+        let mut renewed = before.clone();
+        renewed.license.as_mut().unwrap().expires_at = Some(1767225600000);
+        let change = renewed.diff(&expired);
+        assert!(change.validity_changed && change.expiry_changed);
+
+        // Losing the license details drops every feature
+        let gone = LicenseCheckResult {
+            license: None,
+            ..expired.clone()
+        };
+        assert_eq!(gone.diff(&before).features_removed, vec!["pro"]);
+    }
+
+    #[test]
+    fn test_result_source_offline_verified() {
+        assert!(ResultSource::Offline.is_offline());
+        assert!(ResultSource::Cache.is_offline());
+        assert!(!ResultSource::Online.is_offline());
+        assert!(!ResultSource::NotFound.is_offline());
+        assert_eq!(ResultSource::NotFound.to_string(), "not_found");
+        assert_eq!(
+            serde_json::to_value(ResultSource::NotFound).unwrap(),
+            "not_found"
+        );
+    }
+
+    #[test]
+    fn test_check_result_serde_roundtrip() {
+        let result = LicenseCheckResult {
+            valid: false,
+            license: Some(sample_details(Some(1735689600000))),
+            reason: Some(crate::error::LicenseInvalidReason::MachineMismatch),
+            source: ResultSource::Cache,
+            stale: false,
+            mismatch_detail: None,
+            warning: None,
+        };
+
+        let json = serde_json::to_value(&result).unwrap();
+        assert_eq!(json["valid"], false);
+        assert_eq!(json["reason"], "machine_mismatch");
+        assert_eq!(json["source"], "cache");
+        assert_eq!(json["license"]["productId"], "prod_x");
+
+        let parsed: LicenseCheckResult = serde_json::from_value(json).unwrap();
+        assert!(!parsed.valid);
+        assert_eq!(parsed.reason, result.reason);
+        assert_eq!(parsed.source, ResultSource::Cache);
+        assert!(parsed.offline_verified());
+        assert_eq!(parsed.license.unwrap().expires_at, Some(1735689600000));
+    }
+
+    #[test]
+    fn test_classify_features_overlapping() {
+        let payload = LicensePayload {
+            lid: "lic_123".into(),
+            pid: "prod_456".into(),
+            cid: "cus_789".into(),
+            did: "dev_012".into(),
+            features: vec!["pro".into(), "sync".into(), "ai".into()],
+            iat: 0,
+            exp: None,
+            mid: None,
+            mids: vec![],
+            entitlements: vec![],
+            support_exp: None,
+            nbf: None,
+            feature_groups: vec![],
+        };
+
+        let (known, unknown) = payload.classify_features(&["pro", "team", "ai"]);
+        assert_eq!(known, vec!["pro", "ai"]);
+        assert_eq!(unknown, vec!["sync"]);
+
+        let details = LicenseDetails::from(&payload);
+        assert_eq!(
+            details.classify_features(&["pro", "team", "ai"]),
+            (known, unknown)
+        );
+    }
+
+    #[test]
+    fn test_feature_groups_expand() {
+        let payload = LicensePayload {
+            lid: "lic_123".into(),
+            pid: "prod_456".into(),
+            cid: "cus_789".into(),
+            did: "dev_012".into(),
+            features: vec!["export".into()],
+            iat: 0,
+            exp: None,
+            mid: None,
+            mids: vec![],
+            entitlements: vec![],
+            support_exp: None,
+            nbf: None,
+            feature_groups: vec!["pro".into(), "legacy".into()],
+        };
+        let groups = HashMap::from([
+            (
+                "pro".to_string(),
+                vec!["sync".to_string(), "export".to_string(), "ai".to_string()],
+            ),
+            ("team".to_string(), vec!["sso".to_string()]),
+        ]);
+
+        assert_eq!(
+            payload.effective_features(&groups),
+            vec!["export", "sync", "ai"]
+        );
+        assert_eq!(payload.effective_features(&HashMap::new()), vec!["export"]);
+
+        assert!(payload.has_feature_in_groups("export", &groups));
+        assert!(payload.has_feature_in_groups("ai", &groups));
+        assert!(!payload.has_feature_in_groups("sso", &groups));
+        assert!(!payload.has_feature_in_groups("ai", &HashMap::new()));
+        assert!(!payload.has_feature("ai"));
+
+        // Round-trips under the `feature_groups` key, omitted when empty
+        let json = serde_json::to_value(&payload).unwrap();
+        assert_eq!(json["feature_groups"], serde_json::json!(["pro", "legacy"]));
+        let plain = LicensePayload {
+            feature_groups: vec![],
+            ..payload
+        };
+        assert!(serde_json::to_value(&plain)
+            .unwrap()
+            .get("feature_groups")
+            .is_none());
+    }
+
+    #[test]
+    fn test_classify_features_disjoint() {
+        let details = LicenseDetails {
+            features: vec!["beta".into(), "export".into()],
+            ..sample_details(None)
+        };
+
+        let (known, unknown) = details.classify_features(&["pro", "team"]);
+        assert!(known.is_empty());
+        assert_eq!(unknown, vec!["beta", "export"]);
+
+        let (known, unknown) = details.classify_features(&[]);
+        assert!(known.is_empty());
+        assert_eq!(unknown.len(), 2);
+
+        let none = sample_details(None);
+        assert_eq!(none.classify_features(&["pro"]), (vec![], vec![]));
+    }
+
+    #[test]
+    fn test_is_trial() {
+        let details = LicenseDetails {
+            features: vec!["pro".into(), TRIAL_FEATURE.into()],
+            ..sample_details(None)
+        };
+        assert!(details.is_trial());
+        assert!(!sample_details(None).is_trial());
+    }
+
+    #[test]
+    fn test_revocation_list_is_revoked() {
+        let list = RevocationList {
+            product_id: "prod_x".into(),
+            revoked_ids: vec!["lic_bad".into(), "lic_worse".into()],
+            issued_at: 0,
+        };
+        assert!(list.is_revoked("lic_bad"));
+        assert!(!list.is_revoked("lic_good"));
+    }
+
+    #[test]
+    fn test_format_date() {
+        assert_eq!(format_date(0), "1970-01-01");
+        assert_eq!(format_date(951782400000), "2000-02-29");
+        assert_eq!(format_date(1735689599999), "2024-12-31");
+        assert_eq!(format_date(-86_400_000), "1969-12-31");
+    }
+
     #[test]
     fn test_tuish_config_builder() {
         let config = TuishConfig::new("prod_123", "abc123hex")
@@ -640,6 +2093,24 @@ mod tests {
         assert_eq!(config.api_base_url, "https://custom.api.com");
         assert_eq!(config.api_key, Some("sk_test_key".to_string()));
         assert!(config.debug);
+        assert!(config.fingerprint_salt.is_none());
+    }
+
+    #[test]
+    fn test_config_machine_fingerprint_salt() {
+        let unsalted = TuishConfig::new("prod_123", "abc123hex");
+        assert_eq!(
+            unsalted.machine_fingerprint(),
+            crate::fingerprint::get_machine_fingerprint()
+        );
+
+        let salted = unsalted.clone().with_fingerprint_salt("app-one");
+        assert_eq!(salted.machine_fingerprint(), salted.machine_fingerprint());
+        assert_ne!(salted.machine_fingerprint(), unsalted.machine_fingerprint());
+        assert_eq!(
+            salted.machine_fingerprint(),
+            crate::fingerprint::get_machine_fingerprint_salted("app-one")
+        );
     }
 
     #[test]
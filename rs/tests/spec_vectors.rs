@@ -3,7 +3,9 @@ use sha2::{Digest, Sha256};
 use std::fs;
 use std::path::{Path, PathBuf};
 
-use tuish::{verify_license, LicensePayload, LicenseStorage, TuishError};
+#[cfg(feature = "storage")]
+use tuish::LicenseStorage;
+use tuish::{verify_license, LicenseInvalidReason, LicensePayload, TuishError};
 
 #[derive(Deserialize)]
 struct LicenseVectors {
@@ -60,6 +62,7 @@ struct MapEntry {
     expected: String,
 }
 
+#[cfg(feature = "storage")]
 #[derive(Deserialize)]
 struct CacheVectors {
     product_id: String,
@@ -67,6 +70,7 @@ struct CacheVectors {
     cases: Vec<CacheCase>,
 }
 
+#[cfg(feature = "storage")]
 #[derive(Deserialize)]
 struct CacheCase {
     name: String,
@@ -166,14 +170,10 @@ fn map_arch(value: &str) -> String {
     }
 }
 
-fn reason_from_error(err: &TuishError) -> &'static str {
-    match err {
-        TuishError::InvalidLicense(_) | TuishError::ParseError(_) => "invalid_format",
-        TuishError::InvalidSignature => "invalid_signature",
-        TuishError::ExpiredLicense => "expired",
-        TuishError::InvalidMachineId => "machine_mismatch",
-        _ => "invalid_format",
-    }
+fn reason_from_error(err: &TuishError) -> String {
+    LicenseInvalidReason::from_error(err)
+        .unwrap_or(LicenseInvalidReason::InvalidFormat)
+        .to_string()
 }
 
 fn evaluate_flow(input: FlowInput) -> FlowOutput {
@@ -337,8 +337,7 @@ fn spec_license_vectors() {
             let actual_reason = result
                 .as_ref()
                 .err()
-                .map(reason_from_error)
-                .unwrap_or("valid");
+                .map_or_else(|| "valid".to_string(), reason_from_error);
             assert_eq!(expected_reason, actual_reason, "case {}", case.name);
         }
         if let Some(expected_payload) = case.expected.payload {
@@ -381,6 +380,7 @@ fn spec_fingerprint_vectors() {
     assert_eq!(tuish::get_machine_fingerprint(), sha256_hex(&runtime_components));
 }
 
+#[cfg(feature = "storage")]
 #[test]
 fn spec_cache_vectors() {
     let vectors: CacheVectors = read_json(&vectors_dir().join("cache.json"));
@@ -406,7 +406,7 @@ fn spec_cache_vectors() {
 
     for case in vectors.cases {
         let cached = tuish::CachedLicenseData {
-            license_key: "license-test".to_string(),
+            license_key: "license-test".into(),
             cached_at: case.cached_at,
             refresh_at: case.refresh_at,
             product_id: vectors.product_id.clone(),